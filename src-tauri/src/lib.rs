@@ -1,146 +1,420 @@
 #[cfg(target_os = "macos")]
 use dirs;
 use once_cell::sync::Lazy;
-use reqwest;
-use serde_json;
 use std::collections::HashMap;
 use std::fs::{self};
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tauri::{
-    menu::{MenuBuilder, MenuItemBuilder},
     tray::{TrayIconBuilder, TrayIconEvent},
-    AppHandle, Emitter, Manager, WindowEvent,
+    AppHandle, Manager, WindowEvent,
 };
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 use tokio::time;
 pub mod mods;
+use mods::api::{EgdataApi, HttpEgdataApi, ManifestUpload};
+use mods::events::AppEvent;
 use mods::models::*;
+use mods::schedule::IntervalSchedule;
 use mods::state::*;
 use mods::utils::*;
 
 static HTTP_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    base_client_builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .expect("Failed to create HTTP client")
+});
+
+// Third-party requests (Epic catalog, GitHub) don't carry the egdata client id
+static EXTERNAL_HTTP_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
     reqwest::Client::builder()
+        .user_agent(user_agent())
         .timeout(Duration::from_secs(10))
         .build()
         .expect("Failed to create HTTP client")
 });
 
-pub async fn upload_manifest_internal(game: &GameInfo) -> Result<UploadStatus, String> {
-    let manifests_path = get_manifests_path();
-    let item_path = manifests_path.join(format!("{}.item", game.installation_guid));
-    let manifest_path = std::path::PathBuf::from(format!(
-        "{}/.egstore/{}.manifest",
-        game.install_location.replace("\\", "/"),
-        game.installation_guid
-    ));
-
-    // Read files first to get manifest hash from .item file
-    let item_bytes =
-        fs::read(&item_path).map_err(|e| format!("Failed to read .item file: {}", e))?;
-    let manifest_bytes =
-        fs::read(&manifest_path).map_err(|e| format!("Failed to read .manifest file: {}", e))?;
+const BUILDS_API_URL: &str = "https://egdata-builds-api.snpm.workers.dev";
+
+// Maximum number of `.item` files parsed concurrently during a scan
+const SCAN_PARALLELISM: usize = 8;
+
+pub async fn upload_manifest_internal(
+    game: &GameInfo,
+    settings: &Settings,
+) -> Result<UploadStatus, String> {
+    upload_manifest_with(&HttpEgdataApi, game, settings).await
+}
+
+// Prefers the file on disk; after an uninstall only the scanned copy is left.
+// The read backs off while the launcher writes, so it runs on the blocking pool.
+async fn read_item_text(game: &GameInfo) -> Result<String, String> {
+    let item_path = get_item_path(game);
+    let read_path = item_path.clone();
+    let read = tokio::task::spawn_blocking(move || read_stable_file(&read_path))
+        .await
+        .map_err(|e| format!("Failed to read .item file: {}", e))?;
+    match read {
+        Ok(bytes) => decode_text_file(&item_path, &bytes),
+        Err(_) if !game.item_content.is_empty() => Ok(game.item_content.clone()),
+        Err(e) => Err(format!("Failed to read .item file: {}", e)),
+    }
+}
+
+pub async fn upload_manifest_with(
+    api: &impl EgdataApi,
+    game: &GameInfo,
+    settings: &Settings,
+) -> Result<UploadStatus, String> {
+    let manifest_path =
+        egstore_manifest_path(&game.install_location, &game.installation_guid).await;
+    let item_text = read_item_text(game).await?;
+
+    let result = upload_manifest_files(api, game, &item_text, manifest_path, settings).await;
+    mods::retry_queue::record(game, &result);
+
+    match &result {
+        Ok(status) if status.status == "uploaded" => mods::webhooks::dispatch(
+            settings,
+            "upload-completed",
+            serde_json::json!({ "game": game, "status": status }),
+        ),
+        Ok(status) if status.status == "failed" => mods::webhooks::dispatch(
+            settings,
+            "upload-failed",
+            serde_json::json!({ "game": game, "status": status }),
+        ),
+        Ok(_) => {}
+        Err(e) => mods::webhooks::dispatch(
+            settings,
+            "upload-failed",
+            serde_json::json!({ "game": game, "error": e }),
+        ),
+    }
+
+    // Already-present manifests are routine and would drown out the timeline
+    let outcome = match &result {
+        Ok(status) if status.status == "uploaded" => {
+            Some(("upload", "Uploaded manifest".to_string()))
+        }
+        Ok(status) if status.status == "failed" => Some((
+            "upload-failed",
+            format!(
+                "Manifest upload failed: {}",
+                status.message.as_deref().unwrap_or("unknown error")
+            ),
+        )),
+        Ok(_) => None,
+        Err(e) => Some(("upload-failed", format!("Manifest upload failed: {}", e))),
+    };
+    if let Some((kind, outcome)) = outcome {
+        mods::activity::record(
+            kind,
+            &format!("{}: {}", game.display_name, outcome),
+            Some(&game.app_name),
+        );
+    }
+
+    result
+}
+
+// Launcher manifests run to a few MB, the largest tens of MB
+pub const MAX_MANIFEST_BYTES: u64 = 64 * 1024 * 1024;
+// First bytes of a binary launcher manifest (0x44BEC00C, little-endian)
+const BINARY_MANIFEST_MAGIC: [u8; 4] = [0x0C, 0xC0, 0xBE, 0x44];
+
+// Rejects a manifest by size before it's read: empty or implausibly large
+fn check_manifest_size(size: u64) -> Result<(), String> {
+    if size == 0 {
+        return Err("Manifest file is empty".to_string());
+    }
+    if size > MAX_MANIFEST_BYTES {
+        return Err(format!(
+            "Manifest file is {} bytes, over the {} byte limit",
+            size, MAX_MANIFEST_BYTES
+        ));
+    }
+    Ok(())
+}
+
+// Rejects files that can't be a launcher manifest: empty, or neither a binary
+// manifest nor the older JSON format
+fn check_manifest_bytes(bytes: &[u8]) -> Result<(), String> {
+    if bytes.is_empty() {
+        return Err("Manifest file is empty".to_string());
+    }
+    let is_json = bytes
+        .iter()
+        .find(|b| !b.is_ascii_whitespace())
+        .is_some_and(|b| *b == b'{');
+    if !bytes.starts_with(&BINARY_MANIFEST_MAGIC) && !is_json {
+        return Err("Manifest file does not start with a manifest header".to_string());
+    }
+    Ok(())
+}
+
+// Uploads an explicit `.item`/`.manifest` pair; `game` is the parsed `.item`
+pub async fn upload_manifest_files(
+    api: &impl EgdataApi,
+    game: &GameInfo,
+    item_text: &str,
+    manifest_path: std::path::PathBuf,
+    settings: &Settings,
+) -> Result<UploadStatus, String> {
+    let manifest_size = file_size_with_timeout(manifest_path.clone())
+        .await
+        .map_err(|e| format!("Failed to read .manifest file: {}", e))?;
+    let manifest_bytes = match check_manifest_size(manifest_size) {
+        Ok(()) => read_sized_file_with_timeout(manifest_path, manifest_size)
+            .await
+            .map_err(|e| format!("Failed to read .manifest file: {}", e))?,
+        Err(e) => {
+            return Ok(UploadStatus {
+                status: "invalid_manifest".to_string(),
+                message: Some(e),
+                manifest_hash: Some(game.manifest_hash.clone()),
+                endpoint: None,
+                error_code: None,
+                metrics: None,
+            })
+        }
+    };
+    if let Err(e) = check_manifest_bytes(&manifest_bytes) {
+        return Ok(UploadStatus {
+            status: "invalid_manifest".to_string(),
+            message: Some(e),
+            manifest_hash: Some(game.manifest_hash.clone()),
+            endpoint: None,
+            error_code: None,
+            metrics: None,
+        });
+    }
 
     // Parse .item file to get ManifestHash
-    let item_json: serde_json::Value = serde_json::from_slice(&item_bytes)
+    let item_json: serde_json::Value = serde_json::from_str(item_text)
         .map_err(|e| format!("Failed to parse .item file: {}", e))?;
     let manifest_hash = item_json["ManifestHash"]
         .as_str()
         .ok_or("ManifestHash not found in .item file")?;
 
-    // Prepare multipart form
-    let manifest_filename = format!("{}.manifest", game.installation_guid);
-    let os_field = if cfg!(target_os = "macos") {
-        "Mac"
-    } else {
-        "Windows"
+    // A manifest that doesn't match its `.item` is corrupt or mid-update;
+    // uploading it would put a wrong build in the dataset
+    let (manifest_bytes, actual_hash) =
+        mods::companions::content_hash_in_background(manifest_bytes).await?;
+    if !actual_hash.eq_ignore_ascii_case(manifest_hash) {
+        return Ok(UploadStatus {
+            status: "hash_mismatch".to_string(),
+            message: Some(format!(
+                "Manifest hash {} does not match ManifestHash {} in the .item file",
+                actual_hash, manifest_hash
+            )),
+            manifest_hash: Some(manifest_hash.to_string()),
+            endpoint: None,
+            error_code: None,
+            metrics: None,
+        });
+    }
+
+    let platform = detect_upload_platform(game);
+    let upload = ManifestUpload {
+        // The file as written (minus BOM/encoding), so the server archives
+        // the launcher's field order and any fields we don't model
+        item_json: item_text.to_string(),
+        manifest_hash: manifest_hash.to_string(),
+        environment: game.environment.clone(),
+        manifest_filename: format!("{}.manifest", game.installation_guid),
+        kind: "manifest".to_string(),
+        manifest_bytes,
+        os: platform.os.to_string(),
+        runtime: platform.runtime.to_string(),
+        arch: upload_architecture(game),
+        launcher_version: mods::launcher::get_launcher_version(),
+        host: settings.share_host_details.then(mods::host::host_details),
+        first_seen: mods::sightings::first_seen(&game.app_name, &game.manifest_hash),
+        chunks: mods::chunks::chunk_availability(game).await,
     };
-    let form = reqwest::multipart::Form::new()
-        .text("item", item_json.to_string())
-        .text("os", os_field)
-        .part(
-            "manifest",
-            reqwest::multipart::Part::bytes(manifest_bytes).file_name(manifest_filename),
-        );
 
-    // Send request
-    let client = reqwest::Client::new();
-    let resp = client
-        .post("https://egdata-builds-api.snpm.workers.dev/upload-manifest")
-        .multipart(form)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to send upload request: {}", e))?;
+    let result = api.upload_manifest(upload, settings).await;
+    if let Ok(status) = &result {
+        mods::ledger::record(game, status);
+    }
+    result
+}
 
-    let status = resp.status();
-    let text = resp.text().await.unwrap_or_default();
+struct UploadPlatform {
+    // Platform the build targets, as expected by the builds API
+    os: &'static str,
+    // "native", or "wine" when a Windows build is run through Wine/Proton
+    runtime: &'static str,
+}
 
-    if status.is_success() {
-        Ok(UploadStatus {
-            status: "uploaded".to_string(),
-            message: Some(text),
-            manifest_hash: Some(manifest_hash.to_string()),
-        })
+fn detect_upload_platform(game: &GameInfo) -> UploadPlatform {
+    if cfg!(target_os = "macos") {
+        UploadPlatform {
+            os: "Mac",
+            runtime: "native",
+        }
+    } else if cfg!(target_os = "windows") {
+        UploadPlatform {
+            os: "Windows",
+            runtime: "native",
+        }
+    } else if game.wine_prefix.is_some() || is_wine_install(&game.install_location) {
+        UploadPlatform {
+            os: "Windows",
+            runtime: "wine",
+        }
     } else {
-        // Check if the error is about identical content already existing
-        if text.contains("A manifest file with identical content already exists") {
-            println!("err: {}", text);
-            return Ok(UploadStatus {
-                status: "already_uploaded".to_string(),
-                message: Some("Manifest with identical content already exists".to_string()),
-                manifest_hash: Some(manifest_hash.to_string()),
-            });
+        UploadPlatform {
+            os: "Linux",
+            runtime: "native",
         }
+    }
+}
 
-        Ok(UploadStatus {
-            status: "failed".to_string(),
-            message: Some(text),
-            manifest_hash: Some(manifest_hash.to_string()),
-        })
+// Scanned games carry their architecture; games parsed for a one-off upload
+// are detected here
+fn upload_architecture(game: &GameInfo) -> String {
+    if game.architecture.is_empty() {
+        mods::arch::detect_architecture(game)
+    } else {
+        game.architecture.clone()
     }
 }
 
-async fn fetch_game_metadata(catalog_item_id: &str, cache: &MetadataCache) -> Option<GameMetadata> {
+// Manifests written by a launcher under Wine/Proton keep Windows-style paths
+fn is_wine_install(install_location: &str) -> bool {
+    let bytes = install_location.as_bytes();
+    let has_drive_letter = bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':';
+    has_drive_letter || install_location.contains("drive_c")
+}
+
+pub async fn fetch_game_metadata(
+    catalog_item_id: &str,
+    cache: &MetadataCache,
+    timeout: Duration,
+) -> Option<GameMetadata> {
+    fetch_game_metadata_with(&HttpEgdataApi, catalog_item_id, cache, timeout).await
+}
+
+pub async fn fetch_game_metadata_with(
+    api: &impl EgdataApi,
+    catalog_item_id: &str,
+    cache: &MetadataCache,
+    timeout: Duration,
+) -> Option<GameMetadata> {
     // Check cache first
     {
-        let cache_lock = cache.lock().ok()?;
-        if let Some(cached_metadata) = cache_lock.get(catalog_item_id) {
-            return Some(cached_metadata.clone());
+        let mut cache_lock = cache.lock().ok()?;
+        if let Some(cached_metadata) = cache_lock.lookup(catalog_item_id) {
+            return Some(cached_metadata);
         }
     }
+    fetch_into_cache(api, catalog_item_id, cache, timeout, false).await
+}
 
-    // Fetch from API
-    let url = format!("https://api.egdata.app/items/{}", catalog_item_id);
+// Asks the server again even when the item is cached, here and in the HTTP
+// cache; for explicit and scheduled refreshes
+pub async fn refresh_game_metadata(
+    catalog_item_id: &str,
+    cache: &MetadataCache,
+    timeout: Duration,
+) -> Option<GameMetadata> {
+    fetch_into_cache(&HttpEgdataApi, catalog_item_id, cache, timeout, true).await
+}
 
-    match HTTP_CLIENT.get(&url).send().await {
-        Ok(response) => {
-            if response.status().is_success() {
-                match response.json::<GameMetadata>().await {
-                    Ok(metadata) => {
-                        // Cache the result
-                        if let Ok(mut cache_lock) = cache.lock() {
-                            cache_lock.insert(catalog_item_id.to_string(), metadata.clone());
-                        }
-                        Some(metadata)
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to parse metadata for {}: {}", catalog_item_id, e);
-                        None
-                    }
-                }
-            } else {
-                eprintln!(
-                    "API request failed for {}: {}",
-                    catalog_item_id,
-                    response.status()
-                );
-                None
+async fn fetch_into_cache(
+    api: &impl EgdataApi,
+    catalog_item_id: &str,
+    cache: &MetadataCache,
+    timeout: Duration,
+    revalidate: bool,
+) -> Option<GameMetadata> {
+    let fetched = match api.fetch_item(catalog_item_id, timeout, revalidate).await {
+        Ok(metadata) => {
+            // Cache the result
+            if let Ok(mut cache_lock) = cache.lock() {
+                cache_lock.insert(catalog_item_id.to_string(), metadata.clone());
             }
+            Some(metadata)
         }
         Err(e) => {
             eprintln!("Failed to fetch metadata for {}: {}", catalog_item_id, e);
             None
         }
+    };
+
+    mods::telemetry::record_api_request(fetched.is_some());
+    fetched
+}
+
+async fn fetch_epic_catalog_metadata(
+    catalog_namespace: &str,
+    catalog_item_id: &str,
+    timeout: Duration,
+) -> Option<GameMetadata> {
+    let url = format!(
+        "https://catalog-public-service-prod06.ol.epicgames.com/catalog/api/shared/namespace/{}/bulk/items?id={}&country=US&locale=en-US",
+        catalog_namespace, catalog_item_id
+    );
+
+    let response = EXTERNAL_HTTP_CLIENT
+        .get(&url)
+        .timeout(timeout)
+        .send()
+        .await
+        .ok()?;
+    if !response.status().is_success() {
+        eprintln!(
+            "Epic catalog request failed for {}: {}",
+            catalog_item_id,
+            response.status()
+        );
+        return None;
+    }
+
+    let mut items = response
+        .json::<HashMap<String, GameMetadata>>()
+        .await
+        .map_err(|e| {
+            eprintln!(
+                "Failed to parse Epic catalog item {}: {}",
+                catalog_item_id, e
+            )
+        })
+        .ok()?;
+    items.remove(catalog_item_id)
+}
+
+// Used when the egdata API can't provide metadata, so the library still shows
+// something meaningful. Fallback entries are never written to the cache, which
+// lets the next scan pick up the real metadata once the API is back.
+async fn fallback_game_metadata(game: &GameInfo, settings: &Settings) -> GameMetadata {
+    if settings.epic_catalog_fallback {
+        if let Some(mut metadata) = fetch_epic_catalog_metadata(
+            &game.catalog_namespace,
+            &game.catalog_item_id,
+            settings.metadata_timeout(),
+        )
+        .await
+        {
+            metadata.fallback = true;
+            return metadata;
+        }
+    }
+
+    GameMetadata {
+        id: game.catalog_item_id.clone(),
+        title: game.display_name.clone(),
+        description: String::new(),
+        key_images: Vec::new(),
+        developer: None,
+        developer_id: None,
+        fallback: true,
     }
 }
 
@@ -161,45 +435,316 @@ fn get_manifests_path() -> std::path::PathBuf {
     }
 }
 
-pub async fn scan_epic_games_with_metadata(
+// All places `.item` files are read from: the native launcher directory and,
+// on Linux, launchers installed inside Wine/Proton prefixes
+fn get_manifest_sources() -> Vec<ManifestSource> {
+    #[cfg(target_os = "linux")]
+    let wine_prefixes = mods::wine::find_wine_prefixes();
+    #[cfg(not(target_os = "linux"))]
+    let wine_prefixes: Vec<std::path::PathBuf> = Vec::new();
+
+    std::iter::once(ManifestSource {
+        manifests_path: get_manifests_path(),
+        wine_prefix: None,
+    })
+    .chain(wine_prefixes.into_iter().map(|prefix| ManifestSource {
+        manifests_path: mods::wine::manifests_path_in_prefix(&prefix),
+        wine_prefix: Some(prefix),
+    }))
+    .collect()
+}
+
+// Finding Wine prefixes lists and stats directories across the home folder, so
+// scans look the sources up once, on the blocking pool
+pub async fn load_manifest_sources() -> Vec<ManifestSource> {
+    tokio::task::spawn_blocking(get_manifest_sources)
+        .await
+        .unwrap_or_else(|e| {
+            eprintln!("Failed to list manifest sources: {}", e);
+            vec![ManifestSource {
+                manifests_path: get_manifests_path(),
+                wine_prefix: None,
+            }]
+        })
+}
+
+fn get_item_path(game: &GameInfo) -> std::path::PathBuf {
+    let manifests_path = match &game.wine_prefix {
+        Some(prefix) => mods::wine::manifests_path_in_prefix(Path::new(prefix)),
+        None => get_manifests_path(),
+    };
+    manifests_path.join(format!("{}.item", game.installation_guid))
+}
+
+pub async fn scan_epic_games(
+    app_handle: &AppHandle,
+    sources: &[ManifestSource],
+    games: &GameStore,
     metadata_cache: &MetadataCache,
+    cancel_flag: &ScanCancelFlag,
 ) -> Result<Vec<GameInfo>, String> {
-    let manifests_path = get_manifests_path();
-    if !manifests_path.exists() {
+    scan_epic_games_with_progress(sources, games, metadata_cache, cancel_flag, |progress| {
+        mods::events::emit(app_handle, AppEvent::ScanProgress(progress));
+    })
+    .await
+}
+
+// Scan without a running app (e.g. from the command line); `on_progress` is
+// called as `.item` files are processed
+pub async fn scan_epic_games_with_progress(
+    sources: &[ManifestSource],
+    games: &GameStore,
+    metadata_cache: &MetadataCache,
+    cancel_flag: &ScanCancelFlag,
+    on_progress: impl Fn(ScanProgress),
+) -> Result<Vec<GameInfo>, String> {
+    // A new scan clears any cancellation left over from a previous one
+    cancel_flag.store(false, Ordering::SeqCst);
+    let scan_started = Instant::now();
+
+    let sources = sources
+        .iter()
+        .filter(|source| source.manifests_path.exists())
+        .collect::<Vec<_>>();
+    if sources.is_empty() {
+        if is_flatpak() {
+            return Err(
+                "Epic Games manifests directory not found; the Flatpak sandbox may need filesystem access to your Wine prefixes".to_string(),
+            );
+        }
         return Err("Epic Games manifests directory not found".to_string());
     }
-    let mut games = Vec::new();
-    let entries = fs::read_dir(manifests_path)
-        .map_err(|e| format!("Failed to read manifests directory: {}", e))?;
-    for entry in entries {
-        let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
-        let path = entry.path();
-        if path.extension().and_then(|s| s.to_str()) == Some("item") {
-            match parse_manifest_file_with_metadata(&path, metadata_cache).await {
-                Ok(game_info) => games.push(game_info),
-                Err(e) => {
-                    eprintln!("Failed to parse manifest file {:?}: {}", path, e);
-                    // Continue processing other files
+    let mut item_paths = Vec::new();
+    for source in sources {
+        // One unreadable source (e.g. a locked Wine prefix) shouldn't fail the scan
+        let entries = match fs::read_dir(&source.manifests_path) {
+            Ok(entries) => entries,
+            Err(e) => {
+                eprintln!(
+                    "Skipping manifests directory {}: {}",
+                    source.manifests_path.display(),
+                    e
+                );
+                continue;
+            }
+        };
+        for entry in entries {
+            if cancel_flag.load(Ordering::SeqCst) {
+                return Err("Scan cancelled".to_string());
+            }
+            let Ok(entry) = entry else {
+                continue;
+            };
+            let path = entry.path();
+            if path.extension().and_then(|s| s.to_str()) == Some("item") {
+                item_paths.push((path, source.wine_prefix.clone()));
+            }
+        }
+    }
+
+    let total = item_paths.len();
+    on_progress(ScanProgress {
+        processed: 0,
+        total,
+        current_game: None,
+    });
+
+    // Parse `.item` files on the blocking pool, a bounded number at a time
+    let semaphore = Arc::new(Semaphore::new(SCAN_PARALLELISM));
+    let mut tasks = JoinSet::new();
+    for (path, wine_prefix) in item_paths {
+        let semaphore = semaphore.clone();
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let task_path = path.clone();
+            let mut result = tokio::task::spawn_blocking(move || {
+                mods::priority::run_in_background(|| parse_item_file(&task_path))
+            })
+            .await
+            .unwrap_or_else(|e| Err(format!("Parser task failed: {}", e)));
+
+            // Games from a Wine prefix record Windows paths; map them to the host
+            if let (Ok(game_info), Some(prefix)) = (result.as_mut(), wine_prefix) {
+                game_info.install_location =
+                    mods::wine::to_host_path(&prefix, &game_info.install_location)
+                        .to_string_lossy()
+                        .to_string();
+                game_info.wine_prefix = Some(prefix.to_string_lossy().to_string());
+            }
+
+            // Probe the install location separately so a dead drive only
+            // marks its own games unavailable instead of stalling the scan
+            if let Ok(game_info) = result.as_mut() {
+                let install_location = game_info.install_location.clone();
+                let resolved = tokio::task::spawn_blocking(move || {
+                    mods::priority::run_in_background(|| {
+                        resolve_install_location(&install_location)
+                    })
+                });
+                let probe = match time::timeout(PATH_PROBE_TIMEOUT, resolved).await {
+                    Ok(Ok(Ok(install_path))) => probe_path(install_path, PATH_PROBE_TIMEOUT).await,
+                    Ok(Ok(Err(e))) => {
+                        eprintln!("{}", e);
+                        PathProbe::Missing
+                    }
+                    Ok(Err(_)) => PathProbe::Missing,
+                    Err(_) => PathProbe::TimedOut,
+                };
+                match probe {
+                    PathProbe::Available => {
+                        let task_game = game_info.clone();
+                        let detected = tokio::task::spawn_blocking(move || {
+                            mods::arch::detect_architecture(&task_game)
+                        });
+                        if let Ok(Ok(architecture)) =
+                            time::timeout(PATH_PROBE_TIMEOUT, detected).await
+                        {
+                            game_info.architecture = architecture;
+                        }
+                    }
+                    PathProbe::Missing => game_info.available = false,
+                    PathProbe::AccessDenied => {
+                        eprintln!(
+                            "No permission to read the install location of {}: {}",
+                            game_info.display_name, game_info.install_location
+                        );
+                        game_info.available = false;
+                        game_info.access_denied = true;
+                    }
+                    PathProbe::TimedOut => {
+                        eprintln!(
+                            "Install location for {} did not respond: {}",
+                            game_info.display_name, game_info.install_location
+                        );
+                        game_info.available = false;
+                    }
                 }
             }
+            (path, result)
+        });
+    }
+
+    let mut scanned = Vec::new();
+    let mut processed = 0;
+    while let Some(joined) = tasks.join_next().await {
+        if cancel_flag.load(Ordering::SeqCst) {
+            tasks.abort_all();
+            return Err("Scan cancelled".to_string());
         }
+
+        processed += 1;
+        let current_game = match joined {
+            Ok((_, Ok(game_info))) => {
+                let name = game_info.display_name.clone();
+                scanned.push(game_info);
+                Some(name)
+            }
+            Ok((path, Err(e))) => {
+                eprintln!("Failed to parse manifest file {:?}: {}", path, e);
+                // Continue processing other files
+                None
+            }
+            Err(e) => {
+                eprintln!("Manifest parsing task failed: {}", e);
+                None
+            }
+        };
+
+        on_progress(ScanProgress {
+            processed,
+            total,
+            current_game,
+        });
     }
-    Ok(games)
+    mods::relocate::reconcile_moved_installs(&mut scanned).await;
+    keep_offline_games(games, &mut scanned).await;
+    attach_known_metadata(games, metadata_cache, &mut scanned);
+    mods::sightings::record(&scanned);
+    mods::telemetry::record_scan(scan_started.elapsed());
+    mods::activity::record(
+        "scan",
+        &format!(
+            "Scanned {} games in {} ms",
+            scanned.len(),
+            scan_started.elapsed().as_millis()
+        ),
+        None,
+    );
+    Ok(scanned)
 }
 
-async fn parse_manifest_file_with_metadata(
-    path: &Path,
-    metadata_cache: &MetadataCache,
-) -> Result<GameInfo, String> {
-    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+// Games whose `.item` disappeared together with a disconnected drive (a Wine
+// prefix or manifests folder on it) stay in the library, unavailable, instead
+// of being treated as uninstalled. An uninstall leaves the folder containing
+// the game in place; a missing or unresponsive one means the drive is gone.
+async fn keep_offline_games(games: &GameStore, scanned: &mut Vec<GameInfo>) {
+    let missing = games
+        .lock()
+        .map(|games_lock| {
+            games_lock
+                .values()
+                .filter(|old| !scanned.iter().any(|g| g.app_name == old.app_name))
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    for mut game in missing {
+        let Some(parent) = Path::new(&game.install_location).parent() else {
+            continue;
+        };
+        if probe_path(parent.to_path_buf(), PATH_PROBE_TIMEOUT).await == PathProbe::Available {
+            continue;
+        }
+        game.available = false;
+        scanned.push(game);
+    }
+}
+
+// A FormatVersion and the sorted `.item` keys it had that the client doesn't model
+type UnknownFieldSet = (i32, Vec<String>);
+
+// Key sets already reported
+static LOGGED_UNKNOWN_FIELDS: Lazy<Mutex<std::collections::HashSet<UnknownFieldSet>>> =
+    Lazy::new(|| Mutex::new(std::collections::HashSet::new()));
+
+// Real `.item` files carry plenty of keys the client doesn't model; report each
+// FormatVersion/key set once per run rather than for every game on every scan
+fn log_unknown_item_fields(format_version: i32, unknown_fields: Vec<String>) {
+    if unknown_fields.is_empty() {
+        return;
+    }
+    let message = format!(
+        ".item files with FormatVersion {} have unrecognized fields: {}",
+        format_version,
+        unknown_fields.join(", ")
+    );
+    let first_seen = LOGGED_UNKNOWN_FIELDS
+        .lock()
+        .map(|mut logged| logged.insert((format_version, unknown_fields)))
+        .unwrap_or(false);
+    if first_seen {
+        eprintln!("{}", message);
+    }
+}
+
+pub fn parse_item_file(path: &Path) -> Result<GameInfo, String> {
+    let bytes = read_stable_file(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let content = decode_text_file(path, &bytes)?;
 
     let manifest: EpicGameManifest =
         serde_json::from_str(&content).map_err(|e| format!("Failed to parse JSON: {}", e))?;
 
-    let metadata = fetch_game_metadata(&manifest.catalog_item_id, metadata_cache).await;
+    let mut unknown_fields: Vec<String> = manifest.unknown_fields.into_keys().collect();
+    unknown_fields.sort();
+    log_unknown_item_fields(manifest.format_version, unknown_fields);
 
     Ok(GameInfo {
-        display_name: manifest.display_name,
+        display_name: if manifest.display_name.is_empty() {
+            manifest.app_name.clone()
+        } else {
+            manifest.display_name
+        },
         app_name: manifest.app_name,
         install_location: manifest.install_location,
         install_size: manifest.install_size,
@@ -208,96 +753,830 @@ async fn parse_manifest_file_with_metadata(
         catalog_item_id: manifest.catalog_item_id,
         installation_guid: manifest.installation_guid,
         manifest_hash: manifest.manifest_hash,
-        metadata,
+        environment: manifest.build_label,
+        metadata: None,
+        available: true,
+        wine_prefix: None,
+        access_denied: false,
+        installed: true,
+        uninstalled_at: None,
+        final_uploaded_at: None,
+        moved_from: None,
+        item_content: content,
+        format_version: manifest.format_version,
+        launch_executable: manifest.launch_executable,
+        main_game_app_name: manifest.main_game_app_name,
+        children: Vec::new(),
+        architecture: String::new(),
     })
 }
 
+// Snapshots and caches the library and notifies webhooks about updated games.
+// Returns the changes since the previous snapshot.
+pub fn record_library_changes(
+    scanned: &[GameInfo],
+    settings: &SettingsState,
+) -> Vec<LibraryChange> {
+    let changes = mods::history::record_snapshot(scanned);
+    save_library_cache_to_file(scanned);
+    let updates = changes.iter().filter(|c| c.kind == "updated");
+    if updates.clone().next().is_some() {
+        let settings = settings.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        for change in updates {
+            mods::webhooks::dispatch(&settings, "game-updated", change);
+        }
+    }
+    changes
+}
+
+// Games that were in the library before a scan but whose `.item` is gone now
+pub fn find_uninstalled(
+    previous: &HashMap<String, GameInfo>,
+    scanned: &[GameInfo],
+) -> Vec<GameInfo> {
+    previous
+        .values()
+        .filter(|old| !scanned.iter().any(|g| g.app_name == old.app_name))
+        .cloned()
+        .collect()
+}
+
+// Turns each removed game into a tombstone and emits `game-uninstalled`, noting
+// whether its manifest is still on disk for one last upload. Games that show up
+// in the scan again lose their tombstone.
+pub async fn announce_uninstalled(
+    app_handle: &AppHandle,
+    removed: Vec<GameInfo>,
+    scanned: &[GameInfo],
+    uninstalled_games: &UninstalledGames,
+) {
+    // Checked before taking the lock; the drive may be gone along with the game
+    let mut manifests_available = Vec::with_capacity(removed.len());
+    for game in &removed {
+        let manifest_path =
+            egstore_manifest_path(&game.install_location, &game.installation_guid).await;
+        manifests_available
+            .push(probe_path(manifest_path, PATH_PROBE_TIMEOUT).await == PathProbe::Available);
+    }
+
+    let mut tombstones = uninstalled_games.lock().unwrap_or_else(|e| e.into_inner());
+    let tombstone_count = tombstones.len();
+    tombstones.retain(|app_name, _| !scanned.iter().any(|g| &g.app_name == app_name));
+    let mut changed = tombstones.len() != tombstone_count;
+
+    for (mut game, manifest_available) in removed.into_iter().zip(manifests_available) {
+        emit_log(
+            app_handle,
+            "INFO",
+            &format!(
+                "{} was uninstalled{}",
+                game.display_name,
+                if manifest_available {
+                    "; its manifest can still be uploaded"
+                } else {
+                    ""
+                }
+            ),
+        );
+
+        game.installed = false;
+        game.uninstalled_at = Some(chrono::Utc::now().to_rfc3339());
+        tombstones.insert(game.app_name.clone(), game.clone());
+        changed = true;
+        mods::events::emit(
+            app_handle,
+            AppEvent::GameUninstalled(&GameUninstalled {
+                game,
+                manifest_available,
+            }),
+        );
+    }
+
+    if changed {
+        save_uninstalled_games_to_file(&tombstones);
+    }
+}
+
+// Games whose catalog item is already in the library keep the metadata they
+// have, so steady-state scans never touch the cache or the network for them.
+// Only item IDs seen for the first time are looked up, under a single lock;
+// whatever is still missing is filled in by `spawn_metadata_enrichment` once
+// the game list has been published.
+fn attach_known_metadata(
+    games: &GameStore,
+    metadata_cache: &MetadataCache,
+    scanned: &mut [GameInfo],
+) {
+    let known: HashMap<String, GameMetadata> = games
+        .lock()
+        .map(|games_lock| {
+            games_lock
+                .values()
+                .filter_map(|g| {
+                    g.metadata
+                        .clone()
+                        .map(|metadata| (g.catalog_item_id.clone(), metadata))
+                })
+                // Outage fallbacks are left for enrichment to replace
+                .filter(|(_, metadata)| !metadata.fallback)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut cache_lock = None;
+    for game in scanned.iter_mut() {
+        game.metadata = match known.get(&game.catalog_item_id) {
+            Some(metadata) => Some(metadata.clone()),
+            None => {
+                if cache_lock.is_none() {
+                    cache_lock = metadata_cache.lock().ok();
+                }
+                cache_lock
+                    .as_mut()
+                    .and_then(|cache| cache.lookup(&game.catalog_item_id))
+                    .filter(|metadata| !metadata.fallback)
+            }
+        };
+    }
+}
+
+// Second phase of a scan: fetch metadata for games that were published without
+// it and emit a `metadata-updated` event for each one as it resolves.
+pub fn spawn_metadata_enrichment(
+    app_handle: AppHandle,
+    games: GameStore,
+    metadata_cache: MetadataCache,
+    settings: SettingsState,
+    scanned_games: Vec<GameInfo>,
+) {
+    // Fallback metadata stands in for a failed fetch, so it's retried too
+    let pending = scanned_games
+        .into_iter()
+        .filter(|g| g.metadata.as_ref().is_none_or(|m| m.fallback))
+        .collect::<Vec<_>>();
+    if pending.is_empty() {
+        return;
+    }
+
+    tauri::async_runtime::spawn(async move {
+        let settings = settings.lock().map(|s| s.clone()).unwrap_or_default();
+
+        for game in pending {
+            let metadata = match fetch_game_metadata(
+                &game.catalog_item_id,
+                &metadata_cache,
+                settings.metadata_timeout(),
+            )
+            .await
+            {
+                Some(metadata) => metadata,
+                None => fallback_game_metadata(&game, &settings).await,
+            };
+
+            publish_metadata(&app_handle, &games, &game.catalog_item_id, &metadata);
+        }
+
+        if let Ok(cache_lock) = metadata_cache.lock() {
+            save_metadata_cache_to_file(&cache_lock.entries);
+        }
+    });
+}
+
+// Stores `metadata` on every game of the catalog item and emits one
+// `metadata-updated` per game, so the UI can hydrate cards without a full
+// `games-updated` broadcast
+pub fn publish_metadata(
+    app_handle: &AppHandle,
+    games: &GameStore,
+    catalog_item_id: &str,
+    metadata: &GameMetadata,
+) {
+    let updated_apps = games
+        .lock()
+        .map(|mut games_lock| {
+            games_lock
+                .values_mut()
+                .filter(|g| g.catalog_item_id == catalog_item_id)
+                .map(|g| {
+                    g.metadata = Some(metadata.clone());
+                    g.app_name.clone()
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    for app_name in updated_apps {
+        mods::events::emit(
+            app_handle,
+            AppEvent::MetadataUpdated(MetadataUpdate {
+                app_name,
+                catalog_item_id: catalog_item_id.to_string(),
+                metadata: metadata.clone(),
+            }),
+        );
+    }
+}
+
+// Scans only fetch metadata for new catalog items, so known ones are re-fetched
+// here instead, replacing stale cache entries and fallback metadata
+async fn periodic_metadata_refresh(
+    app_handle: AppHandle,
+    games: GameStore,
+    metadata_cache: MetadataCache,
+    settings: SettingsState,
+) {
+    let read_interval = || {
+        settings
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .metadata_refresh_interval_minutes
+    };
+    let mut schedule = IntervalSchedule::new("metadata refresh", read_interval());
+    // The first run is due right after startup, when metadata is fresh anyway
+    schedule.tick(&app_handle, read_interval).await;
+
+    loop {
+        schedule.tick(&app_handle, read_interval).await;
+
+        let item_ids = games
+            .lock()
+            .map(|games_lock| {
+                games_lock
+                    .values()
+                    .map(|g| g.catalog_item_id.clone())
+                    .collect::<std::collections::HashSet<_>>()
+            })
+            .unwrap_or_default();
+        let timeout = settings
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .metadata_timeout();
+
+        let mut refreshed = 0;
+        // Entries are replaced only once a fetch succeeds
+        for item_id in item_ids {
+            let Some(metadata) = refresh_game_metadata(&item_id, &metadata_cache, timeout).await
+            else {
+                continue;
+            };
+            refreshed += 1;
+            publish_metadata(&app_handle, &games, &item_id, &metadata);
+        }
+
+        if let Ok(cache_lock) = metadata_cache.lock() {
+            save_metadata_cache_to_file(&cache_lock.entries);
+        }
+        emit_log(
+            &app_handle,
+            "INFO",
+            &format!("Refreshed metadata for {} catalog items", refreshed),
+        );
+    }
+}
+
+// Uploads what a scan just found (new installs and new versions) without
+// waiting for the next periodic upload; short-lived builds may be gone by then
+pub fn spawn_upload_on_detect(
+    app_handle: &AppHandle,
+    delta: &GamesDelta,
+    scanned: &[GameInfo],
+    settings: &SettingsState,
+) {
+    let settings = settings.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    if !settings.upload_on_detect || !settings.auto_upload_enabled || mods::pause::is_paused() {
+        return;
+    }
+    let ledger = mods::ledger::load_ledger();
+    let detected = scanned
+        .iter()
+        .filter(|g| g.available)
+        .filter(|g| delta.added.contains(&g.app_name) || delta.changed.contains(&g.app_name))
+        .filter(|g| !ledger.contains_key(&g.manifest_hash))
+        .cloned()
+        .collect::<Vec<_>>();
+    if detected.is_empty() {
+        return;
+    }
+
+    // Goes through the same batch path as the schedule so in-batch dedup, the
+    // server check and the batch lock all apply
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        match upload_games_with(&HttpEgdataApi, detected, &settings, true).await {
+            Ok(results) => {
+                for result in results {
+                    let hash = result
+                        .manifest_hash
+                        .as_deref()
+                        .unwrap_or("unknown manifest");
+                    match result.status.as_str() {
+                        "uploaded" | "already_uploaded" => emit_log(
+                            &app_handle,
+                            "SUCCESS",
+                            &format!("Uploaded newly detected manifest {}", hash),
+                        ),
+                        "skipped_duplicate" => {}
+                        status => emit_log(
+                            &app_handle,
+                            "WARN",
+                            &format!(
+                                "Upload of newly detected manifest {} returned {}; the periodic upload will retry",
+                                hash, status
+                            ),
+                        ),
+                    }
+                }
+            }
+            Err(e) => emit_log(
+                &app_handle,
+                "ERROR",
+                &format!("Upload of newly detected games failed: {}", e),
+            ),
+        }
+    });
+}
+
 async fn periodic_upload(app_handle: AppHandle, games: GameStore, settings: SettingsState) {
-    let mut current_interval_minutes = {
-        let settings_lock = settings.lock().unwrap();
-        settings_lock.upload_interval
+    // A zero interval parks the schedule, which is how disabled auto upload
+    // and the tray's pause are applied live
+    let read_interval = || {
+        let settings = settings.lock().unwrap_or_else(|e| e.into_inner());
+        if settings.auto_upload_enabled && !mods::pause::is_paused() {
+            settings.upload_interval
+        } else {
+            0
+        }
+    };
+    let mut schedule = IntervalSchedule::new("upload", read_interval());
+
+    loop {
+        // Resume from sleep or a restored connection flushes uploads early;
+        // both flags are taken so neither lingers into the next wait
+        schedule
+            .tick_or_wake(&app_handle, read_interval, || {
+                mods::power::take_upload_wake() | mods::network::take_upload_wake()
+            })
+            .await;
+
+        emit_log(&app_handle, "INFO", "Starting periodic manifest upload...");
+
+        let settings_snapshot = settings.lock().unwrap_or_else(|e| e.into_inner()).clone();
+
+        match upload_all_manifests_with(&HttpEgdataApi, &games, &settings_snapshot, true).await {
+            Ok(results) => {
+                let uploaded_count = results.iter().filter(|r| r.status == "uploaded").count();
+                let already_uploaded_count = results
+                    .iter()
+                    .filter(|r| r.status == "already_uploaded")
+                    .count();
+                let duplicate_count = results
+                    .iter()
+                    .filter(|r| r.status == "skipped_duplicate")
+                    .count();
+                let failed_count = results.iter().filter(|r| r.status == "failed").count();
+
+                emit_log(
+                    &app_handle,
+                    "SUCCESS",
+                    &format!(
+                        "Periodic upload completed: {} uploaded, {} already uploaded, {} duplicates skipped, {} failed",
+                        uploaded_count, already_uploaded_count, duplicate_count, failed_count
+                    ),
+                );
+
+                // With the digest on, failures are reported once a day instead
+                if failed_count > 0 && !settings_snapshot.daily_digest_enabled {
+                    mods::notifications::notify_upload_failures(&app_handle, failed_count);
+                }
+
+                // Emit event to frontend
+                mods::events::emit(&app_handle, AppEvent::PeriodicUploadCompleted(&results));
+            }
+            Err(e) => {
+                emit_log(
+                    &app_handle,
+                    "ERROR",
+                    &format!("Periodic upload failed: {}", e),
+                );
+            }
+        }
+    }
+}
+
+pub async fn upload_all_manifests_internal(
+    games: &GameStore,
+    settings: &Settings,
+) -> Result<Vec<UploadStatus>, String> {
+    upload_all_manifests_with(&HttpEgdataApi, games, settings, false).await
+}
+
+// `automatic` runs (the schedule) stop between games once uploads are paused;
+// ones the user asked for go through regardless
+pub async fn upload_all_manifests_with(
+    api: &impl EgdataApi,
+    games: &GameStore,
+    settings: &Settings,
+    automatic: bool,
+) -> Result<Vec<UploadStatus>, String> {
+    let games_to_upload = {
+        let games_lock = games
+            .lock()
+            .map_err(|e| format!("Failed to lock games: {}", e))?;
+        games_lock.values().cloned().collect::<Vec<_>>()
+    };
+    upload_games_with(api, games_to_upload, settings, automatic).await
+}
+
+// One batch at a time: the schedule, upload-on-detect and the user's "upload
+// all" would otherwise send the same manifest concurrently
+static UPLOAD_BATCH_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+pub async fn upload_games_with(
+    api: &impl EgdataApi,
+    games_to_upload: Vec<GameInfo>,
+    settings: &Settings,
+    automatic: bool,
+) -> Result<Vec<UploadStatus>, String> {
+    let _batch = UPLOAD_BATCH_LOCK.lock().await;
+
+    // Manifests of games on an offline drive can't be read; they wait for it to return
+    let (games_to_upload, unavailable): (Vec<_>, Vec<_>) =
+        games_to_upload.into_iter().partition(|g| g.available);
+    let (access_denied, offline): (Vec<_>, Vec<_>) =
+        unavailable.into_iter().partition(|g| g.access_denied);
+    if !offline.is_empty() {
+        eprintln!(
+            "Skipping {} game(s) whose install drive is offline",
+            offline.len()
+        );
+    }
+
+    let scanned_guids = games_to_upload
+        .iter()
+        .map(|g| g.installation_guid.to_uppercase())
+        .collect::<std::collections::HashSet<_>>();
+    // Installs in another user's profile stay unreadable until scanned with
+    // more rights, so say so instead of silently skipping them
+    let mut results = access_denied
+        .iter()
+        .map(|game| UploadStatus {
+            status: "access_denied".to_string(),
+            message: Some(format!(
+                "No permission to read {}; it may belong to another user",
+                game.install_location
+            )),
+            manifest_hash: Some(game.manifest_hash.clone()),
+            endpoint: None,
+            error_code: None,
+            metrics: None,
+        })
+        .collect::<Vec<_>>();
+
+    // Duplicate installs and DLC can share a manifest; send each hash once per
+    // run, and not at all if the ledger has it for another game
+    let ledger = mods::ledger::load_ledger();
+    let mut batch_hashes = std::collections::HashSet::new();
+
+    for game in games_to_upload {
+        if automatic && mods::pause::is_paused() {
+            eprintln!("Uploads paused; stopping the running batch");
+            break;
+        }
+        let hash = game.manifest_hash.to_uppercase();
+        let ledger_owner = ledger
+            .get(&game.manifest_hash)
+            .map(|entry| entry.app_name.as_str())
+            .filter(|app_name| *app_name != game.app_name);
+        if !batch_hashes.insert(hash) || ledger_owner.is_some() {
+            results.push(UploadStatus {
+                status: "skipped_duplicate".to_string(),
+                message: Some(match ledger_owner {
+                    Some(app_name) => format!("Manifest was already uploaded for {}", app_name),
+                    None => "Manifest is shared with another game in this batch".to_string(),
+                }),
+                manifest_hash: Some(game.manifest_hash.clone()),
+                endpoint: None,
+                error_code: None,
+                metrics: None,
+            });
+            continue;
+        }
+
+        if api
+            .manifest_exists(&game.manifest_hash, &game.environment, settings)
+            .await
+            == Some(true)
+        {
+            let status = UploadStatus {
+                status: "already_uploaded".to_string(),
+                message: Some("Manifest already exists on server".to_string()),
+                manifest_hash: Some(game.manifest_hash.clone()),
+                endpoint: None,
+                error_code: None,
+                metrics: None,
+            };
+            // A queued retry is settled once the server has the manifest
+            mods::retry_queue::record(&game, &Ok(status.clone()));
+            results.push(status);
+        } else {
+            match upload_manifest_with(api, &game, settings).await {
+                Ok(status) => results.push(status),
+                Err(e) => results.push(UploadStatus {
+                    status: "failed".to_string(),
+                    message: Some(e),
+                    manifest_hash: None,
+                    endpoint: None,
+                    error_code: None,
+                    metrics: None,
+                }),
+            }
+        }
+
+        results.extend(
+            upload_companion_manifests(
+                api,
+                &game,
+                &scanned_guids,
+                &ledger,
+                &mut batch_hashes,
+                settings,
+            )
+            .await,
+        );
+        if settings.sidecar_upload_games.contains(&game.app_name) {
+            results.extend(upload_sidecar_bundle(api, &game, &ledger, settings).await);
+        }
+    }
+
+    for result in &results {
+        mods::telemetry::record_upload(&result.status);
+    }
+
+    Ok(results)
+}
+
+// Uploads the game's `.mancpn` and DLC manifests, skipping ones the ledger
+// already has or this batch already sent. Each gets its own status, keyed by
+// its content hash. Nothing is sent to endpoints that don't take companions.
+async fn upload_companion_manifests(
+    api: &impl EgdataApi,
+    game: &GameInfo,
+    scanned_guids: &std::collections::HashSet<String>,
+    ledger: &std::collections::HashMap<String, LedgerEntry>,
+    batch_hashes: &mut std::collections::HashSet<String>,
+    settings: &Settings,
+) -> Vec<UploadStatus> {
+    if !api.accepts_companions(&game.environment, settings).await {
+        return Vec::new();
+    }
+    // Listing `.egstore` touches the install drive; keep it off the async workers
+    let companions = {
+        let (game, scanned_guids) = (game.clone(), scanned_guids.clone());
+        time::timeout(
+            PATH_PROBE_TIMEOUT,
+            tokio::task::spawn_blocking(move || {
+                mods::companions::find_companion_manifests(&game, &scanned_guids)
+            }),
+        )
+        .await
+        .ok()
+        .and_then(Result::ok)
+        .unwrap_or_default()
+    };
+    if companions.is_empty() {
+        return Vec::new();
+    }
+    let item_text = match read_item_text(game).await {
+        Ok(item_text) => item_text,
+        Err(_) => return Vec::new(),
     };
 
-    let mut interval = time::interval(Duration::from_secs(current_interval_minutes * 60));
+    let platform = detect_upload_platform(game);
+    let mut chunks = None;
+    let mut results = Vec::new();
+    for companion in companions {
+        // An unchanged file keeps its hash, so a known one needn't be read again
+        let cached_hash = mods::companions::cached_hash(&companion.path);
+        if let Some(hash) = &cached_hash {
+            if ledger.contains_key(hash) || batch_hashes.contains(&hash.to_uppercase()) {
+                continue;
+            }
+        }
 
-    loop {
-        interval.tick().await;
+        let bytes = match read_file_with_timeout(companion.path.clone()).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                results.push(UploadStatus {
+                    status: "failed".to_string(),
+                    message: Some(format!(
+                        "Failed to read {}: {}",
+                        companion.path.display(),
+                        e
+                    )),
+                    manifest_hash: None,
+                    endpoint: None,
+                    error_code: None,
+                    metrics: None,
+                });
+                continue;
+            }
+        };
+        let (bytes, hash) = match cached_hash {
+            Some(hash) => (bytes, hash),
+            None => match mods::companions::content_hash_in_background(bytes).await {
+                Ok((bytes, hash)) => {
+                    mods::companions::remember_hash(&companion.path, &hash);
+                    (bytes, hash)
+                }
+                Err(e) => {
+                    results.push(UploadStatus {
+                        status: "failed".to_string(),
+                        message: Some(e),
+                        manifest_hash: None,
+                        endpoint: None,
+                        error_code: None,
+                        metrics: None,
+                    });
+                    continue;
+                }
+            },
+        };
+        if ledger.contains_key(&hash) || !batch_hashes.insert(hash.to_uppercase()) {
+            continue;
+        }
 
-        // Check if interval has changed
-        let new_interval_minutes = {
-            let settings_lock = settings.lock().unwrap();
-            settings_lock.upload_interval
+        if chunks.is_none() {
+            chunks = Some(mods::chunks::chunk_availability(game).await);
+        }
+        let upload = ManifestUpload {
+            item_json: item_text.clone(),
+            manifest_hash: hash.clone(),
+            environment: game.environment.clone(),
+            manifest_filename: companion
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            kind: companion.kind.to_string(),
+            manifest_bytes: bytes,
+            os: platform.os.to_string(),
+            runtime: platform.runtime.to_string(),
+            arch: upload_architecture(game),
+            launcher_version: mods::launcher::get_launcher_version(),
+            host: settings.share_host_details.then(mods::host::host_details),
+            first_seen: mods::sightings::first_seen(&game.app_name, &game.manifest_hash),
+            chunks: chunks.clone().unwrap_or_default(),
         };
+        let status = api
+            .upload_manifest(upload, settings)
+            .await
+            .unwrap_or_else(|e| UploadStatus {
+                status: "failed".to_string(),
+                message: Some(e),
+                manifest_hash: Some(hash),
+                endpoint: None,
+                error_code: None,
+                metrics: None,
+            });
+        mods::ledger::record(game, &status);
+        results.push(status);
+    }
+    results
+}
 
-        if new_interval_minutes != current_interval_minutes {
-            current_interval_minutes = new_interval_minutes;
-            interval = time::interval(Duration::from_secs(current_interval_minutes * 60));
-            emit_log(
-                &app_handle,
-                "INFO",
-                &format!(
-                    "Upload interval updated to {} minutes",
-                    current_interval_minutes
-                ),
-            );
+// Uploads the game's opted-in sidecar bundle unless the ledger already has it
+async fn upload_sidecar_bundle(
+    api: &impl EgdataApi,
+    game: &GameInfo,
+    ledger: &std::collections::HashMap<String, LedgerEntry>,
+    settings: &Settings,
+) -> Option<UploadStatus> {
+    if !api.accepts_companions(&game.environment, settings).await {
+        return None;
+    }
+    let (bytes, hash) = match mods::sidecars::bundle_in_background(game.clone()).await {
+        Ok(Some(bundle)) => bundle,
+        Ok(None) => return None,
+        Err(e) => {
+            return Some(UploadStatus {
+                status: "failed".to_string(),
+                message: Some(e),
+                manifest_hash: None,
+                endpoint: None,
+                error_code: None,
+                metrics: None,
+            })
         }
+    };
+    if ledger.contains_key(&hash) {
+        return None;
+    }
+    let item_text = read_item_text(game).await.ok()?;
 
-        emit_log(&app_handle, "INFO", "Starting periodic manifest upload...");
+    let platform = detect_upload_platform(game);
+    let upload = ManifestUpload {
+        item_json: item_text,
+        manifest_hash: hash.clone(),
+        environment: game.environment.clone(),
+        manifest_filename: format!("{}.sidecars.zip", game.installation_guid),
+        kind: "sidecars".to_string(),
+        manifest_bytes: bytes,
+        os: platform.os.to_string(),
+        runtime: platform.runtime.to_string(),
+        arch: upload_architecture(game),
+        launcher_version: mods::launcher::get_launcher_version(),
+        host: settings.share_host_details.then(mods::host::host_details),
+        first_seen: mods::sightings::first_seen(&game.app_name, &game.manifest_hash),
+        chunks: mods::chunks::chunk_availability(game).await,
+    };
+    let status = api
+        .upload_manifest(upload, settings)
+        .await
+        .unwrap_or_else(|e| UploadStatus {
+            status: "failed".to_string(),
+            message: Some(e),
+            manifest_hash: Some(hash),
+            endpoint: None,
+            error_code: None,
+            metrics: None,
+        });
+    mods::ledger::record(game, &status);
+    Some(status)
+}
 
-        match upload_all_manifests_internal(&games).await {
-            Ok(results) => {
-                let uploaded_count = results.iter().filter(|r| r.status == "uploaded").count();
-                let already_uploaded_count = results
-                    .iter()
-                    .filter(|r| r.status == "already_uploaded")
-                    .count();
-                let failed_count = results.iter().filter(|r| r.status == "failed").count();
+// Time the window and tray get to appear before startup work hits the network
+const POST_STARTUP_DELAY: Duration = Duration::from_secs(3);
 
-                emit_log(
-                    &app_handle,
-                    "SUCCESS",
-                    &format!(
-                        "Periodic upload completed: {} uploaded, {} already uploaded, {} failed",
-                        uploaded_count, already_uploaded_count, failed_count
-                    ),
-                );
+// Quiet libraries are rescanned at most this many times less often than configured
+const MAX_SCAN_BACKOFF_MULTIPLIER: u64 = 8;
 
-                // Emit event to frontend
-                let _ = app_handle.emit("periodic-upload-completed", &results);
-            }
-            Err(e) => {
-                emit_log(
-                    &app_handle,
-                    "ERROR",
-                    &format!("Periodic upload failed: {}", e),
-                );
-            }
+// Works out whether there is anything to scan. A change is logged and sent as
+// `launcher-status` once, instead of a "directory not found" error every scan.
+fn check_launcher(app_handle: &AppHandle, sources: &[ManifestSource]) -> bool {
+    let state = if sources.iter().any(|source| source.manifests_path.exists()) {
+        LauncherState::Ok
+    } else if mods::launcher::is_launcher_installed() {
+        LauncherState::NoManifests
+    } else {
+        LauncherState::LauncherNotFound
+    };
+    if mods::launcher::set_launcher_state(state) {
+        match state {
+            LauncherState::Ok => emit_log(app_handle, "INFO", "Epic Games manifests found"),
+            LauncherState::NoManifests => emit_log(
+                app_handle,
+                "WARN",
+                "The Epic Games Launcher has no installed games yet; scanning resumes when it does",
+            ),
+            LauncherState::LauncherNotFound => emit_log(
+                app_handle,
+                "WARN",
+                "Epic Games Launcher not found; scanning resumes once it is installed",
+            ),
         }
+        mods::events::emit(app_handle, AppEvent::LauncherStatus(state));
     }
+    state == LauncherState::Ok
 }
 
-async fn upload_all_manifests_internal(games: &GameStore) -> Result<Vec<UploadStatus>, String> {
-    let games_to_upload = {
-        let games_lock = games
-            .lock()
-            .map_err(|e| format!("Failed to lock games: {}", e))?;
-        games_lock.values().cloned().collect::<Vec<_>>()
-    };
-
-    let mut results = Vec::new();
+// Modification times of every manifests directory; adding or removing an `.item`
+// file bumps its directory's mtime
+fn manifest_sources_fingerprint(sources: &[ManifestSource]) -> Vec<Option<std::time::SystemTime>> {
+    sources
+        .iter()
+        .map(|source| {
+            fs::metadata(&source.manifests_path)
+                .and_then(|m| m.modified())
+                .ok()
+        })
+        .collect()
+}
 
-    for game in games_to_upload {
-        match upload_manifest_internal(&game).await {
-            Ok(status) => results.push(status),
-            Err(e) => results.push(UploadStatus {
-                status: "failed".to_string(),
-                message: Some(e),
-                manifest_hash: None,
-            }),
+pub fn library_delta(previous: &HashMap<String, GameInfo>, scanned: &[GameInfo]) -> GamesDelta {
+    let mut delta = GamesDelta::default();
+    for game in scanned {
+        match previous.get(&game.app_name) {
+            Some(old) => {
+                if old.version != game.version
+                    || old.manifest_hash != game.manifest_hash
+                    || old.install_location != game.install_location
+                    || old.available != game.available
+                {
+                    delta.changed.push(game.app_name.clone());
+                }
+            }
+            None => delta.added.push(game.app_name.clone()),
         }
     }
-
-    Ok(results)
+    delta.removed = previous
+        .values()
+        .filter(|old| !scanned.iter().any(|g| g.app_name == old.app_name))
+        .map(|old| GameKey {
+            app_name: old.app_name.clone(),
+            catalog_item_id: old.catalog_item_id.clone(),
+        })
+        .collect();
+    delta
 }
 
 async fn periodic_scan(
@@ -305,58 +1584,76 @@ async fn periodic_scan(
     games: GameStore,
     metadata_cache: MetadataCache,
     settings: SettingsState,
+    cancel_flag: ScanCancelFlag,
+    uninstalled_games: UninstalledGames,
 ) {
-    let mut current_interval_minutes = {
-        let settings_lock = settings.lock().unwrap();
-        settings_lock.scan_interval_minutes
+    // Parked while auto scan is off; the change-detection wake is skipped then too
+    let read_interval = || {
+        let settings = settings.lock().unwrap_or_else(|e| e.into_inner());
+        if settings.auto_scan_enabled {
+            settings.scan_interval_minutes
+        } else {
+            0
+        }
     };
-
-    let mut interval = time::interval(Duration::from_secs(current_interval_minutes * 60));
+    let mut schedule = IntervalSchedule::new("scan", read_interval());
+    let mut sources = load_manifest_sources().await;
+    let mut manifests_fingerprint = manifest_sources_fingerprint(&sources);
 
     loop {
-        interval.tick().await;
+        // Any change to a manifests directory (an install, update or removal)
+        // or a resume from sleep cuts a backed-off wait short
+        schedule
+            .tick_or_wake(&app_handle, read_interval, || {
+                let fingerprint = manifest_sources_fingerprint(&sources);
+                let changed = fingerprint != manifests_fingerprint;
+                manifests_fingerprint = fingerprint;
+                mods::power::take_scan_wake() || changed
+            })
+            .await;
 
-        // Check if interval has changed
-        let new_interval_minutes = {
-            let settings_lock = settings.lock().unwrap();
-            settings_lock.scan_interval_minutes
-        };
+        // Looked up once per cycle; new Wine prefixes are picked up here too
+        sources = load_manifest_sources().await;
+        manifests_fingerprint = manifest_sources_fingerprint(&sources);
 
-        if new_interval_minutes != current_interval_minutes {
-            current_interval_minutes = new_interval_minutes;
-            interval = time::interval(Duration::from_secs(current_interval_minutes * 60));
-            emit_log(
-                &app_handle,
-                "INFO",
-                &format!(
-                    "Scan interval updated to {} minutes",
-                    current_interval_minutes
-                ),
-            );
+        // Nothing to scan; stay backed off until a manifests directory appears,
+        // which changes the fingerprint and wakes the schedule
+        if !check_launcher(&app_handle, &sources) {
+            schedule.back_off(MAX_SCAN_BACKOFF_MULTIPLIER);
+            continue;
         }
 
-        match scan_epic_games_with_metadata(&metadata_cache).await {
+        match scan_epic_games(&app_handle, &sources, &games, &metadata_cache, &cancel_flag).await {
             Ok(scanned_games) => {
-                let mut games_lock = match games.lock() {
-                    Ok(lock) => lock,
-                    Err(e) => {
-                        eprintln!("Failed to lock games during periodic scan: {}", e);
-                        continue;
-                    }
-                };
-
-                let old_count = games_lock.len();
-                games_lock.clear();
+                let (delta, removed, new_count) = {
+                    let mut games_lock = match games.lock() {
+                        Ok(lock) => lock,
+                        Err(e) => {
+                            eprintln!("Failed to lock games during periodic scan: {}", e);
+                            continue;
+                        }
+                    };
 
-                for game in &scanned_games {
-                    games_lock.insert(game.app_name.clone(), game.clone());
-                }
+                    let delta = library_delta(&games_lock, &scanned_games);
+                    let removed = find_uninstalled(&games_lock, &scanned_games);
+                    games_lock.clear();
 
-                let new_count = games_lock.len();
-                drop(games_lock);
+                    for game in &scanned_games {
+                        games_lock.insert(game.app_name.clone(), game.clone());
+                    }
+                    (delta, removed, games_lock.len())
+                };
+                announce_uninstalled(&app_handle, removed, &scanned_games, &uninstalled_games)
+                    .await;
 
-                // Emit event to frontend if game count changed
-                if old_count != new_count {
+                if !delta.is_empty() {
+                    let installed = record_library_changes(&scanned_games, &settings)
+                        .into_iter()
+                        .filter(|c| c.kind == "installed")
+                        .map(|c| c.display_name)
+                        .collect::<Vec<_>>();
+                    mods::notifications::notify_new_games(&app_handle, &installed);
+                    schedule.reset_backoff();
                     emit_log(
                         &app_handle,
                         "INFO",
@@ -365,8 +1662,11 @@ async fn periodic_scan(
                             new_count
                         ),
                     );
-                    let _ = app_handle.emit("games-updated", &scanned_games);
+                    spawn_upload_on_detect(&app_handle, &delta, &scanned_games, &settings);
+                    mods::events::emit(&app_handle, AppEvent::GamesUpdated(delta));
+                    mods::tray::refresh(&app_handle);
                 } else {
+                    schedule.back_off(MAX_SCAN_BACKOFF_MULTIPLIER);
                     emit_log(
                         &app_handle,
                         "INFO",
@@ -376,6 +1676,14 @@ async fn periodic_scan(
                         ),
                     );
                 }
+
+                spawn_metadata_enrichment(
+                    app_handle.clone(),
+                    games.clone(),
+                    metadata_cache.clone(),
+                    settings.clone(),
+                    scanned_games,
+                );
             }
             Err(e) => {
                 eprintln!("Periodic scan failed: {}", e);
@@ -384,171 +1692,847 @@ async fn periodic_scan(
     }
 }
 
+// Scans once at startup, replacing the cached library shown until now; skipped
+// when automatic scanning is off. Returns the scanned games so metadata can be
+// fetched once startup settles.
+async fn initial_scan(
+    app_handle: &AppHandle,
+    games: &GameStore,
+    metadata_cache: &MetadataCache,
+    settings: &SettingsState,
+    cancel_flag: &ScanCancelFlag,
+    uninstalled_games: &UninstalledGames,
+) -> Option<Vec<GameInfo>> {
+    if !settings.lock().map(|s| s.auto_scan_enabled).unwrap_or(true) {
+        emit_log(
+            app_handle,
+            "INFO",
+            "Automatic scanning is off; showing the library from the last scan",
+        );
+        return None;
+    }
+    let sources = load_manifest_sources().await;
+    if !check_launcher(app_handle, &sources) {
+        return None;
+    }
+
+    let scanned_games =
+        match scan_epic_games(app_handle, &sources, games, metadata_cache, cancel_flag).await {
+            Ok(scanned_games) => scanned_games,
+            Err(e) => {
+                eprintln!("Initial scan failed: {}", e);
+                return None;
+            }
+        };
+
+    let (removed, delta, had_cache) = {
+        let mut games_lock = match games.lock() {
+            Ok(lock) => lock,
+            Err(e) => {
+                eprintln!("Failed to lock games during initial scan: {}", e);
+                return None;
+            }
+        };
+        // Games in the cache but no longer on disk went away while the app was closed
+        let removed = find_uninstalled(&games_lock, &scanned_games);
+        let delta = library_delta(&games_lock, &scanned_games);
+        // Without a cached library every game looks new; the schedule covers those
+        let had_cache = !games_lock.is_empty();
+        games_lock.clear();
+        for game in &scanned_games {
+            games_lock.insert(game.app_name.clone(), game.clone());
+        }
+        (removed, delta, had_cache)
+    };
+
+    println!(
+        "Initial scan completed. Found {} games.",
+        scanned_games.len()
+    );
+
+    record_library_changes(&scanned_games, settings);
+    announce_uninstalled(app_handle, removed, &scanned_games, uninstalled_games).await;
+
+    // Tell the frontend what differs from the cached library it loaded
+    if !delta.is_empty() {
+        if had_cache {
+            spawn_upload_on_detect(app_handle, &delta, &scanned_games, settings);
+        }
+        mods::events::emit(app_handle, AppEvent::GamesUpdated(delta));
+    }
+    mods::tray::refresh(app_handle);
+    Some(scanned_games)
+}
+
+// Deferred startup work: autostart registration, network checks and the
+// supervised periodic tasks
+fn start_background_services(
+    app_handle: AppHandle,
+    games: GameStore,
+    metadata_cache: MetadataCache,
+    settings: SettingsState,
+    cancel_flag: ScanCancelFlag,
+    uninstalled_games: UninstalledGames,
+) {
+    let autostart_settings = settings.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Err(e) = setup_auto_start(&autostart_settings) {
+            eprintln!("Failed to register autostart: {}", e);
+        }
+    });
+
+    // Detect the launcher version off the main thread so uploads don't pay for it
+    tauri::async_runtime::spawn_blocking(mods::launcher::get_launcher_version);
+
+    // Submit crash reports from previous runs if the user opted in
+    let crash_reporting_enabled = settings
+        .lock()
+        .map(|s| s.crash_reporting_enabled)
+        .unwrap_or(false);
+    if crash_reporting_enabled {
+        tauri::async_runtime::spawn(async move {
+            match mods::crash::submit_pending_crash_reports().await {
+                Ok(0) => {}
+                Ok(count) => println!("Submitted {} crash report(s)", count),
+                Err(e) => eprintln!("Failed to submit crash reports: {}", e),
+            }
+        });
+    }
+
+    // Opt-in automation API
+    let (local_api_enabled, local_api_port) = settings
+        .lock()
+        .map(|s| (s.local_api_enabled, s.local_api_port))
+        .unwrap_or((false, 0));
+    if local_api_enabled {
+        tauri::async_runtime::spawn(mods::local_api::serve(app_handle.clone(), local_api_port));
+    }
+
+    // Apply edits made to settings.json outside the app
+    mods::supervisor::spawn_supervised(app_handle.clone(), "settings_watch", {
+        let app_handle = app_handle.clone();
+        let settings = settings.clone();
+        move || mods::settings_watch::watch_settings_file(app_handle.clone(), settings.clone())
+    });
+
+    // Start periodic scanning
+    mods::supervisor::spawn_supervised(app_handle.clone(), "periodic_scan", {
+        let app_handle = app_handle.clone();
+        let games = games.clone();
+        let metadata_cache = metadata_cache.clone();
+        let settings = settings.clone();
+        let cancel_flag = cancel_flag.clone();
+        let uninstalled_games = uninstalled_games.clone();
+        move || {
+            periodic_scan(
+                app_handle.clone(),
+                games.clone(),
+                metadata_cache.clone(),
+                settings.clone(),
+                cancel_flag.clone(),
+                uninstalled_games.clone(),
+            )
+        }
+    });
+
+    // Refresh metadata for known catalog items on a slower schedule
+    mods::supervisor::spawn_supervised(app_handle.clone(), "metadata_refresh", {
+        let app_handle = app_handle.clone();
+        let games = games.clone();
+        let metadata_cache = metadata_cache.clone();
+        let settings = settings.clone();
+        move || {
+            periodic_metadata_refresh(
+                app_handle.clone(),
+                games.clone(),
+                metadata_cache.clone(),
+                settings.clone(),
+            )
+        }
+    });
+
+    // Aggregate usage telemetry, only sent when the user opted in
+    mods::supervisor::spawn_supervised(app_handle.clone(), "telemetry_report", {
+        let settings = settings.clone();
+        move || mods::telemetry::periodic_report(settings.clone())
+    });
+
+    // Wake the scan and upload tasks after the machine resumes from sleep
+    mods::supervisor::spawn_supervised(app_handle.clone(), "resume_watch", {
+        let app_handle = app_handle.clone();
+        move || mods::power::watch_for_resume(app_handle.clone())
+    });
+
+    // Daily summary notification, when enabled
+    mods::supervisor::spawn_supervised(app_handle.clone(), "daily_digest", {
+        let app_handle = app_handle.clone();
+        let settings = settings.clone();
+        move || mods::digest::periodic_digest(app_handle.clone(), settings.clone())
+    });
+
+    // Retry uploads and failed metadata as soon as the network returns
+    mods::supervisor::spawn_supervised(app_handle.clone(), "network_watch", {
+        let app_handle = app_handle.clone();
+        let games = games.clone();
+        let metadata_cache = metadata_cache.clone();
+        let settings = settings.clone();
+        move || {
+            mods::network::watch_connectivity(
+                app_handle.clone(),
+                games.clone(),
+                metadata_cache.clone(),
+                settings.clone(),
+            )
+        }
+    });
+
+    // Start periodic upload
+    mods::supervisor::spawn_supervised(app_handle.clone(), "periodic_upload", {
+        let app_handle = app_handle.clone();
+        let games = games.clone();
+        let settings = settings.clone();
+        move || periodic_upload(app_handle.clone(), games.clone(), settings.clone())
+    });
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    let games: GameStore = Arc::new(Mutex::new(HashMap::new()));
-    let metadata_cache: MetadataCache = Arc::new(Mutex::new(HashMap::new()));
-    let settings: SettingsState = Arc::new(Mutex::new(load_settings_from_file()));
+    mods::crash::install_panic_hook();
+    #[cfg(target_os = "linux")]
+    migrate_legacy_data_dir();
+
+    // `--scan-once`, `--upload-all`, `--once` and `--export-library <path>` run headless and exit
+    match mods::cli::parse_args(std::env::args().skip(1)) {
+        Ok(Some(command)) => std::process::exit(mods::cli::run(command)),
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    }
 
-    // Setup auto-start
-    let _ = setup_auto_start();
+    let games: GameStore = Arc::new(Mutex::new(load_library_cache_from_file()));
+    let initial_settings = load_settings_from_file();
+    let metadata_cache: MetadataCache = Arc::new(Mutex::new(MetadataCacheData::new(
+        load_metadata_cache_from_file(),
+        initial_settings.metadata_cache_max_entries,
+        initial_settings.metadata_cache_max_bytes,
+    )));
+    mods::priority::set_enabled(initial_settings.background_priority);
+    mods::telemetry::set_enabled(initial_settings.telemetry_enabled);
+    mods::http_cache::set_limits(
+        initial_settings.metadata_cache_max_entries,
+        initial_settings.metadata_cache_max_bytes,
+    );
+    let settings: SettingsState = Arc::new(Mutex::new(initial_settings));
+    let scan_cancel_flag: ScanCancelFlag = Arc::new(AtomicBool::new(false));
+    let uninstalled_games: UninstalledGames =
+        Arc::new(Mutex::new(load_uninstalled_games_from_file()));
 
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
-        .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
-            // When a second instance is launched, show the existing window
-            if let Some(window) = app.get_webview_window("main") {
-                let _ = window.show();
-                let _ = window.set_focus();
+        .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+            // A Jump List task runs in this instance; any other launch shows the window
+            match mods::jump_list::task_from_args(&args) {
+                Some(task) => mods::jump_list::run_task(app, task),
+                None => mods::window::show_main_window(app),
             }
         }))
         .manage(games.clone())
         .manage(metadata_cache.clone())
         .manage(settings.clone())
+        .manage(scan_cancel_flag.clone())
+        .manage(uninstalled_games.clone())
         // Removed uploaded_manifests management - API handles duplicates
         .invoke_handler(tauri::generate_handler![
             mods::commands::show_window,
             mods::commands::hide_window,
             mods::commands::minimize_window,
             mods::commands::get_installed_games,
+            mods::commands::get_games,
+            mods::commands::open_install_location,
+            mods::commands::open_manifest_location,
+            mods::commands::show_in_epic_launcher,
+            mods::commands::get_launcher_status,
+            mods::commands::get_elevation_status,
+            mods::commands::run_elevated_task,
+            mods::commands::reconcile_with_server,
+            mods::commands::get_contribution_opportunities,
             mods::commands::scan_games_now,
+            mods::commands::cancel_scan,
             mods::commands::get_settings,
             mods::commands::set_settings,
             mods::commands::upload_manifest,
             mods::commands::upload_all_manifests,
             mods::commands::open_directory,
+            mods::commands::get_crash_reports,
+            mods::commands::clear_crash_reports,
+            mods::commands::get_cache_stats,
+            mods::commands::clear_metadata_cache,
+            mods::commands::refresh_metadata,
+            mods::commands::check_launcher_consistency,
+            mods::commands::get_launcher_version,
+            mods::commands::get_telemetry_preview,
+            mods::commands::get_release_notes,
+            mods::commands::run_diagnostics,
+            mods::commands::export_diagnostics,
+            mods::commands::import_manifests,
+            mods::commands::get_library_history,
+            mods::commands::upload_final_manifest,
+            mods::commands::query_games,
+            mods::commands::get_whats_new,
+            mods::commands::get_activity_timeline,
+            mods::commands::get_local_api_token,
+            mods::commands::restart_app,
+            mods::commands::export_manifest_bundle,
+            mods::commands::export_all_manifests,
+            mods::commands::preview_sidecar_uploads,
+            mods::commands::backup_app_data,
+            mods::commands::restore_app_data,
+            mods::commands::switch_profile,
+            mods::commands::set_uploads_paused,
+            mods::commands::get_failed_uploads,
+            mods::commands::retry_failed_uploads,
+            mods::commands::install_background_service,
+            mods::commands::uninstall_background_service,
+            mods::commands::is_background_service_installed,
         ])
         .setup(move |app| {
             let app_handle = app.handle().clone();
 
             // Create tray menu
-            let show_item = MenuItemBuilder::new("Show").id("show").build(app)?;
-            let hide_item = MenuItemBuilder::new("Hide").id("hide").build(app)?;
-            let quit_item = MenuItemBuilder::new("Quit").id("quit").build(app)?;
-
-            let menu = MenuBuilder::new(app)
-                .item(&show_item)
-                .item(&hide_item)
-                .separator()
-                .item(&quit_item)
-                .build()?;
-
-            // Create tray icon
-            let _tray = TrayIconBuilder::new()
-                .icon(app.default_window_icon().unwrap().clone())
+            let menu = mods::tray::build_menu(&app_handle)?;
+
+            // Create tray icon, matching the system theme the main window reports
+            let theme = app
+                .get_webview_window("main")
+                .and_then(|window| window.theme().ok())
+                .unwrap_or(tauri::Theme::Dark);
+            let tray_icon = mods::tray::icon_for_theme(theme)
+                .unwrap_or_else(|| app.default_window_icon().unwrap().clone());
+            // Popover mode is read once at startup, like the rest of the tray setup
+            let popover_enabled = cfg!(target_os = "macos")
+                && settings.lock().map(|s| s.tray_popover).unwrap_or(false);
+            let tooltip = mods::tray::tooltip(&settings.lock().unwrap_or_else(|e| e.into_inner()));
+            let _tray = TrayIconBuilder::with_id(mods::tray::TRAY_ID)
+                .icon(tray_icon)
+                .icon_as_template(true)
                 .menu(&menu)
-                .tooltip("EGData Client")
+                .tooltip(tooltip)
                 .on_menu_event(move |app, event| match event.id().as_ref() {
-                    "show" => {
-                        if let Some(window) = app.get_webview_window("main") {
-                            let _ = window.show();
-                            let _ = window.set_focus();
-                        }
-                    }
-                    "hide" => {
-                        if let Some(window) = app.get_webview_window("main") {
-                            let _ = window.hide();
-                        }
-                    }
+                    "show" => mods::window::show_main_window(app),
+                    "hide" => mods::window::hide_main_window(app),
                     "quit" => {
                         app.exit(0);
                     }
-                    _ => {}
+                    mods::tray::PAUSE_MENU_ID => mods::pause::toggle(app),
+                    id if mods::tray::handle_game_action(app, id) => {}
+                    id => {
+                        if let Some(name) = id.strip_prefix(mods::tray::PROFILE_MENU_PREFIX) {
+                            let settings = app.state::<SettingsState>();
+                            if let Err(e) = mods::profiles::switch_profile(app, &settings, name) {
+                                emit_log(app, "ERROR", &e);
+                            }
+                        }
+                    }
                 })
-                .on_tray_icon_event(|tray, event| match event {
-                    TrayIconEvent::Click {
+                .show_menu_on_left_click(!popover_enabled)
+                .on_tray_icon_event(move |tray, event| {
+                    if let TrayIconEvent::Click {
                         button: tauri::tray::MouseButton::Left,
                         button_state: tauri::tray::MouseButtonState::Up,
+                        rect,
                         ..
-                    } => {
-                        let app_handle = tray.app_handle();
-                        if let Some(window) = app_handle.get_webview_window("main") {
-                            if window.is_visible().unwrap_or(false) {
-                                let _ = window.hide();
-                            } else {
-                                let _ = window.show();
-                                let _ = window.set_focus();
+                    } = event
+                    {
+                        if popover_enabled {
+                            if let Err(e) = mods::window::toggle_popover(tray.app_handle(), rect) {
+                                emit_log(tray.app_handle(), "ERROR", &e);
                             }
+                        } else {
+                            mods::window::toggle_main_window(tray.app_handle());
                         }
                     }
-                    _ => {}
                 })
                 .build(app)?;
 
             // Ensure the main window starts hidden
-            if let Some(window) = app.get_webview_window("main") {
-                let _ = window.show();
+            mods::window::show_main_window(&app_handle);
+
+            mods::jump_list::register();
+            let args = std::env::args().collect::<Vec<_>>();
+            if let Some(task) = mods::jump_list::task_from_args(&args) {
+                mods::jump_list::run_task(&app_handle, task);
             }
 
             // Handle window events
             if let Some(window) = app.get_webview_window("main") {
-                let window_clone = window.clone();
+                let app_handle_for_window = app_handle.clone();
                 window.on_window_event(move |event| {
                     match event {
                         WindowEvent::CloseRequested { api, .. } => {
                             // Prevent the window from closing and hide it instead
                             api.prevent_close();
-                            let _ = window_clone.hide();
+                            mods::window::hide_main_window(&app_handle_for_window);
+                        }
+                        WindowEvent::ThemeChanged(theme) => {
+                            mods::tray::apply_theme(&app_handle_for_window, *theme);
                         }
                         _ => {}
                     }
                 });
             }
 
-            let app_handle_for_periodic = app_handle.clone();
-            let games_for_periodic = games.clone();
-            let metadata_cache_for_periodic = metadata_cache.clone();
-            let settings_for_periodic = settings.clone();
+            let games_for_startup = games.clone();
+            let metadata_cache_for_startup = metadata_cache.clone();
+            let settings_for_startup = settings.clone();
+            let cancel_flag_for_startup = scan_cancel_flag.clone();
+            let uninstalled_for_startup = uninstalled_games.clone();
 
-            // Perform initial scan
-            let games_for_initial = games.clone();
-            let metadata_cache_for_initial = metadata_cache.clone();
+            // The scan only reads local files; everything that touches the
+            // network waits until the window and tray are up
             tauri::async_runtime::spawn(async move {
-                match scan_epic_games_with_metadata(&metadata_cache_for_initial).await {
-                    Ok(scanned_games) => {
-                        let mut games_lock = match games_for_initial.lock() {
-                            Ok(lock) => lock,
-                            Err(e) => {
-                                eprintln!("Failed to lock games during initial scan: {}", e);
-                                return;
-                            }
-                        };
-
-                        for game in &scanned_games {
-                            games_lock.insert(game.app_name.clone(), game.clone());
-                        }
-
-                        println!(
-                            "Initial scan completed. Found {} games.",
-                            scanned_games.len()
-                        );
+                let scanned_games = initial_scan(
+                    &app_handle,
+                    &games_for_startup,
+                    &metadata_cache_for_startup,
+                    &settings_for_startup,
+                    &cancel_flag_for_startup,
+                    &uninstalled_for_startup,
+                )
+                .await;
 
-                        // Emit initial games to frontend
-                        let _ = app_handle.emit("games-updated", &scanned_games);
-                    }
-                    Err(e) => {
-                        eprintln!("Initial scan failed: {}", e);
-                    }
+                time::sleep(POST_STARTUP_DELAY).await;
+                if let Some(scanned_games) = scanned_games {
+                    spawn_metadata_enrichment(
+                        app_handle.clone(),
+                        games_for_startup.clone(),
+                        metadata_cache_for_startup.clone(),
+                        settings_for_startup.clone(),
+                        scanned_games,
+                    );
                 }
+                start_background_services(
+                    app_handle,
+                    games_for_startup,
+                    metadata_cache_for_startup,
+                    settings_for_startup,
+                    cancel_flag_for_startup,
+                    uninstalled_for_startup,
+                );
             });
 
-            // Start periodic scanning
-            tauri::async_runtime::spawn(periodic_scan(
-                app_handle_for_periodic.clone(),
-                games_for_periodic.clone(),
-                metadata_cache_for_periodic,
-                settings_for_periodic.clone(),
-            ));
-
-            // Start periodic upload
-            tauri::async_runtime::spawn(periodic_upload(
-                app_handle_for_periodic,
-                games_for_periodic,
-                settings_for_periodic,
-            ));
-
             Ok(())
         })
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mods::api::MockEgdataApi;
+    use std::path::PathBuf;
+
+    fn item_fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/items")
+            .join(name)
+    }
+
+    fn assert_sample_game(game: &GameInfo) {
+        assert_eq!(game.app_name, "SampleGame");
+        assert_eq!(game.display_name, "Sample Game");
+        assert_eq!(
+            game.manifest_hash,
+            "3f786850e387550fdab836ed7e6dc881de23001b"
+        );
+        assert_eq!(game.installation_guid, "0A1B2C3D4E5F60718293A4B5C6D7E8F9");
+        assert_eq!(game.environment, "Live");
+    }
+
+    #[test]
+    fn parses_plain_item() {
+        let game = parse_item_file(&item_fixture("plain.item")).unwrap();
+        assert_sample_game(&game);
+        assert_eq!(game.format_version, 0);
+    }
+
+    #[test]
+    fn parses_item_with_utf8_bom() {
+        let game = parse_item_file(&item_fixture("utf8_bom.item")).unwrap();
+        assert_sample_game(&game);
+        assert!(!game.item_content.starts_with('\u{feff}'));
+    }
+
+    #[test]
+    fn parses_utf16_items_in_both_byte_orders() {
+        for name in ["utf16_le.item", "utf16_be.item"] {
+            let game = parse_item_file(&item_fixture(name)).unwrap();
+            assert_sample_game(&game);
+        }
+    }
+
+    #[test]
+    fn partially_written_item_is_a_parse_error() {
+        for name in ["partial_write.item", "empty.item"] {
+            let err = parse_item_file(&item_fixture(name)).unwrap_err();
+            assert!(err.starts_with("Failed to parse JSON"), "{}: {}", name, err);
+        }
+    }
+
+    #[test]
+    fn missing_format_version_and_optional_fields_use_defaults() {
+        let game = parse_item_file(&item_fixture("format_version_missing.item")).unwrap();
+        assert_eq!(game.format_version, 0);
+        assert_eq!(game.display_name, game.app_name);
+        assert_eq!(game.environment, "");
+        assert_eq!(game.main_game_app_name, "");
+    }
+
+    #[test]
+    fn string_format_version_is_read_as_a_number() {
+        let game = parse_item_file(&item_fixture("format_version_string.item")).unwrap();
+        assert_sample_game(&game);
+        assert_eq!(game.format_version, 3);
+    }
+
+    #[test]
+    fn future_format_version_still_parses() {
+        let game = parse_item_file(&item_fixture("format_version_future.item")).unwrap();
+        assert_sample_game(&game);
+        assert_eq!(game.format_version, 99);
+    }
+
+    #[test]
+    fn item_without_manifest_hash_is_rejected() {
+        let err = parse_item_file(&item_fixture("missing_manifest_hash.item")).unwrap_err();
+        assert!(err.contains("ManifestHash"), "{}", err);
+    }
+
+    // Upload tests share the app data directory (ledger, retry queue), which
+    // points at a scratch folder for this test process
+    static APP_DATA: Lazy<PathBuf> = Lazy::new(|| {
+        let root = std::env::temp_dir().join(format!("egdata-client-tests-{}", std::process::id()));
+        std::env::set_var("XDG_DATA_HOME", &root);
+        std::env::set_var("APPDATA", &root);
+        root
+    });
+    static UPLOAD_TESTS: Mutex<()> = Mutex::new(());
+
+    fn isolated_app_data() -> std::sync::MutexGuard<'static, ()> {
+        Lazy::force(&APP_DATA);
+        UPLOAD_TESTS.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn binary_manifest(payload: &[u8]) -> Vec<u8> {
+        [BINARY_MANIFEST_MAGIC.as_slice(), payload].concat()
+    }
+
+    // Lays out an install the way the launcher does (`.item` plus the
+    // `.manifest` under `.egstore`) and scans its `.item`
+    fn scanned_install(app_name: &str, manifest: &[u8]) -> GameInfo {
+        let root = APP_DATA.join("installs").join(app_name);
+        let install_location = root.join("game");
+        let guid = mods::companions::content_hash(app_name.as_bytes())[..32].to_uppercase();
+        let manifest_path = get_egstore_manifest_path(&install_location.to_string_lossy(), &guid);
+        fs::create_dir_all(manifest_path.parent().unwrap()).unwrap();
+        fs::write(&manifest_path, manifest).unwrap();
+
+        let item = serde_json::json!({
+            "FormatVersion": 0,
+            "AppName": app_name,
+            "DisplayName": app_name,
+            "InstallationGuid": guid,
+            "InstallLocation": install_location,
+            "ManifestHash": mods::companions::content_hash(manifest),
+            "CatalogNamespace": "testnamespace",
+            "CatalogItemId": format!("{}-item", app_name),
+            "AppVersionString": "1.0.0",
+            "BuildLabel": "Live",
+        });
+        let item_path = root.join(format!("{}.item", guid));
+        fs::write(&item_path, item.to_string()).unwrap();
+        parse_item_file(&item_path).unwrap()
+    }
+
+    fn upload_all(api: &MockEgdataApi, games: &[GameInfo]) -> Vec<UploadStatus> {
+        let store: GameStore = Arc::new(Mutex::new(
+            games
+                .iter()
+                .map(|g| (g.app_name.clone(), g.clone()))
+                .collect(),
+        ));
+        tauri::async_runtime::block_on(upload_all_manifests_with(
+            api,
+            &store,
+            &Settings::default(),
+            false,
+        ))
+        .unwrap()
+    }
+
+    fn statuses(results: &[UploadStatus]) -> Vec<&str> {
+        results.iter().map(|r| r.status.as_str()).collect()
+    }
+
+    #[test]
+    fn scanned_manifest_is_uploaded_and_recorded_in_the_ledger() {
+        let _data = isolated_app_data();
+        let manifest = binary_manifest(b"uploaded once");
+        let game = scanned_install("UploadedGame", &manifest);
+        let api = MockEgdataApi::default();
+
+        assert_eq!(
+            statuses(&upload_all(&api, std::slice::from_ref(&game))),
+            ["uploaded"]
+        );
+        {
+            let uploads = api.uploads.lock().unwrap();
+            assert_eq!(uploads.len(), 1);
+            assert_eq!(uploads[0].manifest_hash, game.manifest_hash);
+            assert_eq!(uploads[0].manifest_bytes, manifest);
+            assert_eq!(uploads[0].item_json, game.item_content);
+        }
+        assert!(mods::ledger::load_ledger().contains_key(&game.manifest_hash));
+
+        // The next run finds it on the server instead of sending it again
+        assert_eq!(statuses(&upload_all(&api, &[game])), ["already_uploaded"]);
+        assert_eq!(api.uploads.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn manifest_shared_within_a_batch_is_sent_once() {
+        let _data = isolated_app_data();
+        let manifest = binary_manifest(b"shared in batch");
+        let games = [
+            scanned_install("BatchBase", &manifest),
+            scanned_install("BatchCopy", &manifest),
+        ];
+        let api = MockEgdataApi::default();
+
+        let results = upload_all(&api, &games);
+        let mut results = statuses(&results);
+        results.sort();
+        assert_eq!(results, ["skipped_duplicate", "uploaded"]);
+        assert_eq!(api.uploads.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn manifest_in_the_ledger_for_another_game_is_skipped() {
+        let _data = isolated_app_data();
+        let manifest = binary_manifest(b"shared across runs");
+        let base = scanned_install("LedgerBase", &manifest);
+        let copy = scanned_install("LedgerCopy", &manifest);
+        let api = MockEgdataApi::default();
+
+        assert_eq!(statuses(&upload_all(&api, &[base])), ["uploaded"]);
+        let results = upload_all(&api, &[copy]);
+        assert_eq!(statuses(&results), ["skipped_duplicate"]);
+        assert!(results[0]
+            .message
+            .as_deref()
+            .unwrap()
+            .contains("LedgerBase"));
+        assert_eq!(api.uploads.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn manifest_already_on_the_server_is_not_uploaded() {
+        let _data = isolated_app_data();
+        let game = scanned_install("ServerHasIt", &binary_manifest(b"already there"));
+        let api = MockEgdataApi::default();
+        api.existing_manifests
+            .lock()
+            .unwrap()
+            .insert(game.manifest_hash.clone());
+
+        assert_eq!(
+            statuses(&upload_all(&api, std::slice::from_ref(&game))),
+            ["already_uploaded"]
+        );
+        assert!(api.uploads.lock().unwrap().is_empty());
+        assert!(!mods::retry_queue::load_failed_uploads().contains_key(&game.app_name));
+    }
+
+    #[test]
+    fn failed_upload_is_queued_for_retry() {
+        let _data = isolated_app_data();
+        let game = scanned_install("RejectedGame", &binary_manifest(b"server error"));
+        let api = MockEgdataApi {
+            upload_error: Some("HTTP 503 Service Unavailable".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            statuses(&upload_all(&api, std::slice::from_ref(&game))),
+            ["failed"]
+        );
+        assert!(!mods::ledger::load_ledger().contains_key(&game.manifest_hash));
+        let failed = mods::retry_queue::load_failed_uploads();
+        let queued = failed.get(&game.app_name).expect("failure was not queued");
+        assert_eq!(queued.attempts, 1);
+        assert!(!queued.permanent);
+    }
+
+    #[test]
+    fn hash_mismatch_is_not_queued_for_retry() {
+        let _data = isolated_app_data();
+        let game = scanned_install("UpdatingGame", &binary_manifest(b"before update"));
+        fs::write(
+            get_egstore_manifest_path(&game.install_location, &game.installation_guid),
+            binary_manifest(b"after update"),
+        )
+        .unwrap();
+        let api = MockEgdataApi::default();
+
+        assert_eq!(
+            statuses(&upload_all(&api, std::slice::from_ref(&game))),
+            ["hash_mismatch"]
+        );
+        assert!(!mods::retry_queue::load_failed_uploads().contains_key(&game.app_name));
+    }
+
+    #[test]
+    fn companions_are_sent_only_to_endpoints_that_take_them() {
+        let _data = isolated_app_data();
+        let base = scanned_install("BaseWithCompanion", &binary_manifest(b"base build"));
+        let dlc = scanned_install("DlcWithCompanion", &binary_manifest(b"dlc build"));
+        // Both installs carry the same `.mancpn`
+        for game in [&base, &dlc] {
+            let manifest_path =
+                get_egstore_manifest_path(&game.install_location, &game.installation_guid);
+            fs::write(manifest_path.with_extension("mancpn"), b"shared companion").unwrap();
+        }
+        let companion_uploads = |api: &MockEgdataApi| {
+            api.uploads
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|upload| upload.kind == "mancpn")
+                .count()
+        };
+
+        let api = MockEgdataApi::default();
+        upload_all(&api, &[base.clone(), dlc.clone()]);
+        assert_eq!(companion_uploads(&api), 0);
+
+        let api = MockEgdataApi {
+            companions: true,
+            ..Default::default()
+        };
+        upload_all(&api, &[base, dlc]);
+        assert_eq!(companion_uploads(&api), 1);
+    }
+
+    // Binary manifest with the given chunk (hash, guid) list, laid out the way
+    // the launcher writes it: header, then a zlib body of meta + chunk data list
+    fn binary_manifest_with_chunks(chunks: &[(u64, [u32; 4])]) -> Vec<u8> {
+        use std::io::Write;
+        let mut body = Vec::new();
+        body.extend(8u32.to_le_bytes());
+        body.extend([0u8; 4]);
+        let count = chunks.len() as u32;
+        body.extend((9 + count * 28).to_le_bytes());
+        body.push(0);
+        body.extend(count.to_le_bytes());
+        for (_, guid) in chunks {
+            guid.iter().for_each(|part| body.extend(part.to_le_bytes()));
+        }
+        for (hash, _) in chunks {
+            body.extend(hash.to_le_bytes());
+        }
+
+        let mut encoder =
+            flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&body).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mut manifest = BINARY_MANIFEST_MAGIC.to_vec();
+        manifest.extend(41u32.to_le_bytes());
+        manifest.extend((body.len() as u32).to_le_bytes());
+        manifest.extend((compressed.len() as u32).to_le_bytes());
+        manifest.extend([0u8; 20]);
+        manifest.push(1);
+        manifest.extend(0u32.to_le_bytes());
+        manifest.extend(compressed);
+        manifest
+    }
+
+    #[test]
+    fn only_chunks_of_the_installed_build_are_counted() {
+        let _data = isolated_app_data();
+        let game = scanned_install(
+            "ChunkedGame",
+            &binary_manifest_with_chunks(&[(0x0123_4567_89AB_CDEF, [1, 2, 3, 4])]),
+        );
+        let egstore = get_egstore_manifest_path(&game.install_location, &game.installation_guid)
+            .parent()
+            .unwrap()
+            .join("bps/ChunksV4/07");
+        fs::create_dir_all(&egstore).unwrap();
+        fs::write(
+            egstore.join("0123456789ABCDEF_00000001000000020000000300000004.chunk"),
+            b"ours",
+        )
+        .unwrap();
+        fs::write(
+            egstore.join("FEDCBA9876543210_00000005000000060000000700000008.chunk"),
+            b"another build",
+        )
+        .unwrap();
+
+        let availability = tauri::async_runtime::block_on(mods::chunks::chunk_availability(&game));
+        assert_eq!(availability.chunk_files, 1);
+        assert_eq!(availability.chunk_bytes, 4);
+    }
+
+    fn manifest_fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/manifests")
+            .join(name)
+    }
+
+    fn upload_fixture_pair(api: &MockEgdataApi, manifest_path: PathBuf) -> UploadStatus {
+        let game = parse_item_file(&manifest_fixture("sample.item")).unwrap();
+        tauri::async_runtime::block_on(upload_manifest_files(
+            api,
+            &game,
+            &game.item_content,
+            manifest_path,
+            &Settings::default(),
+        ))
+        .unwrap()
+    }
+
+    // The `.item` records the SHA-1 of the `.manifest` file as the launcher
+    // downloaded it, header included
+    #[test]
+    fn manifest_matching_its_item_hash_is_uploaded() {
+        let _data = isolated_app_data();
+        let api = MockEgdataApi::default();
+
+        let status = upload_fixture_pair(&api, manifest_fixture("sample.manifest"));
+        assert_eq!(status.status, "uploaded");
+        let uploads = api.uploads.lock().unwrap();
+        assert_eq!(
+            uploads[0].manifest_bytes,
+            fs::read(manifest_fixture("sample.manifest")).unwrap()
+        );
+    }
+
+    #[test]
+    fn manifest_not_matching_its_item_hash_is_rejected() {
+        let _data = isolated_app_data();
+        let api = MockEgdataApi::default();
+        let other_build = APP_DATA.join("other_build.manifest");
+        let mut bytes = fs::read(manifest_fixture("sample.manifest")).unwrap();
+        *bytes.last_mut().unwrap() ^= 0xFF;
+        fs::create_dir_all(&*APP_DATA).unwrap();
+        fs::write(&other_build, bytes).unwrap();
+
+        let status = upload_fixture_pair(&api, other_build);
+        assert_eq!(status.status, "hash_mismatch");
+        assert!(api.uploads.lock().unwrap().is_empty());
+    }
+}
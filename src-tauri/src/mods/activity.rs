@@ -0,0 +1,124 @@
+use super::models::ActivityEvent;
+use super::utils::get_app_data_path;
+use once_cell::sync::Lazy;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+const ACTIVITY_FILE: &str = "activity.jsonl";
+// The file is compacted back down to this many events once it grows past twice that
+const MAX_EVENTS: usize = 5000;
+
+struct Timeline {
+    events: Vec<ActivityEvent>,
+    next_id: u64,
+}
+
+// Loaded lazily; appended to in memory and on disk as events happen
+static TIMELINE: Lazy<Mutex<Timeline>> = Lazy::new(|| {
+    let events = load_events();
+    let next_id = events.last().map_or(1, |e| e.id + 1);
+    Mutex::new(Timeline { events, next_id })
+});
+
+fn get_activity_path() -> PathBuf {
+    get_app_data_path().join(ACTIVITY_FILE)
+}
+
+fn load_events() -> Vec<ActivityEvent> {
+    fs::read_to_string(get_activity_path())
+        .map(|contents| {
+            contents
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn rewrite_file(events: &[ActivityEvent]) {
+    let contents = events
+        .iter()
+        .filter_map(|e| serde_json::to_string(e).ok())
+        .map(|line| line + "\n")
+        .collect::<String>();
+    if let Err(e) = fs::write(get_activity_path(), contents) {
+        eprintln!("Failed to compact activity timeline: {}", e);
+    }
+}
+
+fn append_to_file(event: &ActivityEvent) {
+    if let Err(e) = fs::create_dir_all(get_app_data_path()) {
+        eprintln!("Failed to create app data directory: {}", e);
+        return;
+    }
+    let Ok(line) = serde_json::to_string(event) else {
+        return;
+    };
+    let result = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(get_activity_path())
+        .and_then(|mut file| writeln!(file, "{}", line));
+    if let Err(e) = result {
+        eprintln!("Failed to write activity event: {}", e);
+    }
+}
+
+// `kind` is one of "scan", "upload", "upload-failed", "installed", "uninstalled",
+// "updated" or "error"
+pub fn record(kind: &str, message: &str, app_name: Option<&str>) {
+    let Ok(mut timeline) = TIMELINE.lock() else {
+        return;
+    };
+
+    let event = ActivityEvent {
+        id: timeline.next_id,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        kind: kind.to_string(),
+        message: message.to_string(),
+        app_name: app_name.map(str::to_string),
+    };
+    timeline.next_id += 1;
+    append_to_file(&event);
+    timeline.events.push(event);
+
+    if timeline.events.len() > MAX_EVENTS * 2 {
+        let excess = timeline.events.len() - MAX_EVENTS;
+        timeline.events.drain(..excess);
+        rewrite_file(&timeline.events);
+    }
+}
+
+// Oldest first; `since` is an RFC 3339 timestamp
+pub fn events_since(since: &str) -> Vec<ActivityEvent> {
+    let Ok(timeline) = TIMELINE.lock() else {
+        return Vec::new();
+    };
+    timeline
+        .events
+        .iter()
+        .filter(|e| e.timestamp.as_str() >= since)
+        .cloned()
+        .collect()
+}
+
+// Newest first; `before` is an RFC 3339 timestamp to page backwards from
+pub fn get_timeline(limit: usize, before: Option<&str>) -> Vec<ActivityEvent> {
+    let Ok(timeline) = TIMELINE.lock() else {
+        return Vec::new();
+    };
+
+    timeline
+        .events
+        .iter()
+        .rev()
+        .filter(|e| match before {
+            Some(before) => e.timestamp.as_str() < before,
+            None => true,
+        })
+        .take(limit)
+        .cloned()
+        .collect()
+}
@@ -0,0 +1,561 @@
+use super::chunks::ChunkAvailability;
+use super::host::HostDetails;
+use super::http_cache;
+use super::models::{
+    GameMetadata, Settings, UploadApiResponse, UploadErrorCode, UploadMetrics, UploadStatus,
+};
+use super::signing;
+use super::utils::{describe_request_error, upload_client};
+use crate::{BUILDS_API_URL, HTTP_CLIENT};
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use std::collections::HashMap;
+#[cfg(test)]
+use std::collections::HashSet;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// Everything needed to submit one manifest to the builds API
+#[derive(Debug, Clone)]
+pub struct ManifestUpload {
+    pub item_json: String,
+    pub manifest_hash: String,
+    // Build label; selects the endpoint via `environment_endpoints`
+    pub environment: String,
+    pub manifest_filename: String,
+    // "manifest" for the main build manifest, otherwise a companion kind
+    pub kind: String,
+    pub manifest_bytes: Vec<u8>,
+    pub os: String,
+    pub runtime: String,
+    // Empty when the architecture couldn't be detected
+    pub arch: String,
+    pub launcher_version: Option<String>,
+    // Only present when the user opted in via `share_host_details`
+    pub host: Option<HostDetails>,
+    // When this build was first observed locally (RFC 3339)
+    pub first_seen: Option<String>,
+    pub chunks: ChunkAvailability,
+}
+
+// All egdata HTTP interactions. Scan and upload logic takes an implementation
+// so it can run against `MockEgdataApi` or an alternative backend.
+pub trait EgdataApi: Send + Sync {
+    // `revalidate` asks the server even when the cached copy is still fresh
+    fn fetch_item(
+        &self,
+        catalog_item_id: &str,
+        timeout: Duration,
+        revalidate: bool,
+    ) -> impl Future<Output = Result<GameMetadata, String>> + Send;
+
+    // Asks the endpoint uploads for `environment` are routed to. None when the
+    // server's answer is inconclusive or it can't be asked.
+    fn manifest_exists(
+        &self,
+        manifest_hash: &str,
+        environment: &str,
+        settings: &Settings,
+    ) -> impl Future<Output = Option<bool>> + Send;
+
+    // Same question, put to one specific endpoint (e.g. where a build was
+    // uploaded) rather than the one uploads are routed to now
+    fn manifest_exists_on(
+        &self,
+        manifest_hash: &str,
+        endpoint: &str,
+        settings: &Settings,
+    ) -> impl Future<Output = Option<bool>> + Send;
+
+    fn upload_manifest(
+        &self,
+        upload: ManifestUpload,
+        settings: &Settings,
+    ) -> impl Future<Output = Result<UploadStatus, String>> + Send;
+
+    // Whether the endpoint uploads for `environment` are routed to takes
+    // companion files; false when it doesn't say so
+    fn accepts_companions(
+        &self,
+        environment: &str,
+        settings: &Settings,
+    ) -> impl Future<Output = bool> + Send;
+}
+
+// The real egdata API over reqwest
+pub struct HttpEgdataApi;
+
+// Consecutive rejections/timeouts before an endpoint is skipped in favour of the next
+const FAILOVER_THRESHOLD: u32 = 3;
+// A skipped endpoint is tried again once this has passed
+const FAILOVER_COOLDOWN: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Default)]
+struct EndpointHealth {
+    consecutive_failures: u32,
+    tripped_at: Option<Instant>,
+}
+
+static ENDPOINT_HEALTH: Lazy<Mutex<HashMap<String, EndpointHealth>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn configured_endpoints(settings: &Settings, environment: &str) -> Vec<String> {
+    // A routed environment only ever goes to its own endpoint, never the defaults
+    if let Some((_, endpoint)) = settings
+        .environment_endpoints
+        .iter()
+        .find(|(env, _)| env.eq_ignore_ascii_case(environment))
+    {
+        return vec![endpoint.trim().trim_end_matches('/').to_string()];
+    }
+
+    let endpoints = settings
+        .upload_endpoints
+        .iter()
+        .map(|e| e.trim().trim_end_matches('/').to_string())
+        .filter(|e| !e.is_empty())
+        .collect::<Vec<_>>();
+    if endpoints.is_empty() {
+        vec![BUILDS_API_URL.to_string()]
+    } else {
+        endpoints
+    }
+}
+
+// Where uploads for unrouted builds go first
+pub fn primary_endpoint(settings: &Settings) -> String {
+    configured_endpoints(settings, "")
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| BUILDS_API_URL.to_string())
+}
+
+// Healthy endpoints in configured order, then tripped ones as a last resort
+fn endpoints_in_failover_order(settings: &Settings, environment: &str) -> Vec<String> {
+    let endpoints = configured_endpoints(settings, environment);
+    let Ok(health) = ENDPOINT_HEALTH.lock() else {
+        return endpoints;
+    };
+
+    let (healthy, tripped): (Vec<_>, Vec<_>) = endpoints.into_iter().partition(|endpoint| {
+        match health.get(endpoint).and_then(|h| h.tripped_at) {
+            Some(tripped_at) => tripped_at.elapsed() >= FAILOVER_COOLDOWN,
+            None => true,
+        }
+    });
+    healthy.into_iter().chain(tripped).collect()
+}
+
+fn record_endpoint_result(endpoint: &str, succeeded: bool) {
+    let Ok(mut health) = ENDPOINT_HEALTH.lock() else {
+        return;
+    };
+    let entry = health.entry(endpoint.to_string()).or_default();
+    if succeeded {
+        *entry = EndpointHealth::default();
+        return;
+    }
+
+    entry.consecutive_failures += 1;
+    if entry.consecutive_failures >= FAILOVER_THRESHOLD {
+        if entry.tripped_at.is_none() {
+            eprintln!(
+                "Upload endpoint {} failed {} times in a row; failing over",
+                endpoint, entry.consecutive_failures
+            );
+        }
+        entry.tripped_at = Some(Instant::now());
+    }
+}
+
+// Optional server features, published at `GET {endpoint}/capabilities`.
+// Endpoints without the document get the original behaviour: duplicates are
+// matched on the response text and nothing is checked before uploading.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct EndpointCapabilities {
+    // Upload responses carry a typed `code`
+    error_codes: bool,
+    // `HEAD /manifests/{hash}` answers 200 or 404
+    manifest_exists: bool,
+    // Uploads with a `kind` other than "manifest" (`.mancpn`, DLC manifests,
+    // sidecar bundles)
+    companions: bool,
+}
+
+static ENDPOINT_CAPABILITIES: Lazy<Mutex<HashMap<String, EndpointCapabilities>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+async fn endpoint_capabilities(
+    client: &reqwest::Client,
+    endpoint: &str,
+    timeout: Duration,
+) -> EndpointCapabilities {
+    if let Some(known) = ENDPOINT_CAPABILITIES
+        .lock()
+        .ok()
+        .and_then(|c| c.get(endpoint).copied())
+    {
+        return known;
+    }
+
+    let resp = match client
+        .get(format!("{}/capabilities", endpoint))
+        .timeout(timeout)
+        .send()
+        .await
+    {
+        Ok(resp) => resp,
+        // Unreachable: assume nothing, and ask again next time
+        Err(_) => return EndpointCapabilities::default(),
+    };
+    // Server trouble says nothing about its features; ask again next time
+    if resp.status().is_server_error() {
+        return EndpointCapabilities::default();
+    }
+    // Anything else but the document (typically a 404) means no extras
+    let capabilities = if resp.status().is_success() {
+        resp.json::<EndpointCapabilities>()
+            .await
+            .unwrap_or_default()
+    } else {
+        EndpointCapabilities::default()
+    };
+
+    if let Ok(mut cache) = ENDPOINT_CAPABILITIES.lock() {
+        cache.insert(endpoint.to_string(), capabilities);
+    }
+    capabilities
+}
+
+// Server-side trouble worth trying another endpoint for; anything else is a
+// verdict on the manifest itself
+fn is_endpoint_failure(status: reqwest::StatusCode) -> bool {
+    status.is_server_error()
+        || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        || status == reqwest::StatusCode::REQUEST_TIMEOUT
+}
+
+fn build_upload_form(upload: &ManifestUpload) -> reqwest::multipart::Form {
+    let mut form = reqwest::multipart::Form::new()
+        .text("item", upload.item_json.clone())
+        .text("os", upload.os.clone())
+        .text("runtime", upload.runtime.clone())
+        .text("kind", upload.kind.clone())
+        .part(
+            "manifest",
+            reqwest::multipart::Part::bytes(upload.manifest_bytes.clone())
+                .file_name(upload.manifest_filename.clone()),
+        );
+    if !upload.arch.is_empty() {
+        form = form.text("arch", upload.arch.clone());
+    }
+    if let Some(launcher_version) = &upload.launcher_version {
+        form = form.text("launcherVersion", launcher_version.clone());
+    }
+    if let Some(first_seen) = &upload.first_seen {
+        form = form.text("firstSeen", first_seen.clone());
+    }
+    if let Some(host) = &upload.host {
+        form = form
+            .text("hostOs", host.os.clone())
+            .text("hostOsVersion", host.os_version.clone())
+            .text("hostArch", host.arch.clone());
+    }
+    form = form
+        .text("chunkFiles", upload.chunks.chunk_files.to_string())
+        .text("chunkBytes", upload.chunks.chunk_bytes.to_string());
+    if let Some(signature) = signing::sign_upload(&upload.manifest_hash) {
+        form = form
+            .text("publicKey", signature.public_key)
+            .text("signedAt", signature.signed_at)
+            .text("signature", signature.signature);
+    }
+    form
+}
+
+impl EgdataApi for HttpEgdataApi {
+    async fn fetch_item(
+        &self,
+        catalog_item_id: &str,
+        timeout: Duration,
+        revalidate: bool,
+    ) -> Result<GameMetadata, String> {
+        let url = format!("https://api.egdata.app/items/{}", catalog_item_id);
+        let body = http_cache::get(&HTTP_CLIENT, &url, None, timeout, revalidate).await?;
+        serde_json::from_str::<GameMetadata>(&body)
+            .map_err(|e| format!("Failed to parse metadata: {}", e))
+    }
+
+    async fn manifest_exists(
+        &self,
+        manifest_hash: &str,
+        environment: &str,
+        settings: &Settings,
+    ) -> Option<bool> {
+        let endpoint = endpoints_in_failover_order(settings, environment)
+            .into_iter()
+            .next()?;
+        self.manifest_exists_on(manifest_hash, &endpoint, settings)
+            .await
+    }
+
+    async fn manifest_exists_on(
+        &self,
+        manifest_hash: &str,
+        endpoint: &str,
+        settings: &Settings,
+    ) -> Option<bool> {
+        let endpoint = endpoint.trim_end_matches('/');
+        let client = upload_client(settings).ok()?;
+        if !endpoint_capabilities(&client, endpoint, settings.metadata_timeout())
+            .await
+            .manifest_exists
+        {
+            return None;
+        }
+        let resp = client
+            .head(format!("{}/manifests/{}", endpoint, manifest_hash))
+            .timeout(settings.metadata_timeout())
+            .send()
+            .await
+            .ok()?;
+
+        match resp.status() {
+            status if status.is_success() => Some(true),
+            reqwest::StatusCode::NOT_FOUND => Some(false),
+            _ => None,
+        }
+    }
+
+    async fn upload_manifest(
+        &self,
+        upload: ManifestUpload,
+        settings: &Settings,
+    ) -> Result<UploadStatus, String> {
+        let client = upload_client(settings)?;
+        let mut last_error = String::new();
+
+        let payload_bytes = (upload.item_json.len() + upload.manifest_bytes.len()) as u64;
+        for endpoint in endpoints_in_failover_order(settings, &upload.environment) {
+            let started = Instant::now();
+            let resp = match client
+                .post(format!("{}/upload-manifest", endpoint))
+                .timeout(settings.upload_timeout())
+                .multipart(build_upload_form(&upload))
+                .send()
+                .await
+            {
+                Ok(resp) => resp,
+                Err(e) => {
+                    record_endpoint_result(&endpoint, false);
+                    last_error = format!(
+                        "Failed to send upload request to {}: {}",
+                        endpoint,
+                        describe_request_error(&e)
+                    );
+                    continue;
+                }
+            };
+
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            if is_endpoint_failure(status) {
+                record_endpoint_result(&endpoint, false);
+                last_error = format!(
+                    "Upload endpoint {} rejected the request: {}",
+                    endpoint, status
+                );
+                continue;
+            }
+
+            record_endpoint_result(&endpoint, true);
+            let capabilities =
+                endpoint_capabilities(&client, &endpoint, settings.metadata_timeout()).await;
+            let mut result = interpret_upload_response(
+                status,
+                &text,
+                &upload.manifest_hash,
+                capabilities.error_codes,
+            );
+            result.endpoint = Some(endpoint);
+            result.metrics = Some(UploadMetrics::new(started.elapsed(), payload_bytes));
+            return Ok(result);
+        }
+
+        Err(last_error)
+    }
+
+    async fn accepts_companions(&self, environment: &str, settings: &Settings) -> bool {
+        let Some(endpoint) = endpoints_in_failover_order(settings, environment)
+            .into_iter()
+            .next()
+        else {
+            return false;
+        };
+        let Ok(client) = upload_client(settings) else {
+            return false;
+        };
+        endpoint_capabilities(&client, &endpoint, settings.metadata_timeout())
+            .await
+            .companions
+    }
+}
+
+fn interpret_upload_response(
+    status: reqwest::StatusCode,
+    text: &str,
+    manifest_hash: &str,
+    error_codes: bool,
+) -> UploadStatus {
+    let mut parsed = serde_json::from_str::<UploadApiResponse>(text).ok();
+    // A `code` from a server that doesn't advertise typed codes isn't trusted
+    if !error_codes {
+        if let Some(response) = parsed.as_mut() {
+            response.code = None;
+        }
+    }
+    let message = parsed
+        .as_ref()
+        .and_then(|r| r.description())
+        .unwrap_or_else(|| text.to_string());
+
+    // Prefer the typed error code; servers that don't send one are matched on
+    // the message text instead
+    let is_duplicate = match parsed.as_ref().filter(|r| r.code.is_some()) {
+        Some(response) => response.is_duplicate(),
+        None => text.contains("A manifest file with identical content already exists"),
+    };
+
+    let succeeded = status.is_success() && parsed.as_ref().and_then(|r| r.success).unwrap_or(true);
+
+    if is_duplicate {
+        UploadStatus {
+            status: "already_uploaded".to_string(),
+            message: Some("Manifest with identical content already exists".to_string()),
+            manifest_hash: Some(manifest_hash.to_string()),
+            endpoint: None,
+            error_code: None,
+            metrics: None,
+        }
+    } else if succeeded {
+        UploadStatus {
+            status: "uploaded".to_string(),
+            message: Some(message),
+            manifest_hash: Some(manifest_hash.to_string()),
+            endpoint: None,
+            error_code: None,
+            metrics: None,
+        }
+    } else {
+        let error_code = UploadErrorCode::from_response(
+            status.as_u16(),
+            parsed.as_ref().and_then(|r| r.code.as_deref()),
+        );
+        UploadStatus {
+            status: "failed".to_string(),
+            message: Some(message),
+            manifest_hash: Some(manifest_hash.to_string()),
+            endpoint: None,
+            error_code: Some(error_code),
+            metrics: None,
+        }
+    }
+}
+
+// In-memory stand-in for the egdata API: serves `items`, treats hashes in
+// `existing_manifests` as already uploaded and records every upload it receives.
+// With `upload_error` set, every upload is answered as failed with that message.
+// Companion files are only accepted with `companions` set.
+#[cfg(test)]
+#[derive(Default)]
+pub struct MockEgdataApi {
+    pub items: HashMap<String, GameMetadata>,
+    pub existing_manifests: Mutex<HashSet<String>>,
+    pub uploads: Mutex<Vec<ManifestUpload>>,
+    pub upload_error: Option<String>,
+    pub companions: bool,
+}
+
+#[cfg(test)]
+impl EgdataApi for MockEgdataApi {
+    async fn fetch_item(
+        &self,
+        catalog_item_id: &str,
+        _timeout: Duration,
+        _revalidate: bool,
+    ) -> Result<GameMetadata, String> {
+        self.items
+            .get(catalog_item_id)
+            .cloned()
+            .ok_or_else(|| "HTTP 404 Not Found".to_string())
+    }
+
+    async fn manifest_exists(
+        &self,
+        manifest_hash: &str,
+        _environment: &str,
+        _settings: &Settings,
+    ) -> Option<bool> {
+        self.existing_manifests
+            .lock()
+            .ok()
+            .map(|existing| existing.contains(manifest_hash))
+    }
+
+    async fn manifest_exists_on(
+        &self,
+        manifest_hash: &str,
+        _endpoint: &str,
+        _settings: &Settings,
+    ) -> Option<bool> {
+        self.existing_manifests
+            .lock()
+            .ok()
+            .map(|existing| existing.contains(manifest_hash))
+    }
+
+    async fn upload_manifest(
+        &self,
+        upload: ManifestUpload,
+        _settings: &Settings,
+    ) -> Result<UploadStatus, String> {
+        let manifest_hash = upload.manifest_hash.clone();
+        if let Some(error) = &self.upload_error {
+            self.uploads.lock().map_err(|e| e.to_string())?.push(upload);
+            return Ok(UploadStatus {
+                status: "failed".to_string(),
+                message: Some(error.clone()),
+                manifest_hash: Some(manifest_hash),
+                endpoint: None,
+                error_code: None,
+                metrics: None,
+            });
+        }
+        let is_new = self
+            .existing_manifests
+            .lock()
+            .map_err(|e| e.to_string())?
+            .insert(manifest_hash.clone());
+        self.uploads.lock().map_err(|e| e.to_string())?.push(upload);
+
+        Ok(UploadStatus {
+            status: if is_new {
+                "uploaded"
+            } else {
+                "already_uploaded"
+            }
+            .to_string(),
+            message: None,
+            manifest_hash: Some(manifest_hash),
+            endpoint: None,
+            error_code: None,
+            metrics: None,
+        })
+    }
+
+    async fn accepts_companions(&self, _environment: &str, _settings: &Settings) -> bool {
+        self.companions
+    }
+}
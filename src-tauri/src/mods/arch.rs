@@ -0,0 +1,95 @@
+use super::models::GameInfo;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+// Enough for the PE header offset, ELF header and Mach-O fat table
+const HEADER_BYTES: usize = 4096;
+
+// Reads the header of the game's launch executable and names the CPU
+// architecture it was built for: "x64", "x86", "arm64", "universal" for a
+// macOS fat binary, or an empty string when it can't be told
+pub fn detect_architecture(game: &GameInfo) -> String {
+    let Some(executable) = executable_path(game) else {
+        return String::new();
+    };
+    let mut header = Vec::with_capacity(HEADER_BYTES);
+    let read = File::open(&executable)
+        .and_then(|file| file.take(HEADER_BYTES as u64).read_to_end(&mut header));
+    if read.is_err() {
+        return String::new();
+    }
+    architecture_from_header(&header)
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn executable_path(game: &GameInfo) -> Option<PathBuf> {
+    if game.launch_executable.is_empty() {
+        return None;
+    }
+    let path = Path::new(&game.install_location).join(&game.launch_executable);
+    // macOS manifests may point at the bundle rather than the binary inside it
+    if path.extension().is_some_and(|ext| ext == "app") {
+        let stem = path.file_stem()?.to_owned();
+        return Some(path.join("Contents").join("MacOS").join(stem));
+    }
+    Some(path)
+}
+
+fn architecture_from_header(header: &[u8]) -> Option<&'static str> {
+    let u16_le = |at: usize| Some(u16::from_le_bytes(header.get(at..at + 2)?.try_into().ok()?));
+    let u32_le = |at: usize| Some(u32::from_le_bytes(header.get(at..at + 4)?.try_into().ok()?));
+    let u32_be = |at: usize| Some(u32::from_be_bytes(header.get(at..at + 4)?.try_into().ok()?));
+
+    // PE: "MZ", then the COFF machine field right after the "PE\0\0" signature
+    if header.starts_with(b"MZ") {
+        let pe_offset = u32_le(0x3C)? as usize;
+        if header.get(pe_offset..pe_offset + 4)? != b"PE\0\0" {
+            return None;
+        }
+        return match u16_le(pe_offset + 4)? {
+            0x8664 => Some("x64"),
+            0x014C => Some("x86"),
+            0xAA64 => Some("arm64"),
+            _ => None,
+        };
+    }
+
+    // ELF: e_machine at offset 18, little-endian on every platform we see
+    if header.starts_with(b"\x7fELF") {
+        return match u16_le(18)? {
+            0x3E => Some("x64"),
+            0x03 => Some("x86"),
+            0xB7 => Some("arm64"),
+            _ => None,
+        };
+    }
+
+    // Mach-O fat binary: big-endian header listing one slice per architecture
+    if u32_be(0)? == 0xCAFE_BABE {
+        let slices = u32_be(4)?;
+        // Java class files share the magic; their "count" is a version >= 45
+        if slices == 0 || slices > 20 {
+            return None;
+        }
+        if slices > 1 {
+            return Some("universal");
+        }
+        return mach_cpu_type(u32_be(8)?);
+    }
+
+    // Thin 64-bit Mach-O, little-endian
+    if u32_le(0)? == 0xFEED_FACF {
+        return mach_cpu_type(u32_le(4)?);
+    }
+    None
+}
+
+fn mach_cpu_type(cpu_type: u32) -> Option<&'static str> {
+    match cpu_type {
+        0x0100_0007 => Some("x64"),
+        0x0100_000C => Some("arm64"),
+        _ => None,
+    }
+}
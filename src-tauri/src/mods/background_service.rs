@@ -0,0 +1,75 @@
+// Windows scheduled task that runs `--once` at login, for users who want
+// uploads without the tray app running
+#[cfg(target_os = "windows")]
+const TASK_NAME: &str = "EGDataClient Background Upload";
+
+#[cfg(target_os = "windows")]
+fn schtasks(args: &[&str]) -> Result<(), String> {
+    let output = super::utils::hidden_command("schtasks")
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run schtasks: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+// Highest available privileges let the run read ProgramData when the launcher
+// locked it down; creating such a task needs an elevated prompt
+pub fn install() -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        let exe_path =
+            std::env::current_exe().map_err(|e| format!("Failed to locate executable: {}", e))?;
+        let command = format!("\"{}\" --once", exe_path.display());
+        schtasks(&[
+            "/Create", "/TN", TASK_NAME, "/TR", &command, "/SC", "ONLOGON", "/RL", "HIGHEST", "/F",
+        ])
+        .map_err(|e| format!("Failed to create scheduled task: {}", e))
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        Err("Background service is only available on Windows; use cron with --once".to_string())
+    }
+}
+
+// `install`, from a copy of the client relaunched behind a UAC prompt when
+// this process isn't elevated (schtasks refuses /RL HIGHEST otherwise)
+pub fn install_elevated() -> Result<(), String> {
+    if !super::elevation::status().supported || super::elevation::is_elevated() {
+        return install();
+    }
+    match super::elevation::run_elevated(&["--install-background-service"])? {
+        0 => Ok(()),
+        code => Err(format!(
+            "Failed to create scheduled task: elevated install exited with code {}",
+            code
+        )),
+    }
+}
+
+pub fn uninstall() -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        schtasks(&["/Delete", "/TN", TASK_NAME, "/F"])
+            .map_err(|e| format!("Failed to delete scheduled task: {}", e))
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        Err("Background service is only available on Windows".to_string())
+    }
+}
+
+// None when not supported on this platform
+pub fn is_installed() -> Option<bool> {
+    #[cfg(target_os = "windows")]
+    {
+        Some(schtasks(&["/Query", "/TN", TASK_NAME]).is_ok())
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        None
+    }
+}
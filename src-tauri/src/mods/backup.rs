@@ -0,0 +1,141 @@
+use super::models::Settings;
+use super::utils::get_app_data_path;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+
+// Bumped when the archive layout changes; newer backups are refused
+const BACKUP_FORMAT_VERSION: u32 = 1;
+const BACKUP_MANIFEST: &str = "backup.json";
+
+// App data files carried over to a new PC. The client id, signing key and
+// local API token are left out: they identify this install, not the user.
+const BACKUP_FILES: [&str; 7] = [
+    "settings.json",
+    "upload_ledger.json",
+    "metadata_cache.json",
+    "library_cache.json",
+    "library_history.json",
+    "uninstalled_games.json",
+    "activity.jsonl",
+];
+
+// Zips the app data files that exist into `destination`, returning their names
+pub fn backup_app_data(destination: &Path) -> Result<Vec<String>, String> {
+    let data_dir = get_app_data_path();
+    let files = BACKUP_FILES
+        .iter()
+        .filter_map(|name| {
+            fs::read(data_dir.join(name))
+                .ok()
+                .map(|bytes| (name.to_string(), bytes))
+        })
+        .collect::<Vec<_>>();
+
+    let manifest = serde_json::json!({
+        "format_version": BACKUP_FORMAT_VERSION,
+        "created_at": chrono::Utc::now().to_rfc3339(),
+        "client_version": env!("CARGO_PKG_VERSION"),
+        "files": files.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+    });
+    let manifest = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize {}: {}", BACKUP_MANIFEST, e))?;
+
+    let file = fs::File::create(destination)
+        .map_err(|e| format!("Failed to create {}: {}", destination.display(), e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, bytes) in files
+        .iter()
+        .map(|(name, bytes)| (name.as_str(), bytes))
+        .chain(std::iter::once((BACKUP_MANIFEST, &manifest)))
+    {
+        zip.start_file(name, options)
+            .map_err(|e| format!("Failed to add {} to backup: {}", name, e))?;
+        zip.write_all(bytes)
+            .map_err(|e| format!("Failed to write {} to backup: {}", name, e))?;
+    }
+    zip.finish()
+        .map_err(|e| format!("Failed to finish backup: {}", e))?;
+
+    Ok(files.into_iter().map(|(name, _)| name).collect())
+}
+
+// Replaces the app data files with the ones in the backup at `source`. Every
+// file is read and checked before anything is written, so a bad archive
+// leaves the current data alone. The app must restart to pick them up.
+pub fn restore_app_data(source: &Path) -> Result<Vec<String>, String> {
+    let file = fs::File::open(source)
+        .map_err(|e| format!("Failed to open {}: {}", source.display(), e))?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|e| format!("Not a backup archive: {}", e))?;
+
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&read_entry(&mut archive, BACKUP_MANIFEST)?)
+            .map_err(|e| format!("Invalid {}: {}", BACKUP_MANIFEST, e))?;
+    let format_version = manifest["format_version"].as_u64().unwrap_or(0);
+    if format_version == 0 || format_version > BACKUP_FORMAT_VERSION as u64 {
+        return Err(format!(
+            "Unsupported backup format version {}; update egdata-client first",
+            format_version
+        ));
+    }
+
+    // Only known file names are restored, whatever else the archive holds
+    let mut files = Vec::new();
+    for name in BACKUP_FILES {
+        if archive.by_name(name).is_err() {
+            continue;
+        }
+        let bytes = read_entry(&mut archive, name)?;
+        validate_entry(name, &bytes)?;
+        files.push((name, bytes));
+    }
+
+    let data_dir = get_app_data_path();
+    fs::create_dir_all(&data_dir)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    for (name, bytes) in &files {
+        // Write next to the target and rename so a crash never leaves half a file
+        let target = data_dir.join(name);
+        let staging = data_dir.join(format!("{}.restore", name));
+        fs::write(&staging, bytes)
+            .and_then(|_| fs::rename(&staging, &target))
+            .map_err(|e| format!("Failed to restore {}: {}", name, e))?;
+    }
+
+    Ok(files
+        .into_iter()
+        .map(|(name, _)| name.to_string())
+        .collect())
+}
+
+fn read_entry(archive: &mut zip::ZipArchive<fs::File>, name: &str) -> Result<Vec<u8>, String> {
+    let mut entry = archive
+        .by_name(name)
+        .map_err(|e| format!("Backup is missing {}: {}", name, e))?;
+    let mut bytes = Vec::new();
+    entry
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("Failed to read {} from backup: {}", name, e))?;
+    Ok(bytes)
+}
+
+fn validate_entry(name: &str, bytes: &[u8]) -> Result<(), String> {
+    if name.ends_with(".jsonl") {
+        return std::str::from_utf8(bytes)
+            .map(|_| ())
+            .map_err(|e| format!("Invalid {} in backup: {}", name, e));
+    }
+    // Settings must load and pass the same checks as ones saved from the UI
+    if name == "settings.json" {
+        return serde_json::from_slice::<Settings>(bytes)
+            .map_err(|e| format!("Invalid {} in backup: {}", name, e))?
+            .validate()
+            .map_err(|e| format!("Invalid {} in backup: {}", name, e));
+    }
+    serde_json::from_slice::<serde_json::Value>(bytes)
+        .map(|_| ())
+        .map_err(|e| format!("Invalid {} in backup: {}", name, e))
+}
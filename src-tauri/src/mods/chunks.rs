@@ -0,0 +1,197 @@
+use super::models::GameInfo;
+use super::utils::get_egstore_manifest_path;
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+// Chunk data the launcher left behind for an installed build. Sent with the
+// upload so the backend knows which builds could still be preserved whole.
+#[derive(Debug, Clone, Default)]
+pub struct ChunkAvailability {
+    pub chunk_files: u64,
+    pub chunk_bytes: u64,
+}
+
+// Staging folders are nested a few levels deep; anything further is not ours
+const MAX_DEPTH: usize = 4;
+
+// Chunk sets by manifest hash; a hash always names the same manifest content
+static BUILD_CHUNKS: Lazy<Mutex<HashMap<String, Arc<HashSet<String>>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Counts the `*.chunk` files under the game's `.egstore` (staging and patch
+// caches from interrupted or in-progress installs) that belong to the
+// installed build. Builds whose manifest isn't the binary format report
+// nothing, since their chunks can't be told apart from another build's.
+// The work runs on the blocking pool at background priority.
+pub async fn chunk_availability(game: &GameInfo) -> ChunkAvailability {
+    let (install_location, installation_guid) = (
+        game.install_location.clone(),
+        game.installation_guid.clone(),
+    );
+    let manifest_hash = game.manifest_hash.to_uppercase();
+    super::priority::spawn_background(move || {
+        let manifest_path = get_egstore_manifest_path(&install_location, &installation_guid);
+        let Some(egstore) = manifest_path.parent() else {
+            return ChunkAvailability::default();
+        };
+        let Some(build_chunks) = cached_build_chunks(&manifest_hash, &manifest_path) else {
+            return ChunkAvailability::default();
+        };
+        let mut availability = ChunkAvailability::default();
+        count_chunks(egstore, 0, &build_chunks, &mut availability);
+        availability
+    })
+    .await
+    .unwrap_or_default()
+}
+
+fn cached_build_chunks(manifest_hash: &str, manifest_path: &Path) -> Option<Arc<HashSet<String>>> {
+    if let Some(known) = BUILD_CHUNKS
+        .lock()
+        .ok()
+        .and_then(|c| c.get(manifest_hash).cloned())
+    {
+        return Some(known);
+    }
+    if fs::metadata(manifest_path).ok()?.len() > crate::MAX_MANIFEST_BYTES {
+        return None;
+    }
+    let chunks = Arc::new(build_chunk_set(&fs::read(manifest_path).ok()?)?);
+    if let Ok(mut cache) = BUILD_CHUNKS.lock() {
+        cache.insert(manifest_hash.to_string(), chunks.clone());
+    }
+    Some(chunks)
+}
+
+fn count_chunks(
+    dir: &Path,
+    depth: usize,
+    build_chunks: &HashSet<String>,
+    availability: &mut ChunkAvailability,
+) {
+    if depth > MAX_DEPTH {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let path = entry.path();
+        if file_type.is_dir() {
+            count_chunks(&path, depth + 1, build_chunks, availability);
+        } else if file_type.is_file()
+            && path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("chunk"))
+            && path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .is_some_and(|stem| build_chunks.contains(&stem.to_uppercase()))
+        {
+            availability.chunk_files += 1;
+            availability.chunk_bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+        }
+    }
+}
+
+// Little-endian reader over a manifest body; None once it runs out
+struct ManifestReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ManifestReader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let slice = self.bytes.get(self.pos..self.pos.checked_add(len)?)?;
+        self.pos += len;
+        Some(slice)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_le_bytes(self.take(4)?.try_into().ok()?))
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        Some(u64::from_le_bytes(self.take(8)?.try_into().ok()?))
+    }
+}
+
+// Chunk file stems (`{hash:016X}_{guid}`, upper-case hex) of a binary build
+// manifest, read from its chunk data list. None for JSON or malformed manifests.
+pub fn build_chunk_set(manifest: &[u8]) -> Option<HashSet<String>> {
+    // Header: magic, header size, body size uncompressed/compressed, SHA-1, storage flags
+    let mut header = ManifestReader {
+        bytes: manifest,
+        pos: 0,
+    };
+    if header.u32()? != 0x44BE_C00C {
+        return None;
+    }
+    let header_size = header.u32()? as usize;
+    let uncompressed_size = header.u32()? as usize;
+    let compressed_size = header.u32()? as usize;
+    header.take(20)?;
+    let stored_as = header.u8()?;
+
+    let body = manifest.get(header_size..header_size.checked_add(compressed_size)?)?;
+    // The sizes come from the file; don't trust them for allocations
+    if uncompressed_size as u64 > crate::MAX_MANIFEST_BYTES {
+        return None;
+    }
+    let body = if stored_as & 0x01 != 0 {
+        let mut inflated = Vec::new();
+        flate2::read::ZlibDecoder::new(body)
+            .take(uncompressed_size as u64)
+            .read_to_end(&mut inflated)
+            .ok()?;
+        inflated
+    } else {
+        body.to_vec()
+    };
+
+    // The meta section comes first and declares its own size; skip it
+    let mut reader = ManifestReader {
+        bytes: &body,
+        pos: 0,
+    };
+    let meta_size = reader.u32()? as usize;
+    reader.pos = meta_size;
+
+    // Chunk data list: size, version, count, then each field as its own array
+    reader.u32()?;
+    reader.u8()?;
+    let count = reader.u32()? as usize;
+    let guids = (0..count)
+        .map(|_| {
+            Some(format!(
+                "{:08X}{:08X}{:08X}{:08X}",
+                reader.u32()?,
+                reader.u32()?,
+                reader.u32()?,
+                reader.u32()?
+            ))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let hashes = (0..count)
+        .map(|_| reader.u64())
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(
+        hashes
+            .iter()
+            .zip(guids)
+            .map(|(hash, guid)| format!("{:016X}_{}", hash, guid))
+            .collect(),
+    )
+}
@@ -0,0 +1,177 @@
+use super::models::GameInfo;
+use super::state::{GameStore, MetadataCache, MetadataCacheData};
+use super::utils::{
+    load_metadata_cache_from_file, load_settings_from_file, save_library_cache_to_file,
+};
+use crate::{load_manifest_sources, scan_epic_games_with_progress, upload_all_manifests_internal};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+
+// One-shot operations that run without the tray or window and then exit
+pub enum CliCommand {
+    ScanOnce,
+    UploadAll,
+    // Scan and upload for external schedulers, reporting JSON on stdout
+    Once,
+    ExportLibrary(PathBuf),
+    // Run by an elevated copy of the client (see `elevation`)
+    InstallBackgroundService,
+}
+
+// Returns None when no one-shot flag was given, so the app starts normally
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Option<CliCommand>, String> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--scan-once" => return Ok(Some(CliCommand::ScanOnce)),
+            "--upload-all" => return Ok(Some(CliCommand::UploadAll)),
+            "--once" => return Ok(Some(CliCommand::Once)),
+            "--install-background-service" => {
+                return Ok(Some(CliCommand::InstallBackgroundService))
+            }
+            "--export-library" => {
+                let path = args
+                    .next()
+                    .ok_or("--export-library requires a destination path")?;
+                return Ok(Some(CliCommand::ExportLibrary(PathBuf::from(path))));
+            }
+            _ => {}
+        }
+    }
+    Ok(None)
+}
+
+// `--once` exit code when the run finished but some uploads failed; 1 means
+// the run itself failed
+const EXIT_UPLOAD_FAILURES: i32 = 2;
+
+// Release builds on Windows are GUI programs without a console of their own,
+// so output would vanish; borrow the console of the shell that started us
+#[cfg(target_os = "windows")]
+fn attach_parent_console() {
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+    // Fails harmlessly when there's no parent console or one is attached already
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+// Runs the command to completion and returns the process exit code
+pub fn run(command: CliCommand) -> i32 {
+    #[cfg(target_os = "windows")]
+    attach_parent_console();
+
+    tauri::async_runtime::block_on(async move {
+        match execute(command).await {
+            Ok(code) => code,
+            Err(e) => {
+                eprintln!("{}", e);
+                1
+            }
+        }
+    })
+}
+
+async fn scan() -> Result<Vec<GameInfo>, String> {
+    let settings = load_settings_from_file();
+    let games: GameStore = Arc::new(Mutex::new(HashMap::new()));
+    let metadata_cache: MetadataCache = Arc::new(Mutex::new(MetadataCacheData::new(
+        load_metadata_cache_from_file(),
+        settings.metadata_cache_max_entries,
+        settings.metadata_cache_max_bytes,
+    )));
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+
+    let sources = load_manifest_sources().await;
+    scan_epic_games_with_progress(&sources, &games, &metadata_cache, &cancel_flag, |_| {}).await
+}
+
+async fn execute(command: CliCommand) -> Result<i32, String> {
+    match command {
+        CliCommand::ScanOnce => {
+            let games = scan().await?;
+            super::history::record_snapshot(&games);
+            for game in &games {
+                println!(
+                    "{}\t{}\t{}",
+                    game.display_name, game.version, game.install_location
+                );
+            }
+            println!("Found {} games", games.len());
+            Ok(0)
+        }
+        CliCommand::UploadAll => {
+            let settings = load_settings_from_file();
+            let scanned = scan().await?;
+            let games: GameStore = Arc::new(Mutex::new(
+                scanned
+                    .into_iter()
+                    .map(|g| (g.app_name.clone(), g))
+                    .collect(),
+            ));
+
+            let results = upload_all_manifests_internal(&games, &settings).await?;
+            let count = |status: &str| results.iter().filter(|r| r.status == status).count();
+            println!(
+                "{} uploaded, {} already uploaded, {} duplicates skipped, {} failed",
+                count("uploaded"),
+                count("already_uploaded"),
+                count("skipped_duplicate"),
+                count("failed")
+            );
+            if count("failed") > 0 {
+                return Err("Some manifests failed to upload".to_string());
+            }
+            Ok(0)
+        }
+        CliCommand::Once => {
+            let settings = load_settings_from_file();
+            let scanned = scan().await?;
+            super::history::record_snapshot(&scanned);
+            save_library_cache_to_file(&scanned);
+            let games: GameStore = Arc::new(Mutex::new(
+                scanned
+                    .iter()
+                    .map(|g| (g.app_name.clone(), g.clone()))
+                    .collect(),
+            ));
+
+            let results = upload_all_manifests_internal(&games, &settings).await?;
+            let count = |status: &str| results.iter().filter(|r| r.status == status).count();
+            let report = serde_json::json!({
+                "games": scanned.len(),
+                "uploaded": count("uploaded"),
+                "already_uploaded": count("already_uploaded"),
+                "skipped_duplicate": count("skipped_duplicate"),
+                "failed": count("failed"),
+                "results": results,
+            });
+            println!("{}", report);
+            Ok(if count("failed") > 0 {
+                EXIT_UPLOAD_FAILURES
+            } else {
+                0
+            })
+        }
+        CliCommand::ExportLibrary(path) => {
+            let games = scan().await?;
+            let json = serde_json::to_string_pretty(&games)
+                .map_err(|e| format!("Failed to serialize library: {}", e))?;
+            std::fs::write(&path, json)
+                .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            println!("Exported {} games to {}", games.len(), path.display());
+            Ok(0)
+        }
+        CliCommand::InstallBackgroundService => {
+            super::background_service::install()?;
+            println!("Background upload task installed");
+            Ok(0)
+        }
+    }
+}
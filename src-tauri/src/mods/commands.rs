@@ -1,10 +1,51 @@
-use super::models::{GameInfo, Settings, UploadStatus};
-use super::state::{GameStore, MetadataCache, SettingsState};
-use super::utils::save_settings_to_file;
+use super::activity;
+use super::api::HttpEgdataApi;
+use super::background_service;
+use super::backup;
+use super::crash;
+use super::diagnostics;
+use super::dlc;
+use super::elevation;
+use super::events::{self, AppEvent};
+use super::export;
+use super::history;
+use super::import;
+use super::launcher;
+use super::local_api;
+use super::models::{
+    ActivityEvent, BulkExportSummary, CacheStats, ContributionOpportunity, CrashReport,
+    DiagnosticsReport, ElevationStatus, FailedUpload, GameInfo, GameMetadata, ImportSummary,
+    LauncherConsistencyReport, LauncherState, LibraryHistory, OpenLocationError,
+    OpenLocationErrorCode, PathProbe, ReleaseNote, ServerReconciliation, Settings, SidecarReport,
+    TelemetryReport, UploadStatus,
+};
+use super::opportunities;
+use super::profiles;
+use super::reconcile;
+use super::release_notes;
+use super::retry_queue;
+use super::settings_watch;
+use super::sidecars;
+use super::state::{GameStore, MetadataCache, ScanCancelFlag, SettingsState, UninstalledGames};
+use super::telemetry;
+use super::utils::{
+    egstore_manifest_path, get_metadata_cache_path, probe_path, save_library_cache_to_file,
+    save_metadata_cache_to_file, save_settings_to_file, save_uninstalled_games_to_file,
+    PATH_PROBE_TIMEOUT,
+};
+use super::window;
 use crate::mods::utils::emit_log;
-use crate::scan_epic_games_with_metadata; // This needs to be public in lib.rs
+use crate::scan_epic_games; // This needs to be public in lib.rs
+use crate::spawn_metadata_enrichment;
+use crate::upload_all_manifests_internal;
 use crate::upload_manifest_internal; // This needs to be public in lib.rs
+use crate::{
+    announce_uninstalled, find_uninstalled, library_delta, load_manifest_sources, publish_metadata,
+    record_library_changes, refresh_game_metadata, spawn_upload_on_detect, upload_games_with,
+};
+use std::sync::atomic::Ordering;
 use tauri::{AppHandle, Manager, State};
+use tauri_plugin_opener::OpenerExt;
 
 #[tauri::command]
 pub fn greet(name: &str) -> String {
@@ -13,17 +54,12 @@ pub fn greet(name: &str) -> String {
 
 #[tauri::command]
 pub fn show_window(app_handle: AppHandle) {
-    if let Some(window) = app_handle.get_webview_window("main") {
-        let _ = window.show();
-        let _ = window.set_focus();
-    }
+    window::show_main_window(&app_handle);
 }
 
 #[tauri::command]
 pub fn hide_window(app_handle: AppHandle) {
-    if let Some(window) = app_handle.get_webview_window("main") {
-        let _ = window.hide();
-    }
+    window::hide_main_window(&app_handle);
 }
 
 #[tauri::command]
@@ -38,7 +74,56 @@ pub fn get_installed_games(games: State<GameStore>) -> Result<Vec<GameInfo>, Str
     let games_lock = games
         .lock()
         .map_err(|e| format!("Failed to lock games: {}", e))?;
-    Ok(games_lock.values().cloned().collect())
+    let games = games_lock.values().cloned().collect::<Vec<_>>();
+    Ok(dlc::group_by_parent(&games))
+}
+
+// Full details for the games named in a `games-updated` delta; unknown names
+// are left out
+#[tauri::command]
+pub fn get_games(games: State<GameStore>, app_names: Vec<String>) -> Result<Vec<GameInfo>, String> {
+    let games_lock = games
+        .lock()
+        .map_err(|e| format!("Failed to lock games: {}", e))?;
+    Ok(app_names
+        .iter()
+        .filter_map(|app_name| games_lock.get(app_name).cloned())
+        .collect())
+}
+
+// Installed games plus, optionally, tombstones of uninstalled ones, filtered by
+// a case-insensitive match on display name or app name
+#[tauri::command]
+pub fn query_games(
+    games: State<GameStore>,
+    uninstalled_games: State<UninstalledGames>,
+    include_uninstalled: Option<bool>,
+    search: Option<String>,
+) -> Result<Vec<GameInfo>, String> {
+    let mut results: Vec<GameInfo> = games
+        .lock()
+        .map_err(|e| format!("Failed to lock games: {}", e))?
+        .values()
+        .cloned()
+        .collect();
+    if include_uninstalled.unwrap_or(true) {
+        results.extend(
+            uninstalled_games
+                .lock()
+                .map_err(|e| format!("Failed to lock uninstalled games: {}", e))?
+                .values()
+                .cloned(),
+        );
+    }
+
+    if let Some(search) = search.map(|s| s.to_lowercase()).filter(|s| !s.is_empty()) {
+        results.retain(|g| {
+            g.display_name.to_lowercase().contains(&search)
+                || g.app_name.to_lowercase().contains(&search)
+        });
+    }
+    results.sort_by(|a, b| a.display_name.cmp(&b.display_name));
+    Ok(results)
 }
 
 #[tauri::command]
@@ -46,19 +131,42 @@ pub async fn scan_games_now(
     app_handle: AppHandle,
     games: State<'_, GameStore>,
     metadata_cache: State<'_, MetadataCache>,
+    settings: State<'_, SettingsState>,
+    cancel_flag: State<'_, ScanCancelFlag>,
+    uninstalled_games: State<'_, UninstalledGames>,
 ) -> Result<Vec<GameInfo>, String> {
     emit_log(&app_handle, "INFO", "Starting scan for Epic Games...");
 
-    let scanned_games = scan_epic_games_with_metadata(&*metadata_cache).await?;
+    let sources = load_manifest_sources().await;
+    let scanned_games =
+        scan_epic_games(&app_handle, &sources, &games, &metadata_cache, &cancel_flag).await?;
 
-    let mut games_lock = games
-        .lock()
-        .map_err(|e| format!("Failed to lock games: {}", e))?;
-    games_lock.clear();
+    let (removed, delta, had_library) = {
+        let mut games_lock = games
+            .lock()
+            .map_err(|e| format!("Failed to lock games: {}", e))?;
+        let removed = find_uninstalled(&games_lock, &scanned_games);
+        let delta = library_delta(&games_lock, &scanned_games);
+        let had_library = !games_lock.is_empty();
+        games_lock.clear();
 
-    for game in &scanned_games {
-        games_lock.insert(game.app_name.clone(), game.clone());
+        for game in &scanned_games {
+            games_lock.insert(game.app_name.clone(), game.clone());
+        }
+        (removed, delta, had_library)
+    };
+    announce_uninstalled(&app_handle, removed, &scanned_games, &uninstalled_games).await;
+    record_library_changes(&scanned_games, &settings);
+
+    // Same path as the background scan, so builds found here are still
+    // uploaded on detection and the frontend hears about the change
+    if !delta.is_empty() {
+        if had_library {
+            spawn_upload_on_detect(&app_handle, &delta, &scanned_games, &settings);
+        }
+        events::emit(&app_handle, AppEvent::GamesUpdated(delta));
     }
+    super::tray::refresh(&app_handle);
 
     emit_log(
         &app_handle,
@@ -80,9 +188,28 @@ pub async fn scan_games_now(
         );
     }
 
+    if let Ok(installations) = launcher::load_launcher_installations() {
+        let report = launcher::reconcile_with_launcher(&scanned_games, &installations);
+        log_launcher_inconsistencies(&app_handle, &report);
+    }
+
+    spawn_metadata_enrichment(
+        app_handle.clone(),
+        (*games).clone(),
+        (*metadata_cache).clone(),
+        (*settings).clone(),
+        scanned_games.clone(),
+    );
+
     Ok(scanned_games)
 }
 
+#[tauri::command]
+pub fn cancel_scan(app_handle: AppHandle, cancel_flag: State<ScanCancelFlag>) {
+    cancel_flag.store(true, Ordering::SeqCst);
+    emit_log(&app_handle, "INFO", "Cancelling scan...");
+}
+
 #[tauri::command]
 pub fn get_settings(settings: State<SettingsState>) -> Result<Settings, String> {
     let settings_lock = settings
@@ -98,20 +225,36 @@ pub fn set_settings(
     new_settings: Settings,
 ) -> Result<(), String> {
     emit_log(&app_handle, "INFO", "Updating settings...");
+    if let Err(e) = new_settings.validate() {
+        emit_log(&app_handle, "ERROR", &e);
+        return Err(e);
+    }
     let mut settings_lock = settings
         .lock()
         .map_err(|e| format!("Failed to lock settings: {}", e))?;
     *settings_lock = new_settings.clone();
+    drop(settings_lock);
     save_settings_to_file(&new_settings);
+    settings_watch::apply_settings(&app_handle, &new_settings);
     Ok(())
 }
 
+#[tauri::command]
+pub fn switch_profile(
+    app_handle: AppHandle,
+    settings: State<'_, SettingsState>,
+    name: String,
+) -> Result<Settings, String> {
+    profiles::switch_profile(&app_handle, &settings, &name)
+}
+
 #[tauri::command]
 pub async fn upload_manifest(
     app_handle: AppHandle,
     game_id: String,
     installation_guid: String,
     games: State<'_, GameStore>,
+    settings: State<'_, SettingsState>,
 ) -> Result<UploadStatus, String> {
     // Find the game by id (clone needed data, release lock before await)
     let game = {
@@ -130,6 +273,21 @@ pub async fn upload_manifest(
             return Err("Game not found".to_string());
         }
     };
+    if !game.available {
+        let message = if game.access_denied {
+            format!(
+                "{} is installed in a folder this account can't read; it may belong to another user",
+                game.display_name
+            )
+        } else {
+            format!(
+                "{} is on a drive that is not connected; reconnect it to upload",
+                game.display_name
+            )
+        };
+        emit_log(&app_handle, "WARN", &message);
+        return Err(message);
+    }
 
     emit_log(
         &app_handle,
@@ -137,8 +295,14 @@ pub async fn upload_manifest(
         &format!("Starting manifest upload for game: {}", game.display_name),
     );
 
+    let settings_snapshot = settings
+        .lock()
+        .map_err(|e| format!("Failed to lock settings: {}", e))?
+        .clone();
+
     // Use the internal upload function
-    let result = upload_manifest_internal(&game).await;
+    let result = upload_manifest_internal(&game, &settings_snapshot).await;
+    telemetry::record_upload(result.as_ref().map_or("failed", |s| s.status.as_str()));
 
     match &result {
         Ok(status) => match status.status.as_str() {
@@ -160,6 +324,15 @@ pub async fn upload_manifest(
                 "ERROR",
                 &format!("Failed to upload manifest for {}", game.display_name),
             ),
+            "invalid_manifest" => emit_log(
+                &app_handle,
+                "WARN",
+                &format!(
+                    "Not uploading manifest for {}: {}",
+                    game.display_name,
+                    status.message.as_deref().unwrap_or("invalid manifest")
+                ),
+            ),
             _ => {}
         },
         Err(e) => emit_log(
@@ -173,49 +346,150 @@ pub async fn upload_manifest(
 }
 
 #[tauri::command]
-pub async fn upload_all_manifests(games: State<'_, GameStore>) -> Result<Vec<UploadStatus>, String> {
-    let games_to_upload = {
+pub async fn upload_all_manifests(
+    games: State<'_, GameStore>,
+    settings: State<'_, SettingsState>,
+) -> Result<Vec<UploadStatus>, String> {
+    let settings_snapshot = settings
+        .lock()
+        .map_err(|e| format!("Failed to lock settings: {}", e))?
+        .clone();
+
+    upload_all_manifests_internal(&games, &settings_snapshot).await
+}
+
+#[tauri::command]
+pub fn set_uploads_paused(app_handle: AppHandle, paused: bool) {
+    super::pause::set_paused(&app_handle, paused);
+}
+
+#[tauri::command]
+pub fn get_failed_uploads() -> Vec<FailedUpload> {
+    let mut failed = retry_queue::load_failed_uploads()
+        .into_values()
+        .collect::<Vec<_>>();
+    failed.sort_by(|a, b| b.failed_at.cmp(&a.failed_at));
+    failed
+}
+
+// Checks every locally known build against the server, updating the ledger
+// and listing the builds the server is missing
+#[tauri::command]
+pub async fn reconcile_with_server(
+    app_handle: AppHandle,
+    games: State<'_, GameStore>,
+    settings: State<'_, SettingsState>,
+) -> Result<ServerReconciliation, String> {
+    let settings_snapshot = settings
+        .lock()
+        .map_err(|e| format!("Failed to lock settings: {}", e))?
+        .clone();
+    let report = reconcile::reconcile_with_server(&HttpEgdataApi, &games, &settings_snapshot).await;
+    reconcile::save_reconciliation(&report);
+    emit_log(
+        &app_handle,
+        "INFO",
+        &format!(
+            "Checked {} builds with the server: {} present, {} missing, {} inconclusive",
+            report.checked,
+            report.on_server,
+            report.missing.len(),
+            report.inconclusive
+        ),
+    );
+    Ok(report)
+}
+
+// Missing builds (as of the last reconcile), failed uploads and unreachable
+// installs in one list, each with the action that would contribute it
+#[tauri::command]
+pub fn get_contribution_opportunities(games: State<GameStore>) -> Vec<ContributionOpportunity> {
+    opportunities::find_opportunities(&games)
+}
+
+// Re-attempts the queued failures, optionally for one game only. Permanent
+// rejections and games no longer known are left in the queue untouched.
+#[tauri::command]
+pub async fn retry_failed_uploads(
+    app_handle: AppHandle,
+    app_name: Option<String>,
+    games: State<'_, GameStore>,
+    uninstalled_games: State<'_, UninstalledGames>,
+    settings: State<'_, SettingsState>,
+) -> Result<Vec<UploadStatus>, String> {
+    let settings_snapshot = settings
+        .lock()
+        .map_err(|e| format!("Failed to lock settings: {}", e))?
+        .clone();
+
+    let queued = retry_queue::load_failed_uploads()
+        .into_values()
+        .filter(|f| !f.permanent)
+        .filter(|f| match &app_name {
+            Some(name) => &f.app_name == name,
+            None => true,
+        })
+        .collect::<Vec<_>>();
+    let to_retry = {
         let games_lock = games
             .lock()
             .map_err(|e| format!("Failed to lock games: {}", e))?;
-        games_lock.values().cloned().collect::<Vec<_>>()
+        let tombstones = uninstalled_games
+            .lock()
+            .map_err(|e| format!("Failed to lock uninstalled games: {}", e))?;
+        queued
+            .iter()
+            .filter_map(|f| {
+                games_lock
+                    .get(&f.app_name)
+                    .or_else(|| tombstones.get(&f.app_name))
+                    .filter(|g| g.installation_guid == f.installation_guid)
+                    .cloned()
+            })
+            .collect::<Vec<_>>()
     };
 
-    let mut results = Vec::new();
-
-    for game in games_to_upload {
-        match upload_manifest_internal(&game).await {
-            Ok(status) => results.push(status),
-            Err(e) => results.push(UploadStatus {
-                status: "failed".to_string(),
-                message: Some(e),
-                manifest_hash: None,
-            }),
-        }
-    }
+    emit_log(
+        &app_handle,
+        "INFO",
+        &format!("Retrying {} failed uploads...", to_retry.len()),
+    );
+    // Same path as scheduled batches: batch lock, dedup and server check
+    let results = upload_games_with(&HttpEgdataApi, to_retry, &settings_snapshot, false).await?;
 
+    let failed_count = results
+        .iter()
+        .filter(|r| r.status != "uploaded" && r.status != "already_uploaded")
+        .count();
+    emit_log(
+        &app_handle,
+        if failed_count > 0 { "WARN" } else { "SUCCESS" },
+        &format!(
+            "Retry completed: {} of {} still failing",
+            failed_count,
+            results.len()
+        ),
+    );
     Ok(results)
 }
 
 #[tauri::command]
 pub fn open_directory(path: &str) -> Result<(), String> {
+    // Hand the path to the OS as-is so non-ASCII and oddly spaced names survive
+    let path = std::path::Path::new(path);
+
     #[cfg(target_os = "windows")]
     {
-        let path = std::path::Path::new(path);
-        let path_str = path.to_str().ok_or_else(|| "Invalid path".to_string())?;
-
         std::process::Command::new("explorer")
-            .args([path_str])
+            .arg(path)
             .spawn()
             .map_err(|e| format!("Failed to open directory: {e}"))?;
     }
     #[cfg(target_os = "macos")]
     {
         // On macOS, use 'open' command
-        let path = std::path::Path::new(path);
-        let path_str = path.to_str().ok_or_else(|| "Invalid path".to_string())?;
         std::process::Command::new("open")
-            .arg(path_str)
+            .arg(path)
             .spawn()
             .map_err(|e| format!("Failed to open directory: {e}"))?;
     }
@@ -230,4 +504,708 @@ pub fn open_directory(path: &str) -> Result<(), String> {
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+// Last known launcher state; None before the first scan attempt
+#[tauri::command]
+pub fn get_launcher_status() -> Option<LauncherState> {
+    launcher::launcher_state()
+}
+
+// Opens the game's library page in the Epic Games Launcher
+#[tauri::command]
+pub fn show_in_epic_launcher(
+    app_handle: AppHandle,
+    app_name: String,
+    games: State<GameStore>,
+) -> Result<(), String> {
+    let game = games
+        .lock()
+        .map_err(|e| format!("Failed to lock games: {}", e))?
+        .get(&app_name)
+        .cloned()
+        .ok_or_else(|| format!("Game not found: {}", app_name))?;
+    app_handle
+        .opener()
+        .open_url(launcher::library_uri(&game), None::<&str>)
+        .map_err(|e| format!("Failed to open the Epic Games Launcher: {}", e))
+}
+
+// Install folder of the game with this installation GUID
+#[tauri::command]
+pub async fn open_install_location(
+    installation_guid: String,
+    games: State<'_, GameStore>,
+) -> Result<(), OpenLocationError> {
+    let game = find_installation(&games, &installation_guid)?;
+    open_game_path(std::path::PathBuf::from(&game.install_location)).await
+}
+
+// The `.egstore` folder holding the game's build manifest
+#[tauri::command]
+pub async fn open_manifest_location(
+    installation_guid: String,
+    games: State<'_, GameStore>,
+) -> Result<(), OpenLocationError> {
+    let game = find_installation(&games, &installation_guid)?;
+    let manifest_path =
+        egstore_manifest_path(&game.install_location, &game.installation_guid).await;
+    let egstore = manifest_path
+        .parent()
+        .map(|dir| dir.to_path_buf())
+        .unwrap_or(manifest_path);
+    open_game_path(egstore).await
+}
+
+fn find_installation(
+    games: &GameStore,
+    installation_guid: &str,
+) -> Result<GameInfo, OpenLocationError> {
+    games
+        .lock()
+        .ok()
+        .and_then(|games_lock| {
+            games_lock
+                .values()
+                .find(|g| g.installation_guid.eq_ignore_ascii_case(installation_guid))
+                .cloned()
+        })
+        .ok_or_else(|| OpenLocationError {
+            code: OpenLocationErrorCode::GameNotFound,
+            message: format!(
+                "No installed game with installation GUID {}",
+                installation_guid
+            ),
+        })
+}
+
+// Probes first so a dead drive yields a clear error instead of a hung file manager
+async fn open_game_path(path: std::path::PathBuf) -> Result<(), OpenLocationError> {
+    let code = match probe_path(path.clone(), PATH_PROBE_TIMEOUT).await {
+        PathProbe::Available => None,
+        PathProbe::Missing => Some(OpenLocationErrorCode::PathMissing),
+        PathProbe::TimedOut => Some(OpenLocationErrorCode::DriveOffline),
+        PathProbe::AccessDenied => Some(OpenLocationErrorCode::AccessDenied),
+    };
+    if let Some(code) = code {
+        return Err(OpenLocationError {
+            code,
+            message: match code {
+                OpenLocationErrorCode::DriveOffline => {
+                    format!("The drive holding {} did not respond", path.display())
+                }
+                OpenLocationErrorCode::AccessDenied => {
+                    format!("No permission to open {}", path.display())
+                }
+                _ => format!("{} does not exist", path.display()),
+            },
+        });
+    }
+
+    open_directory(&path.to_string_lossy()).map_err(|message| OpenLocationError {
+        code: OpenLocationErrorCode::OpenFailed,
+        message,
+    })
+}
+
+#[tauri::command]
+pub fn get_crash_reports() -> Vec<CrashReport> {
+    crash::load_crash_reports()
+}
+
+#[tauri::command]
+pub fn clear_crash_reports(app_handle: AppHandle) -> Result<usize, String> {
+    let removed = crash::clear_crash_reports()?;
+    emit_log(
+        &app_handle,
+        "INFO",
+        &format!("Removed {} crash report(s)", removed),
+    );
+    Ok(removed)
+}
+
+#[tauri::command]
+pub fn get_cache_stats(metadata_cache: State<MetadataCache>) -> Result<CacheStats, String> {
+    let cache_lock = metadata_cache
+        .lock()
+        .map_err(|e| format!("Failed to lock metadata cache: {}", e))?;
+    let lookups = cache_lock.hits + cache_lock.misses;
+    let disk_size_bytes = std::fs::metadata(get_metadata_cache_path())
+        .map(|m| m.len())
+        .unwrap_or(0);
+
+    Ok(CacheStats {
+        entries: cache_lock.entries.len(),
+        hits: cache_lock.hits,
+        misses: cache_lock.misses,
+        hit_rate: if lookups > 0 {
+            cache_lock.hits as f64 / lookups as f64
+        } else {
+            0.0
+        },
+        disk_size_bytes,
+    })
+}
+
+#[tauri::command]
+pub fn clear_metadata_cache(
+    app_handle: AppHandle,
+    metadata_cache: State<MetadataCache>,
+) -> Result<(), String> {
+    let mut cache_lock = metadata_cache
+        .lock()
+        .map_err(|e| format!("Failed to lock metadata cache: {}", e))?;
+    let removed = cache_lock.entries.len();
+    cache_lock.clear();
+
+    let cache_path = get_metadata_cache_path();
+    if cache_path.exists() {
+        std::fs::remove_file(&cache_path)
+            .map_err(|e| format!("Failed to remove metadata cache file: {}", e))?;
+    }
+    // Otherwise still-fresh HTTP responses would repopulate the same entries
+    super::http_cache::clear()?;
+
+    emit_log(
+        &app_handle,
+        "INFO",
+        &format!("Cleared {} cached metadata entries", removed),
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn refresh_metadata(
+    app_handle: AppHandle,
+    item_id: String,
+    games: State<'_, GameStore>,
+    metadata_cache: State<'_, MetadataCache>,
+    settings: State<'_, SettingsState>,
+) -> Result<GameMetadata, String> {
+    // The cached entry stays until the fetch succeeds and replaces it
+    let timeout = settings
+        .lock()
+        .map_err(|e| format!("Failed to lock settings: {}", e))?
+        .metadata_timeout();
+
+    let metadata = refresh_game_metadata(&item_id, &metadata_cache, timeout)
+        .await
+        .ok_or_else(|| format!("Failed to fetch metadata for {}", item_id))?;
+
+    if let Ok(cache_lock) = metadata_cache.lock() {
+        save_metadata_cache_to_file(&cache_lock.entries);
+    }
+
+    publish_metadata(&app_handle, &games, &item_id, &metadata);
+
+    emit_log(
+        &app_handle,
+        "SUCCESS",
+        &format!("Refreshed metadata for {}", metadata.title),
+    );
+    Ok(metadata)
+}
+
+fn log_launcher_inconsistencies(app_handle: &AppHandle, report: &LauncherConsistencyReport) {
+    for app_name in &report.orphaned_manifests {
+        emit_log(
+            app_handle,
+            "WARN",
+            &format!(
+                "{} has a manifest but is not listed in LauncherInstalled.dat",
+                app_name
+            ),
+        );
+    }
+    for installation in &report.untracked_installs {
+        emit_log(
+            app_handle,
+            "WARN",
+            &format!(
+                "{} is installed at {} but has no manifest",
+                installation.app_name, installation.install_location
+            ),
+        );
+    }
+    for app_name in &report.location_mismatches {
+        emit_log(
+            app_handle,
+            "WARN",
+            &format!(
+                "Install location of {} differs from LauncherInstalled.dat",
+                app_name
+            ),
+        );
+    }
+}
+
+#[tauri::command]
+pub fn check_launcher_consistency(
+    app_handle: AppHandle,
+    games: State<GameStore>,
+) -> Result<LauncherConsistencyReport, String> {
+    let installations = launcher::load_launcher_installations()?;
+    let games_list = {
+        let games_lock = games
+            .lock()
+            .map_err(|e| format!("Failed to lock games: {}", e))?;
+        games_lock.values().cloned().collect::<Vec<_>>()
+    };
+
+    let report = launcher::reconcile_with_launcher(&games_list, &installations);
+    if report.is_consistent() {
+        emit_log(
+            &app_handle,
+            "SUCCESS",
+            "Manifests are consistent with LauncherInstalled.dat",
+        );
+    } else {
+        log_launcher_inconsistencies(&app_handle, &report);
+    }
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn get_launcher_version() -> Option<String> {
+    tauri::async_runtime::spawn_blocking(launcher::get_launcher_version)
+        .await
+        .ok()
+        .flatten()
+}
+
+#[tauri::command]
+pub fn get_telemetry_preview() -> TelemetryReport {
+    telemetry::build_report()
+}
+
+#[tauri::command]
+pub async fn get_release_notes(limit: Option<usize>) -> Result<Vec<ReleaseNote>, String> {
+    release_notes::fetch_release_notes(limit.unwrap_or(10)).await
+}
+
+#[tauri::command]
+pub async fn get_whats_new() -> Result<Option<ReleaseNote>, String> {
+    release_notes::take_whats_new().await
+}
+
+#[tauri::command]
+pub async fn run_diagnostics(
+    app_handle: AppHandle,
+    games: State<'_, GameStore>,
+) -> Result<DiagnosticsReport, String> {
+    let games_list = {
+        let games_lock = games
+            .lock()
+            .map_err(|e| format!("Failed to lock games: {}", e))?;
+        games_lock.values().cloned().collect::<Vec<_>>()
+    };
+
+    let report = diagnostics::run_diagnostics(games_list).await;
+    emit_log(
+        &app_handle,
+        if report.healthy { "SUCCESS" } else { "WARN" },
+        &format!(
+            "Diagnostics completed: {} check(s), {}",
+            report.checks.len(),
+            if report.healthy {
+                "no errors"
+            } else {
+                "problems found"
+            }
+        ),
+    );
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn export_diagnostics(
+    app_handle: AppHandle,
+    path: String,
+    games: State<'_, GameStore>,
+    settings: State<'_, SettingsState>,
+) -> Result<(), String> {
+    let games_list = {
+        let games_lock = games
+            .lock()
+            .map_err(|e| format!("Failed to lock games: {}", e))?;
+        games_lock.values().cloned().collect::<Vec<_>>()
+    };
+    let settings_snapshot = settings
+        .lock()
+        .map_err(|e| format!("Failed to lock settings: {}", e))?
+        .clone();
+
+    diagnostics::export_diagnostics(std::path::Path::new(&path), games_list, settings_snapshot)
+        .await?;
+
+    emit_log(
+        &app_handle,
+        "SUCCESS",
+        &format!("Diagnostics bundle exported to {}", path),
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn export_manifest_bundle(
+    app_handle: AppHandle,
+    installation_guid: String,
+    dest: String,
+    games: State<'_, GameStore>,
+    uninstalled_games: State<'_, UninstalledGames>,
+) -> Result<(), String> {
+    let find = |store: &std::collections::HashMap<String, GameInfo>| {
+        store
+            .values()
+            .find(|g| g.installation_guid == installation_guid)
+            .cloned()
+    };
+    let game = games
+        .lock()
+        .map_err(|e| format!("Failed to lock games: {}", e))
+        .map(|games_lock| find(&games_lock))?
+        .or_else(|| uninstalled_games.lock().ok().and_then(|t| find(&t)))
+        .ok_or_else(|| format!("No game with installation {}", installation_guid))?;
+
+    // Reading the manifests and deflating the zip is disk-bound; keep it off the async workers
+    let bundle_game = game.clone();
+    let bundle_dest = dest.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        export::export_manifest_bundle(&bundle_game, std::path::Path::new(&bundle_dest))
+    })
+    .await
+    .map_err(|e| format!("Export failed: {}", e))??;
+
+    emit_log(
+        &app_handle,
+        "SUCCESS",
+        &format!("Exported {} manifest bundle to {}", game.display_name, dest),
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn export_all_manifests(
+    app_handle: AppHandle,
+    dest_dir: String,
+    games: State<'_, GameStore>,
+) -> Result<BulkExportSummary, String> {
+    let games_list = {
+        let games_lock = games
+            .lock()
+            .map_err(|e| format!("Failed to lock games: {}", e))?;
+        games_lock.values().cloned().collect::<Vec<_>>()
+    };
+
+    let total = games_list.len();
+    let export_dir = dest_dir.clone();
+    let summary = tauri::async_runtime::spawn_blocking(move || {
+        export::export_all_manifests(&games_list, std::path::Path::new(&export_dir))
+    })
+    .await
+    .map_err(|e| format!("Export failed: {}", e))??;
+
+    emit_log(
+        &app_handle,
+        if summary.failed.is_empty() {
+            "SUCCESS"
+        } else {
+            "WARN"
+        },
+        &format!(
+            "Exported {} of {} manifests to {}",
+            summary.exported.len(),
+            total,
+            summary.path
+        ),
+    );
+    for failure in &summary.failed {
+        emit_log(&app_handle, "WARN", &format!("Export skipped {}", failure));
+    }
+    Ok(summary)
+}
+
+// Lists the extra `.egstore` files each installed game would upload once
+// opted in, so the user can judge size and privacy first
+#[tauri::command]
+pub async fn preview_sidecar_uploads(
+    games: State<'_, GameStore>,
+) -> Result<Vec<SidecarReport>, String> {
+    let games_list = {
+        let games_lock = games
+            .lock()
+            .map_err(|e| format!("Failed to lock games: {}", e))?;
+        games_lock.values().cloned().collect::<Vec<_>>()
+    };
+
+    // Reads every sidecar file of every game; keep it off the async workers
+    let mut reports = tauri::async_runtime::spawn_blocking(move || {
+        games_list
+            .iter()
+            .map(sidecars::preview)
+            .filter(|report| !report.files.is_empty())
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|e| format!("Sidecar preview failed: {}", e))?;
+    reports.sort_by(|a, b| a.display_name.cmp(&b.display_name));
+    Ok(reports)
+}
+
+#[tauri::command]
+pub async fn install_background_service(app_handle: AppHandle) -> Result<(), String> {
+    // May wait on a UAC prompt and schtasks; keep it off the main thread
+    let result = tauri::async_runtime::spawn_blocking(background_service::install_elevated)
+        .await
+        .map_err(|e| format!("Failed to create scheduled task: {}", e))?;
+    if let Err(e) = result {
+        emit_log(&app_handle, "ERROR", &e);
+        return Err(e);
+    }
+    emit_log(
+        &app_handle,
+        "SUCCESS",
+        "Background upload task installed; it runs at each login",
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn uninstall_background_service(app_handle: AppHandle) -> Result<(), String> {
+    let result = tauri::async_runtime::spawn_blocking(background_service::uninstall)
+        .await
+        .map_err(|e| format!("Failed to delete scheduled task: {}", e))?;
+    if let Err(e) = result {
+        emit_log(&app_handle, "ERROR", &e);
+        return Err(e);
+    }
+    emit_log(&app_handle, "SUCCESS", "Background upload task removed");
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn is_background_service_installed() -> Option<bool> {
+    tauri::async_runtime::spawn_blocking(background_service::is_installed)
+        .await
+        .ok()
+        .flatten()
+}
+
+#[tauri::command]
+pub async fn get_elevation_status() -> ElevationStatus {
+    tauri::async_runtime::spawn_blocking(elevation::status)
+        .await
+        .unwrap_or(ElevationStatus {
+            supported: cfg!(target_os = "windows"),
+            elevated: false,
+        })
+}
+
+// Runs a one-off task ("scan" or "install-background-service") in an elevated
+// copy of the client, after a UAC prompt, and waits for it to finish
+#[tauri::command]
+pub async fn run_elevated_task(app_handle: AppHandle, task: String) -> Result<(), String> {
+    let args = elevation::task_args(&task)?;
+    emit_log(
+        &app_handle,
+        "INFO",
+        &format!("Requesting administrator rights for {}", task),
+    );
+    let result = tauri::async_runtime::spawn_blocking(move || elevation::run_elevated(args))
+        .await
+        .map_err(|e| format!("Elevated task failed: {}", e))?;
+    match result {
+        Ok(0) => {
+            emit_log(
+                &app_handle,
+                "SUCCESS",
+                &format!("Elevated {} completed", task),
+            );
+            Ok(())
+        }
+        Ok(code) => {
+            let message = format!("Elevated {} exited with code {}", task, code);
+            emit_log(&app_handle, "ERROR", &message);
+            Err(message)
+        }
+        Err(e) => {
+            emit_log(&app_handle, "ERROR", &e);
+            Err(e)
+        }
+    }
+}
+
+#[tauri::command]
+pub fn backup_app_data(app_handle: AppHandle, path: String) -> Result<Vec<String>, String> {
+    let files = backup::backup_app_data(std::path::Path::new(&path))?;
+    emit_log(
+        &app_handle,
+        "SUCCESS",
+        &format!("Backed up {} app data files to {}", files.len(), path),
+    );
+    Ok(files)
+}
+
+// Restores the backup and restarts so every component reloads from disk. State
+// is deliberately not saved first: that would overwrite the restored files.
+#[tauri::command]
+pub fn restore_app_data(
+    app_handle: AppHandle,
+    path: String,
+    cancel_flag: State<'_, ScanCancelFlag>,
+) -> Result<(), String> {
+    cancel_flag.store(true, Ordering::SeqCst);
+    let files = match backup::restore_app_data(std::path::Path::new(&path)) {
+        Ok(files) => files,
+        Err(e) => {
+            cancel_flag.store(false, Ordering::SeqCst);
+            return Err(e);
+        }
+    };
+    emit_log(
+        &app_handle,
+        "SUCCESS",
+        &format!(
+            "Restored {} app data files from {}, restarting...",
+            files.len(),
+            path
+        ),
+    );
+    app_handle.restart();
+}
+
+#[tauri::command]
+pub async fn import_manifests(
+    app_handle: AppHandle,
+    dir: String,
+    settings: State<'_, SettingsState>,
+) -> Result<ImportSummary, String> {
+    let settings_snapshot = settings
+        .lock()
+        .map_err(|e| format!("Failed to lock settings: {}", e))?
+        .clone();
+
+    emit_log(
+        &app_handle,
+        "INFO",
+        &format!("Importing manifests from {}", dir),
+    );
+    let summary =
+        import::import_manifests(&app_handle, std::path::Path::new(&dir), &settings_snapshot)
+            .await?;
+
+    let uploaded = summary
+        .results
+        .iter()
+        .filter(|r| r.status == "uploaded")
+        .count();
+    emit_log(
+        &app_handle,
+        "SUCCESS",
+        &format!(
+            "Import completed: {} uploaded, {} processed, {} invalid",
+            uploaded,
+            summary.results.len(),
+            summary.invalid_items.len()
+        ),
+    );
+    Ok(summary)
+}
+
+#[tauri::command]
+pub fn get_library_history() -> LibraryHistory {
+    history::get_library_history()
+}
+
+// One last upload for a game that was uninstalled while its manifest survived
+#[tauri::command]
+pub async fn upload_final_manifest(
+    app_handle: AppHandle,
+    app_name: String,
+    uninstalled_games: State<'_, UninstalledGames>,
+    settings: State<'_, SettingsState>,
+) -> Result<UploadStatus, String> {
+    let game = uninstalled_games
+        .lock()
+        .map_err(|e| format!("Failed to lock uninstalled games: {}", e))?
+        .get(&app_name)
+        .cloned()
+        .ok_or_else(|| format!("No uninstalled game named {}", app_name))?;
+    let settings_snapshot = settings
+        .lock()
+        .map_err(|e| format!("Failed to lock settings: {}", e))?
+        .clone();
+
+    let result = upload_manifest_internal(&game, &settings_snapshot).await;
+    telemetry::record_upload(result.as_ref().map_or("failed", |s| s.status.as_str()));
+
+    match &result {
+        Ok(status) if status.status == "uploaded" || status.status == "already_uploaded" => {
+            emit_log(
+                &app_handle,
+                "SUCCESS",
+                &format!("Uploaded final manifest for {}", game.display_name),
+            );
+            // The tombstone stays in the history; it only records the upload
+            if let Ok(mut uninstalled_lock) = uninstalled_games.lock() {
+                if let Some(tombstone) = uninstalled_lock.get_mut(&app_name) {
+                    tombstone.final_uploaded_at = Some(chrono::Utc::now().to_rfc3339());
+                }
+                save_uninstalled_games_to_file(&uninstalled_lock);
+            }
+        }
+        Ok(_) => emit_log(
+            &app_handle,
+            "ERROR",
+            &format!("Failed to upload final manifest for {}", game.display_name),
+        ),
+        Err(e) => emit_log(
+            &app_handle,
+            "ERROR",
+            &format!("Final upload error for {}: {}", game.display_name, e),
+        ),
+    }
+
+    result
+}
+
+#[tauri::command]
+pub fn get_activity_timeline(limit: Option<usize>, before: Option<String>) -> Vec<ActivityEvent> {
+    activity::get_timeline(limit.unwrap_or(100), before.as_deref())
+}
+
+// Shown in Settings so the user can configure scripts against the local API
+#[tauri::command]
+pub fn get_local_api_token() -> String {
+    local_api::get_token().to_string()
+}
+
+// Cancels any running scan, persists in-memory state and relaunches; for
+// settings that only apply at startup
+#[tauri::command]
+pub fn restart_app(
+    app_handle: AppHandle,
+    games: State<'_, GameStore>,
+    metadata_cache: State<'_, MetadataCache>,
+    settings: State<'_, SettingsState>,
+    cancel_flag: State<'_, ScanCancelFlag>,
+    uninstalled_games: State<'_, UninstalledGames>,
+) {
+    emit_log(&app_handle, "INFO", "Restarting...");
+    cancel_flag.store(true, Ordering::SeqCst);
+
+    if let Ok(settings_lock) = settings.lock() {
+        save_settings_to_file(&settings_lock);
+    }
+    if let Ok(cache_lock) = metadata_cache.lock() {
+        save_metadata_cache_to_file(&cache_lock.entries);
+    }
+    if let Ok(games_lock) = games.lock() {
+        save_library_cache_to_file(&games_lock.values().cloned().collect::<Vec<_>>());
+    }
+    if let Ok(uninstalled_lock) = uninstalled_games.lock() {
+        save_uninstalled_games_to_file(&uninstalled_lock);
+    }
+
+    app_handle.restart();
+}
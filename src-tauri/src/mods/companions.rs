@@ -0,0 +1,95 @@
+use super::models::GameInfo;
+use super::utils::{file_fingerprint, get_egstore_manifest_path};
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+type Fingerprint = (u64, Option<SystemTime>);
+
+// Content hashes of companion files, valid while their size and mtime are unchanged
+static HASH_CACHE: Lazy<Mutex<HashMap<PathBuf, (Fingerprint, String)>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+// A file in a game's `.egstore` uploaded alongside its main manifest
+pub struct CompanionManifest {
+    pub path: PathBuf,
+    // "mancpn", or "dlc-manifest" for a DLC's build manifest
+    pub kind: &'static str,
+}
+
+// `.mancpn` files, plus `.manifest` files that belong to no scanned `.item`
+// (DLC installed into the base game's folder). Manifests of other scanned
+// games are left out since those are uploaded on their own.
+pub fn find_companion_manifests(
+    game: &GameInfo,
+    scanned_guids: &HashSet<String>,
+) -> Vec<CompanionManifest> {
+    let main_manifest = get_egstore_manifest_path(&game.install_location, &game.installation_guid);
+    let Some(egstore) = main_manifest.parent() else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(egstore) else {
+        return Vec::new();
+    };
+
+    let mut companions = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter_map(|path| {
+            let stem = path.file_stem()?.to_str()?.to_string();
+            let kind = match path.extension()?.to_str()? {
+                "mancpn" => "mancpn",
+                "manifest"
+                    if !stem.eq_ignore_ascii_case(&game.installation_guid)
+                        && !scanned_guids.contains(&stem.to_uppercase()) =>
+                {
+                    "dlc-manifest"
+                }
+                _ => return None,
+            };
+            Some(CompanionManifest { path, kind })
+        })
+        .collect::<Vec<_>>();
+    companions.sort_by(|a, b| a.path.cmp(&b.path));
+    companions
+}
+
+// SHA-1 hex, the hash the launcher records as ManifestHash. Verifies main
+// manifests and identifies companion files for dedup.
+pub fn content_hash(bytes: &[u8]) -> String {
+    sha1_smol::Sha1::from(bytes).digest().to_string()
+}
+
+// `content_hash` on the blocking pool at background priority; hands the bytes
+// back alongside their hash
+pub async fn content_hash_in_background(bytes: Vec<u8>) -> Result<(Vec<u8>, String), String> {
+    super::priority::spawn_background(move || {
+        let hash = content_hash(&bytes);
+        (bytes, hash)
+    })
+    .await
+    .map_err(|e| format!("Hashing task failed: {}", e))
+}
+
+// The hash `path` had when last read, if the file hasn't changed since
+pub fn cached_hash(path: &Path) -> Option<String> {
+    let fingerprint = file_fingerprint(path).ok()?;
+    let cache = HASH_CACHE.lock().ok()?;
+    cache
+        .get(path)
+        .filter(|(cached, _)| *cached == fingerprint)
+        .map(|(_, hash)| hash.clone())
+}
+
+pub fn remember_hash(path: &Path, hash: &str) {
+    let Ok(fingerprint) = file_fingerprint(path) else {
+        return;
+    };
+    if let Ok(mut cache) = HASH_CACHE.lock() {
+        cache.insert(path.to_path_buf(), (fingerprint, hash.to_string()));
+    }
+}
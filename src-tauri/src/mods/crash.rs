@@ -0,0 +1,130 @@
+use super::models::CrashReport;
+use super::utils::get_app_data_path;
+use std::fs;
+use std::panic;
+use std::path::PathBuf;
+
+const CRASH_DIR: &str = "crashes";
+const CRASH_REPORT_URL: &str = "https://egdata-builds-api.snpm.workers.dev/crash-report";
+
+pub fn get_crash_dir() -> PathBuf {
+    get_app_data_path().join(CRASH_DIR)
+}
+
+// Records every panic (including ones inside background tasks, which would
+// otherwise die silently) to the crash directory, then defers to the default hook.
+pub fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let message = if let Some(s) = info.payload().downcast_ref::<&str>() {
+            s.to_string()
+        } else if let Some(s) = info.payload().downcast_ref::<String>() {
+            s.clone()
+        } else {
+            "Unknown panic payload".to_string()
+        };
+
+        let now = chrono::Utc::now();
+        let report = CrashReport {
+            id: format!("crash-{}", now.format("%Y%m%d-%H%M%S%.3f")),
+            timestamp: now.to_rfc3339(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            thread: std::thread::current()
+                .name()
+                .unwrap_or("<unnamed>")
+                .to_string(),
+            message,
+            location: info
+                .location()
+                .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column())),
+            backtrace: std::backtrace::Backtrace::force_capture().to_string(),
+        };
+
+        write_crash_report(&report);
+        default_hook(info);
+    }));
+}
+
+fn write_crash_report(report: &CrashReport) {
+    let crash_dir = get_crash_dir();
+    if let Err(e) = fs::create_dir_all(&crash_dir) {
+        eprintln!("Failed to create crash directory: {}", e);
+        return;
+    }
+
+    let report_path = crash_dir.join(format!("{}.json", report.id));
+    match serde_json::to_string_pretty(report) {
+        Ok(json) => {
+            if let Err(e) = fs::write(&report_path, json) {
+                eprintln!("Failed to write crash report {:?}: {}", report_path, e);
+            }
+        }
+        Err(e) => eprintln!("Failed to serialize crash report: {}", e),
+    }
+}
+
+pub fn load_crash_reports() -> Vec<CrashReport> {
+    let mut reports = Vec::new();
+    let entries = match fs::read_dir(get_crash_dir()) {
+        Ok(entries) => entries,
+        Err(_) => return reports,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|s| s.to_str()) != Some("json") {
+            continue;
+        }
+        match fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<CrashReport>(&content).ok())
+        {
+            Some(report) => reports.push(report),
+            None => eprintln!("Failed to read crash report {:?}", path),
+        }
+    }
+
+    reports.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    reports
+}
+
+pub fn clear_crash_reports() -> Result<usize, String> {
+    let reports = load_crash_reports();
+    for report in &reports {
+        let path = get_crash_dir().join(format!("{}.json", report.id));
+        fs::remove_file(&path)
+            .map_err(|e| format!("Failed to remove crash report {}: {}", report.id, e))?;
+    }
+    Ok(reports.len())
+}
+
+// Only called when the user has opted in via `crash_reporting_enabled`.
+// Reports are removed locally once the server has accepted them.
+pub async fn submit_pending_crash_reports() -> Result<usize, String> {
+    let mut submitted = 0;
+
+    for report in load_crash_reports() {
+        let resp = crate::HTTP_CLIENT
+            .post(CRASH_REPORT_URL)
+            .json(&report)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send crash report: {}", e))?;
+
+        if !resp.status().is_success() {
+            return Err(format!(
+                "Crash report {} rejected: {}",
+                report.id,
+                resp.status()
+            ));
+        }
+
+        let path = get_crash_dir().join(format!("{}.json", report.id));
+        let _ = fs::remove_file(path);
+        submitted += 1;
+    }
+
+    Ok(submitted)
+}
@@ -0,0 +1,300 @@
+use super::crash;
+use super::launcher;
+use super::models::{DiagnosticCheck, DiagnosticsReport, GameInfo, Settings};
+use super::utils::{
+    decode_text_file, get_app_data_path, get_egstore_manifest_path, get_recent_logs,
+    is_auto_start_enabled,
+};
+use super::webhooks;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+fn check(name: &str, status: &str, detail: impl Into<String>) -> DiagnosticCheck {
+    DiagnosticCheck {
+        name: name.to_string(),
+        status: status.to_string(),
+        detail: detail.into(),
+    }
+}
+
+fn check_manifests_directory() -> Vec<DiagnosticCheck> {
+    let manifests_path = crate::get_manifests_path();
+    let entries = match fs::read_dir(&manifests_path) {
+        Ok(entries) => entries,
+        Err(e) => {
+            return vec![check(
+                "manifests_directory",
+                "error",
+                format!("{} is not readable: {}", manifests_path.display(), e),
+            )]
+        }
+    };
+
+    let item_paths = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().and_then(|s| s.to_str()) == Some("item"))
+        .collect::<Vec<_>>();
+
+    let mut checks = vec![check(
+        "manifests_directory",
+        "ok",
+        format!(
+            "{} contains {} .item file(s)",
+            manifests_path.display(),
+            item_paths.len()
+        ),
+    )];
+
+    checks.push(match item_paths.first() {
+        None => check("item_parse", "warning", "No .item files to sample"),
+        Some(sample) => {
+            let parsed = fs::read(sample)
+                .map_err(|e| e.to_string())
+                .and_then(|bytes| decode_text_file(sample, &bytes))
+                .and_then(|text| {
+                    serde_json::from_str::<serde_json::Value>(&text).map_err(|e| e.to_string())
+                });
+            match parsed {
+                Ok(_) => check(
+                    "item_parse",
+                    "ok",
+                    format!("{} parsed successfully", sample.display()),
+                ),
+                Err(e) => check(
+                    "item_parse",
+                    "error",
+                    format!("{} could not be parsed: {}", sample.display(), e),
+                ),
+            }
+        }
+    });
+
+    checks
+}
+
+fn check_egstore_access(games: &[GameInfo]) -> DiagnosticCheck {
+    let unreachable = games
+        .iter()
+        .filter(|g| !get_egstore_manifest_path(&g.install_location, &g.installation_guid).exists())
+        .map(|g| g.display_name.clone())
+        .collect::<Vec<_>>();
+
+    if games.is_empty() {
+        check("egstore_access", "warning", "No games scanned yet")
+    } else if unreachable.is_empty() {
+        check(
+            "egstore_access",
+            "ok",
+            format!("All {} game manifests are reachable", games.len()),
+        )
+    } else {
+        check(
+            "egstore_access",
+            "warning",
+            format!(
+                "{} of {} manifests unreachable: {}",
+                unreachable.len(),
+                games.len(),
+                unreachable.join(", ")
+            ),
+        )
+    }
+}
+
+async fn check_endpoint(name: &str, url: &str) -> DiagnosticCheck {
+    match crate::HTTP_CLIENT.get(url).send().await {
+        // Any HTTP answer means the service is reachable
+        Ok(resp) => check(
+            name,
+            "ok",
+            format!("{} responded with {}", url, resp.status()),
+        ),
+        Err(e) => check(name, "error", format!("{} unreachable: {}", url, e)),
+    }
+}
+
+fn check_app_data_writable() -> DiagnosticCheck {
+    let app_data_path = get_app_data_path();
+    let probe = app_data_path.join(".write_test");
+    let result = fs::create_dir_all(&app_data_path)
+        .and_then(|_| fs::write(&probe, b"ok"))
+        .and_then(|_| fs::remove_file(&probe));
+
+    match result {
+        Ok(()) => check(
+            "app_data_writable",
+            "ok",
+            format!("{} is writable", app_data_path.display()),
+        ),
+        Err(e) => check(
+            "app_data_writable",
+            "error",
+            format!("{} is not writable: {}", app_data_path.display(), e),
+        ),
+    }
+}
+
+fn check_auto_start() -> DiagnosticCheck {
+    match is_auto_start_enabled() {
+        Some(true) => check("auto_start", "ok", "Registered to start at login"),
+        Some(false) => check("auto_start", "warning", "Not registered to start at login"),
+        None => check(
+            "auto_start",
+            "warning",
+            "Auto-start is not supported on this platform",
+        ),
+    }
+}
+
+fn check_launcher_version() -> DiagnosticCheck {
+    match launcher::get_launcher_version() {
+        Some(version) => check("launcher_version", "ok", version),
+        None => check(
+            "launcher_version",
+            "warning",
+            "Epic Games Launcher version could not be detected",
+        ),
+    }
+}
+
+pub async fn run_diagnostics(games: Vec<GameInfo>) -> DiagnosticsReport {
+    let mut checks = tauri::async_runtime::spawn_blocking(move || {
+        let mut checks = check_manifests_directory();
+        checks.push(check_egstore_access(&games));
+        checks.push(check_app_data_writable());
+        checks.push(check_auto_start());
+        checks.push(check_launcher_version());
+        checks
+    })
+    .await
+    .unwrap_or_default();
+
+    checks.push(check_endpoint("metadata_api", "https://api.egdata.app/").await);
+    checks.push(check_endpoint("builds_api", crate::BUILDS_API_URL).await);
+
+    DiagnosticsReport {
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        client_version: env!("CARGO_PKG_VERSION").to_string(),
+        healthy: checks.iter().all(|c| c.status != "error"),
+        checks,
+    }
+}
+
+// Settings with anything that could identify the user (paths, secrets) blanked
+fn redacted_settings(settings: &Settings) -> serde_json::Value {
+    let mut value = serde_json::to_value(settings).unwrap_or_default();
+    if let Some(map) = value.as_object_mut() {
+        for key in ["upload_pinned_cert_path"] {
+            if map.get(key).is_some_and(|v| !v.is_null()) {
+                map.insert(key.to_string(), serde_json::json!("<redacted>"));
+            }
+        }
+        // Webhook URLs usually embed a secret token
+        if let Some(webhooks) = map.get_mut("webhooks").and_then(|v| v.as_array_mut()) {
+            for webhook in webhooks {
+                webhook["url"] = serde_json::json!("<redacted>");
+            }
+        }
+        // Self-hosted endpoints can name private hosts or carry credentials;
+        // how many there are (and which environments are routed) is enough
+        if let Some(endpoints) = map
+            .get_mut("upload_endpoints")
+            .and_then(|v| v.as_array_mut())
+        {
+            for endpoint in endpoints {
+                *endpoint = serde_json::json!("<redacted>");
+            }
+        }
+        if let Some(endpoints) = map
+            .get_mut("environment_endpoints")
+            .and_then(|v| v.as_object_mut())
+        {
+            for endpoint in endpoints.values_mut() {
+                *endpoint = serde_json::json!("<redacted>");
+            }
+        }
+    }
+    value
+}
+
+fn library_summary(games: &[GameInfo]) -> serde_json::Value {
+    let entries = games
+        .iter()
+        .map(|g| {
+            serde_json::json!({
+                "display_name": g.display_name,
+                "app_name": g.app_name,
+                "version": g.version,
+                "catalog_item_id": g.catalog_item_id,
+                "manifest_hash": g.manifest_hash,
+                "available": g.available,
+                "has_metadata": g.metadata.is_some(),
+            })
+        })
+        .collect::<Vec<_>>();
+    serde_json::json!({ "game_count": games.len(), "games": entries })
+}
+
+pub async fn export_diagnostics(
+    destination: &Path,
+    games: Vec<GameInfo>,
+    settings: Settings,
+) -> Result<(), String> {
+    let report = run_diagnostics(games.clone()).await;
+
+    let files: Vec<(&str, serde_json::Value)> = vec![
+        (
+            "diagnostics.json",
+            serde_json::to_value(&report).unwrap_or_default(),
+        ),
+        ("settings.json", redacted_settings(&settings)),
+        ("library.json", library_summary(&games)),
+        (
+            "logs.json",
+            serde_json::to_value(get_recent_logs()).unwrap_or_default(),
+        ),
+        (
+            "crashes.json",
+            serde_json::to_value(crash::load_crash_reports()).unwrap_or_default(),
+        ),
+    ];
+
+    // The bundle goes on bug reports; logs, crashes and checks mention install
+    // folders and the home directory, which carries the account name
+    let known_paths = games
+        .iter()
+        .flat_map(|g| {
+            [
+                Some(g.install_location.clone()),
+                g.moved_from.clone(),
+                g.wine_prefix.clone(),
+            ]
+        })
+        .flatten()
+        .collect::<Vec<_>>();
+    let files = files
+        .into_iter()
+        .map(|(name, value)| (name, webhooks::redact_local_paths(value, &known_paths)))
+        .collect::<Vec<_>>();
+
+    let file = fs::File::create(destination)
+        .map_err(|e| format!("Failed to create {}: {}", destination.display(), e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    for (name, value) in files {
+        let json = serde_json::to_vec_pretty(&value)
+            .map_err(|e| format!("Failed to serialize {}: {}", name, e))?;
+        zip.start_file(name, options)
+            .map_err(|e| format!("Failed to add {} to bundle: {}", name, e))?;
+        zip.write_all(&json)
+            .map_err(|e| format!("Failed to write {} to bundle: {}", name, e))?;
+    }
+
+    zip.finish()
+        .map_err(|e| format!("Failed to finalize diagnostics bundle: {}", e))?;
+    Ok(())
+}
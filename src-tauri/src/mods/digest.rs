@@ -0,0 +1,74 @@
+use super::activity;
+use super::events::{self, AppEvent};
+use super::models::DailyDigest;
+use super::notifications;
+use super::state::SettingsState;
+use super::utils::{read_json, write_json};
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+use tauri::AppHandle;
+use tokio::time;
+
+const LAST_DIGEST_FILE: &str = "last_digest";
+const DIGEST_PERIOD: chrono::Duration = chrono::Duration::hours(24);
+const CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+fn read_last_digest() -> Option<DateTime<Utc>> {
+    read_json(LAST_DIGEST_FILE)
+}
+
+fn write_last_digest(at: DateTime<Utc>) {
+    write_json(LAST_DIGEST_FILE, &at, "digest time");
+}
+
+// Summarizes the activity timeline between `since` and `until`
+pub fn build_digest(since: DateTime<Utc>, until: DateTime<Utc>) -> DailyDigest {
+    let mut digest = DailyDigest {
+        since: since.to_rfc3339(),
+        until: until.to_rfc3339(),
+        ..Default::default()
+    };
+    for event in activity::events_since(&digest.since) {
+        match event.kind.as_str() {
+            "upload" => digest.uploaded += 1,
+            "upload-failed" => digest.failed += 1,
+            "installed" => digest.installed += 1,
+            "updated" => digest.updated += 1,
+            _ => {}
+        }
+    }
+    digest
+}
+
+// Sends one digest a day while `daily_digest_enabled` is on; quiet days are
+// skipped. The time of the last digest survives restarts.
+pub async fn periodic_digest(app_handle: AppHandle, settings: SettingsState) {
+    loop {
+        time::sleep(CHECK_INTERVAL).await;
+
+        let enabled = settings
+            .lock()
+            .map(|s| s.daily_digest_enabled)
+            .unwrap_or(false);
+        let now = Utc::now();
+        let Some(last) = read_last_digest() else {
+            // Count from the first time the loop runs
+            write_last_digest(now);
+            continue;
+        };
+        if now - last < DIGEST_PERIOD {
+            continue;
+        }
+        write_last_digest(now);
+        if !enabled {
+            continue;
+        }
+
+        let digest = build_digest(last, now);
+        if digest.is_empty() {
+            continue;
+        }
+        notifications::notify_digest(&app_handle, &digest);
+        events::emit(&app_handle, AppEvent::DailyDigest(&digest));
+    }
+}
@@ -0,0 +1,51 @@
+use super::models::GameInfo;
+use std::collections::{HashMap, HashSet};
+
+// Nests DLC and addons under the top-level game their `MainGameAppName` chain
+// leads to, so an addon of an addon isn't lost. Entries whose parent isn't
+// installed, or whose chain loops, stay at the top level.
+pub fn group_by_parent(games: &[GameInfo]) -> Vec<GameInfo> {
+    let by_name = games
+        .iter()
+        .map(|game| (game.app_name.as_str(), game))
+        .collect::<HashMap<_, _>>();
+    let parent_of = |game: &GameInfo| {
+        Some(game.main_game_app_name.as_str())
+            .filter(|name| !name.is_empty() && *name != game.app_name)
+            .and_then(|name| by_name.get(name).copied())
+    };
+    let top_level_ancestor = |game: &GameInfo| {
+        let mut seen = HashSet::from([game.app_name.as_str()]);
+        let mut ancestor = parent_of(game)?;
+        while let Some(parent) = parent_of(ancestor) {
+            if !seen.insert(ancestor.app_name.as_str()) {
+                return None;
+            }
+            ancestor = parent;
+        }
+        Some(ancestor.app_name.as_str())
+    };
+
+    let mut children: HashMap<&str, Vec<GameInfo>> = HashMap::new();
+    let mut top_level = Vec::new();
+    for game in games {
+        match top_level_ancestor(game) {
+            Some(ancestor) => children.entry(ancestor).or_default().push(game.clone()),
+            None => top_level.push(game),
+        }
+    }
+
+    let mut grouped = top_level
+        .into_iter()
+        .map(|game| {
+            let mut parent = game.clone();
+            parent.children = children.remove(game.app_name.as_str()).unwrap_or_default();
+            parent
+                .children
+                .sort_by(|a, b| a.display_name.cmp(&b.display_name));
+            parent
+        })
+        .collect::<Vec<_>>();
+    grouped.sort_by(|a, b| a.display_name.cmp(&b.display_name));
+    grouped
+}
@@ -0,0 +1,88 @@
+use super::models::ElevationStatus;
+
+// One-off tasks that may need administrator rights, run by a short-lived
+// elevated copy of the client through its command-line flags
+pub fn task_args(task: &str) -> Result<&'static [&'static str], String> {
+    match task {
+        // Scan and upload with rights to read installs in other users' profiles
+        "scan" => Ok(&["--once"]),
+        "install-background-service" => Ok(&["--install-background-service"]),
+        _ => Err(format!("Unknown elevated task: {}", task)),
+    }
+}
+
+pub fn status() -> ElevationStatus {
+    ElevationStatus {
+        supported: cfg!(target_os = "windows"),
+        elevated: is_elevated(),
+    }
+}
+
+// Whether this process's token is elevated (UAC), not just an admin's
+#[cfg(target_os = "windows")]
+pub fn is_elevated() -> bool {
+    use windows::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows::Win32::Security::{
+        GetTokenInformation, TokenElevation, TOKEN_ELEVATION, TOKEN_QUERY,
+    };
+    use windows::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
+
+    unsafe {
+        let mut token = HANDLE::default();
+        if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token).is_err() {
+            return false;
+        }
+        let mut elevation = TOKEN_ELEVATION::default();
+        let mut returned = 0u32;
+        let queried = GetTokenInformation(
+            token,
+            TokenElevation,
+            Some(&mut elevation as *mut TOKEN_ELEVATION as *mut std::ffi::c_void),
+            std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+            &mut returned,
+        );
+        let _ = CloseHandle(token);
+        queried.is_ok() && elevation.TokenIsElevated != 0
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn is_elevated() -> bool {
+    std::process::Command::new("id")
+        .arg("-u")
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim() == "0")
+        .unwrap_or(false)
+}
+
+// Relaunches this executable with `args` behind a UAC prompt and waits for it.
+// Declining the prompt is reported as an error.
+#[cfg(target_os = "windows")]
+pub fn run_elevated(args: &[&str]) -> Result<i32, String> {
+    let exe_path =
+        std::env::current_exe().map_err(|e| format!("Failed to locate executable: {}", e))?;
+    // Single-quoted PowerShell strings only need their quotes doubled
+    let quote = |s: &str| format!("'{}'", s.replace('\'', "''"));
+    let argument_list = args.iter().map(|a| quote(a)).collect::<Vec<_>>().join(",");
+    let script = format!(
+        "$p = Start-Process -FilePath {} -ArgumentList {} -Verb RunAs -Wait -PassThru -WindowStyle Hidden -ErrorAction Stop; exit $p.ExitCode",
+        quote(&exe_path.to_string_lossy()),
+        argument_list
+    );
+    let output = super::utils::hidden_command("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .output()
+        .map_err(|e| format!("Failed to start PowerShell: {}", e))?;
+    match output.status.code() {
+        Some(code) if output.stderr.is_empty() => Ok(code),
+        _ => Err(format!(
+            "Administrator prompt was declined or failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn run_elevated(_args: &[&str]) -> Result<i32, String> {
+    Err("Elevated tasks are only available on Windows; rerun the client with sudo".to_string())
+}
@@ -0,0 +1,62 @@
+use super::models::{
+    DailyDigest, GameUninstalled, GamesDelta, LauncherState, LogEvent, MetadataUpdate,
+    ScanProgress, Settings, UploadStatus,
+};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+// Every event the backend sends to the frontend, with its payload. The names
+// are the frontend contract; emit only through `emit` so they can't drift.
+pub enum AppEvent<'a> {
+    ScanProgress(ScanProgress),
+    GamesUpdated(GamesDelta),
+    GameUninstalled(&'a GameUninstalled),
+    MetadataUpdated(MetadataUpdate),
+    PeriodicUploadCompleted(&'a [UploadStatus]),
+    // Batched by `log_emitter`, in logging order
+    LogEvents(&'a [LogEvent]),
+    SettingsUpdated(&'a Settings),
+    ShowLogConsole,
+    DailyDigest(&'a DailyDigest),
+    LauncherStatus(LauncherState),
+}
+
+impl AppEvent<'_> {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AppEvent::ScanProgress(_) => "scan-progress",
+            AppEvent::GamesUpdated(_) => "games-updated",
+            AppEvent::GameUninstalled(_) => "game-uninstalled",
+            AppEvent::MetadataUpdated(_) => "metadata-updated",
+            AppEvent::PeriodicUploadCompleted(_) => "periodic-upload-completed",
+            AppEvent::LogEvents(_) => "log-events",
+            AppEvent::SettingsUpdated(_) => "settings-updated",
+            AppEvent::ShowLogConsole => "show-log-console",
+            AppEvent::DailyDigest(_) => "daily-digest",
+            AppEvent::LauncherStatus(_) => "launcher-status",
+        }
+    }
+}
+
+pub fn emit(app_handle: &AppHandle, event: AppEvent) {
+    let name = event.name();
+    let result = match &event {
+        AppEvent::ScanProgress(progress) => send(app_handle, name, progress),
+        AppEvent::GamesUpdated(delta) => send(app_handle, name, delta),
+        AppEvent::GameUninstalled(uninstalled) => send(app_handle, name, uninstalled),
+        AppEvent::MetadataUpdated(update) => send(app_handle, name, update),
+        AppEvent::PeriodicUploadCompleted(results) => send(app_handle, name, results),
+        AppEvent::LogEvents(logs) => send(app_handle, name, logs),
+        AppEvent::SettingsUpdated(settings) => send(app_handle, name, settings),
+        AppEvent::ShowLogConsole => send(app_handle, name, ()),
+        AppEvent::DailyDigest(digest) => send(app_handle, name, digest),
+        AppEvent::LauncherStatus(state) => send(app_handle, name, state),
+    };
+    if let Err(e) = result {
+        eprintln!("Failed to emit {}: {}", name, e);
+    }
+}
+
+fn send(app_handle: &AppHandle, name: &str, payload: impl Serialize + Clone) -> tauri::Result<()> {
+    app_handle.emit(name, payload)
+}
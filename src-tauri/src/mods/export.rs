@@ -0,0 +1,156 @@
+use super::models::{BulkExportSummary, GameInfo};
+use super::utils::{get_egstore_manifest_path, read_stable_file};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+// Files that make up one installed build, named as the launcher names them
+struct ManifestFiles {
+    item: (String, Vec<u8>),
+    manifest: (String, Vec<u8>),
+    mancpn: Option<(String, Vec<u8>)>,
+}
+
+impl ManifestFiles {
+    // The files followed by metadata.json, in the order they are written
+    fn into_entries(self, metadata: Vec<u8>) -> impl Iterator<Item = (String, Vec<u8>)> {
+        [Some(self.item), Some(self.manifest), self.mancpn]
+            .into_iter()
+            .flatten()
+            .chain(std::iter::once(("metadata.json".to_string(), metadata)))
+    }
+}
+
+fn read_manifest_files(game: &GameInfo) -> Result<ManifestFiles, String> {
+    let item_name = format!("{}.item", game.installation_guid);
+    // After an uninstall the `.item` is gone; the copy captured at scan time stands in
+    let item_bytes = match read_stable_file(&crate::get_item_path(game)) {
+        Ok(bytes) => bytes,
+        Err(_) if !game.item_content.is_empty() => game.item_content.clone().into_bytes(),
+        Err(e) => return Err(format!("Failed to read .item file: {}", e)),
+    };
+
+    let manifest_path = get_egstore_manifest_path(&game.install_location, &game.installation_guid);
+    let manifest_bytes = read_stable_file(&manifest_path)
+        .map_err(|e| format!("Failed to read {}: {}", manifest_path.display(), e))?;
+
+    // Not every install has one
+    let mancpn_path = manifest_path.with_extension("mancpn");
+    let mancpn = fs::read(&mancpn_path)
+        .ok()
+        .map(|bytes| (format!("{}.mancpn", game.installation_guid), bytes));
+
+    Ok(ManifestFiles {
+        item: (item_name, item_bytes),
+        manifest: (
+            format!("{}.manifest", game.installation_guid),
+            manifest_bytes,
+        ),
+        mancpn,
+    })
+}
+
+fn bundle_metadata(game: &GameInfo) -> serde_json::Value {
+    serde_json::json!({
+        "app_name": game.app_name,
+        "display_name": game.display_name,
+        "version": game.version,
+        "environment": game.environment,
+        "catalog_namespace": game.catalog_namespace,
+        "catalog_item_id": game.catalog_item_id,
+        "installation_guid": game.installation_guid,
+        "manifest_hash": game.manifest_hash,
+        "exported_at": chrono::Utc::now().to_rfc3339(),
+        "client_version": env!("CARGO_PKG_VERSION"),
+        "os": std::env::consts::OS,
+    })
+}
+
+// Zips the game's `.item`, `.manifest` and (if present) `.mancpn` together
+// with a metadata.json describing the build
+pub fn export_manifest_bundle(game: &GameInfo, destination: &Path) -> Result<(), String> {
+    let files = read_manifest_files(game)?;
+    let metadata = serde_json::to_vec_pretty(&bundle_metadata(game))
+        .map_err(|e| format!("Failed to serialize metadata.json: {}", e))?;
+
+    let file = fs::File::create(destination)
+        .map_err(|e| format!("Failed to create {}: {}", destination.display(), e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    let entries = files.into_entries(metadata);
+    for (name, bytes) in entries {
+        zip.start_file(name.as_str(), options)
+            .map_err(|e| format!("Failed to add {} to bundle: {}", name, e))?;
+        zip.write_all(&bytes)
+            .map_err(|e| format!("Failed to write {} to bundle: {}", name, e))?;
+    }
+
+    zip.finish()
+        .map_err(|e| format!("Failed to finalize manifest bundle: {}", e))?;
+    Ok(())
+}
+
+// Copies every game's manifest files into `<dest_dir>/egdata-manifests-<date>/<app_name>/`,
+// one folder per game, as a local backup independent of uploads
+pub fn export_all_manifests(
+    games: &[GameInfo],
+    dest_dir: &Path,
+) -> Result<BulkExportSummary, String> {
+    let folder = dest_dir.join(format!(
+        "egdata-manifests-{}",
+        chrono::Local::now().format("%Y-%m-%d_%H%M%S")
+    ));
+    fs::create_dir_all(&folder)
+        .map_err(|e| format!("Failed to create {}: {}", folder.display(), e))?;
+
+    let mut summary = BulkExportSummary {
+        path: folder.display().to_string(),
+        ..Default::default()
+    };
+
+    for game in games {
+        match write_game_folder(game, &folder.join(sanitize_file_name(&game.app_name))) {
+            Ok(()) => summary.exported.push(game.app_name.clone()),
+            Err(e) => summary.failed.push(format!("{}: {}", game.display_name, e)),
+        }
+    }
+
+    Ok(summary)
+}
+
+fn write_game_folder(game: &GameInfo, game_dir: &Path) -> Result<(), String> {
+    let files = read_manifest_files(game)?;
+    let metadata = serde_json::to_vec_pretty(&bundle_metadata(game))
+        .map_err(|e| format!("Failed to serialize metadata.json: {}", e))?;
+
+    fs::create_dir_all(game_dir)
+        .map_err(|e| format!("Failed to create {}: {}", game_dir.display(), e))?;
+    let entries = files.into_entries(metadata);
+    for (name, bytes) in entries {
+        fs::write(game_dir.join(&name), bytes)
+            .map_err(|e| format!("Failed to write {}: {}", name, e))?;
+    }
+    Ok(())
+}
+
+// App names are launcher ids, but keep them safe as directory names regardless
+fn sanitize_file_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    // "", "." and ".." would resolve to the export folder or its parent
+    if sanitized.chars().all(|c| c == '.') {
+        "_".repeat(sanitized.len().max(1))
+    } else {
+        sanitized
+    }
+}
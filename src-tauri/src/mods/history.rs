@@ -0,0 +1,135 @@
+use super::activity;
+use super::models::{GameInfo, LibraryChange, LibraryHistory, LibrarySnapshot, SnapshotEntry};
+use super::utils::{read_json, write_json};
+use std::collections::HashMap;
+
+const HISTORY_FILE: &str = "library_history.json";
+// Oldest snapshots are dropped beyond this; each one only holds a few fields per game
+const MAX_SNAPSHOTS: usize = 200;
+
+pub fn load_snapshots() -> Vec<LibrarySnapshot> {
+    read_json(HISTORY_FILE).unwrap_or_default()
+}
+
+fn save_snapshots(snapshots: &[LibrarySnapshot]) {
+    write_json(HISTORY_FILE, snapshots, "library history");
+}
+
+fn snapshot_entries(games: &[GameInfo]) -> Vec<SnapshotEntry> {
+    let mut entries = games
+        .iter()
+        .map(|g| SnapshotEntry {
+            app_name: g.app_name.clone(),
+            display_name: g.display_name.clone(),
+            catalog_item_id: g.catalog_item_id.clone(),
+            version: g.version.clone(),
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by(|a, b| a.app_name.cmp(&b.app_name));
+    entries
+}
+
+// Appends a snapshot when the set of installed games or their versions differs
+// from the last one recorded. Returns the changes relative to that snapshot.
+pub fn record_snapshot(games: &[GameInfo]) -> Vec<LibraryChange> {
+    let entries = snapshot_entries(games);
+    let mut snapshots = load_snapshots();
+    if snapshots.last().is_some_and(|last| last.games == entries) {
+        return Vec::new();
+    }
+
+    let snapshot = LibrarySnapshot {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        games: entries,
+    };
+    let changes = snapshots
+        .last()
+        .map(|previous| diff_snapshots(previous, &snapshot))
+        .unwrap_or_default();
+    for change in &changes {
+        let message = match change.kind.as_str() {
+            "updated" => format!(
+                "{} updated from {} to {}",
+                change.display_name,
+                change.from_version.as_deref().unwrap_or("?"),
+                change.to_version.as_deref().unwrap_or("?")
+            ),
+            kind => format!("{} {}", change.display_name, kind),
+        };
+        activity::record(&change.kind, &message, Some(&change.app_name));
+    }
+    snapshots.push(snapshot);
+    if snapshots.len() > MAX_SNAPSHOTS {
+        let excess = snapshots.len() - MAX_SNAPSHOTS;
+        snapshots.drain(..excess);
+    }
+    save_snapshots(&snapshots);
+    changes
+}
+
+fn diff_snapshots(previous: &LibrarySnapshot, current: &LibrarySnapshot) -> Vec<LibraryChange> {
+    let before: HashMap<&str, &SnapshotEntry> = previous
+        .games
+        .iter()
+        .map(|e| (e.app_name.as_str(), e))
+        .collect();
+    let after: HashMap<&str, &SnapshotEntry> = current
+        .games
+        .iter()
+        .map(|e| (e.app_name.as_str(), e))
+        .collect();
+
+    let change =
+        |kind: &str, entry: &SnapshotEntry, from: Option<&str>, to: Option<&str>| LibraryChange {
+            timestamp: current.timestamp.clone(),
+            kind: kind.to_string(),
+            app_name: entry.app_name.clone(),
+            display_name: entry.display_name.clone(),
+            from_version: from.map(str::to_string),
+            to_version: to.map(str::to_string),
+        };
+
+    let mut changes = Vec::new();
+    for entry in &current.games {
+        match before.get(entry.app_name.as_str()) {
+            None => changes.push(change("installed", entry, None, Some(&entry.version))),
+            Some(old) if old.version != entry.version => changes.push(change(
+                "updated",
+                entry,
+                Some(&old.version),
+                Some(&entry.version),
+            )),
+            Some(_) => {}
+        }
+    }
+    for entry in &previous.games {
+        if !after.contains_key(entry.app_name.as_str()) {
+            changes.push(change("uninstalled", entry, Some(&entry.version), None));
+        }
+    }
+    changes
+}
+
+// Installs, uninstalls and version bumps between consecutive snapshots, newest first.
+// The first snapshot is the baseline, so games present then aren't counted as installs.
+pub fn get_library_history() -> LibraryHistory {
+    let snapshots = load_snapshots();
+    let mut history = LibraryHistory {
+        first_snapshot_at: snapshots.first().map(|s| s.timestamp.clone()),
+        ..Default::default()
+    };
+
+    for pair in snapshots.windows(2) {
+        for change in diff_snapshots(&pair[0], &pair[1]) {
+            match change.kind.as_str() {
+                "installed" => history.installs += 1,
+                "uninstalled" => history.uninstalls += 1,
+                _ => history.updates += 1,
+            }
+            history.changes.push(change);
+        }
+    }
+
+    history.changes.reverse();
+    history
+}
@@ -0,0 +1,17 @@
+// Coarse description of the machine an upload came from, sent only when
+// `share_host_details` is on. Nothing here identifies the user or device.
+#[derive(Debug, Clone)]
+pub struct HostDetails {
+    pub os: String,
+    // e.g. "10.0.22631" or "14.5"; "Unknown" when the OS doesn't say
+    pub os_version: String,
+    pub arch: String,
+}
+
+pub fn host_details() -> HostDetails {
+    HostDetails {
+        os: std::env::consts::OS.to_string(),
+        os_version: os_info::get().version().to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+    }
+}
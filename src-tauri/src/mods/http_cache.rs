@@ -0,0 +1,232 @@
+use super::utils::get_app_data_path;
+use reqwest::header::{
+    ACCEPT, CACHE_CONTROL, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, SystemTime};
+
+const HTTP_CACHE_DIR: &str = "http_cache";
+
+// Same limits as the in-memory metadata cache (`metadata_cache_max_entries`
+// and `metadata_cache_max_bytes`); 0 means unlimited
+static MAX_ENTRIES: AtomicUsize = AtomicUsize::new(0);
+static MAX_BYTES: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    // Unix seconds until which the body can be used without revalidating
+    fresh_until: i64,
+    body: String,
+}
+
+fn get_cache_dir() -> PathBuf {
+    get_app_data_path().join(HTTP_CACHE_DIR)
+}
+
+fn entry_path(url: &str) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    url.hash(&mut hasher);
+    get_cache_dir().join(format!("{:016x}.json", hasher.finish()))
+}
+
+fn load_entry(url: &str) -> Option<CacheEntry> {
+    fs::read_to_string(entry_path(url))
+        .ok()
+        .and_then(|contents| serde_json::from_str::<CacheEntry>(&contents).ok())
+        // Guard against hash collisions
+        .filter(|entry| entry.url == url)
+}
+
+// Writes the entry and trims the cache on the blocking pool; trimming lists
+// and stats every cached file
+fn save_entry(entry: CacheEntry) {
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Err(e) = fs::create_dir_all(get_cache_dir()) {
+            eprintln!("Failed to create HTTP cache directory: {}", e);
+            return;
+        }
+        if let Ok(json) = serde_json::to_string(&entry) {
+            if let Err(e) = fs::write(entry_path(&entry.url), json) {
+                eprintln!("Failed to write HTTP cache entry: {}", e);
+            }
+        }
+        enforce_limits();
+    });
+}
+
+pub fn set_limits(max_entries: usize, max_bytes: u64) {
+    MAX_ENTRIES.store(max_entries, Ordering::Relaxed);
+    MAX_BYTES.store(max_bytes, Ordering::Relaxed);
+    tauri::async_runtime::spawn_blocking(enforce_limits);
+}
+
+// Deletes the least recently written entries until the cache fits its limits
+fn enforce_limits() {
+    let max_entries = MAX_ENTRIES.load(Ordering::Relaxed);
+    let max_bytes = MAX_BYTES.load(Ordering::Relaxed);
+    if max_entries == 0 && max_bytes == 0 {
+        return;
+    }
+    let Ok(dir) = fs::read_dir(get_cache_dir()) else {
+        return;
+    };
+
+    let mut files = dir
+        .flatten()
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            metadata.is_file().then(|| {
+                let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                (modified, metadata.len(), entry.path())
+            })
+        })
+        .collect::<Vec<_>>();
+    files.sort_by_key(|(modified, _, _)| *modified);
+
+    let mut count = files.len();
+    let mut total_bytes: u64 = files.iter().map(|(_, size, _)| size).sum();
+    for (_, size, path) in files {
+        let too_many = max_entries > 0 && count > max_entries;
+        let too_big = max_bytes > 0 && total_bytes > max_bytes;
+        if !too_many && !too_big {
+            break;
+        }
+        if fs::remove_file(&path).is_ok() {
+            count -= 1;
+            total_bytes -= size;
+        }
+    }
+}
+
+pub fn clear() -> Result<(), String> {
+    match fs::remove_dir_all(get_cache_dir()) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("Failed to clear HTTP cache: {}", e)),
+    }
+}
+
+// None when the response must not be stored at all
+fn freshness_lifetime(headers: &reqwest::header::HeaderMap) -> Option<i64> {
+    let cache_control = headers
+        .get(CACHE_CONTROL)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    let mut max_age = 0;
+    for directive in cache_control.split(',').map(str::trim) {
+        if directive == "no-store" {
+            return None;
+        }
+        if directive == "no-cache" {
+            max_age = 0;
+            break;
+        }
+        if let Some(value) = directive.strip_prefix("max-age=") {
+            max_age = value.trim_matches('"').parse().unwrap_or(0);
+        }
+    }
+    Some(max_age)
+}
+
+fn header_string(
+    headers: &reqwest::header::HeaderMap,
+    name: reqwest::header::HeaderName,
+) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+}
+
+// GET through the on-disk cache. Honors `Cache-Control` max-age/no-cache/no-store,
+// revalidates with ETag/Last-Modified, and serves a stale body if the network fails.
+// `accept` is sent as the Accept header when given. With `revalidate`, a fresh
+// entry is still checked with the server, for explicit refreshes.
+pub async fn get(
+    client: &reqwest::Client,
+    url: &str,
+    accept: Option<&str>,
+    timeout: Duration,
+    revalidate: bool,
+) -> Result<String, String> {
+    let now = chrono::Utc::now().timestamp();
+    let cached = load_entry(url);
+    if let Some(entry) = cached
+        .as_ref()
+        .filter(|e| !revalidate && e.fresh_until > now)
+    {
+        return Ok(entry.body.clone());
+    }
+
+    let mut request = client.get(url).timeout(timeout);
+    if let Some(accept) = accept {
+        request = request.header(ACCEPT, accept);
+    }
+    if let Some(entry) = &cached {
+        if let Some(etag) = &entry.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &entry.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+
+    let response = match request.send().await {
+        Ok(response) => response,
+        Err(e) => {
+            return match cached {
+                Some(entry) => Ok(entry.body),
+                None => Err(format!("Request failed: {}", e)),
+            };
+        }
+    };
+
+    let status = response.status();
+    let headers = response.headers().clone();
+
+    if status == reqwest::StatusCode::NOT_MODIFIED {
+        if let Some(mut entry) = cached {
+            if let Some(lifetime) = freshness_lifetime(&headers) {
+                entry.fresh_until = now + lifetime;
+                let body = entry.body.clone();
+                save_entry(entry);
+                return Ok(body);
+            }
+            return Ok(entry.body);
+        }
+    }
+
+    if !status.is_success() {
+        return Err(format!("HTTP {}", status));
+    }
+
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read response: {}", e))?;
+    if let Some(lifetime) = freshness_lifetime(&headers) {
+        let etag = header_string(&headers, ETAG);
+        let last_modified = header_string(&headers, LAST_MODIFIED);
+        // Nothing to revalidate with and already stale: storing it would be useless
+        if lifetime > 0 || etag.is_some() || last_modified.is_some() {
+            save_entry(CacheEntry {
+                url: url.to_string(),
+                etag,
+                last_modified,
+                fresh_until: now + lifetime,
+                body: body.clone(),
+            });
+        }
+    }
+    Ok(body)
+}
@@ -0,0 +1,146 @@
+use super::api::HttpEgdataApi;
+use super::models::{ImportSummary, PathProbe, Settings, UploadStatus};
+use super::utils::{egstore_manifest_path, emit_log, probe_path, PATH_PROBE_TIMEOUT};
+use crate::{parse_item_file, upload_manifest_files};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+// Guards against pathological trees (and link loops, which are not followed)
+const MAX_IMPORT_DEPTH: usize = 16;
+
+#[derive(Default)]
+struct ImportCandidates {
+    items: Vec<PathBuf>,
+    // `<InstallationGuid>.manifest` file name (lowercase) -> path
+    manifests: HashMap<String, PathBuf>,
+}
+
+fn collect_candidates(dir: &Path, depth: usize, candidates: &mut ImportCandidates) {
+    if depth > MAX_IMPORT_DEPTH {
+        return;
+    }
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("Failed to read {}: {}", dir.display(), e);
+            return;
+        }
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+
+        if file_type.is_dir() {
+            collect_candidates(&path, depth + 1, candidates);
+        } else if file_type.is_file() {
+            match path.extension().and_then(|s| s.to_str()) {
+                Some("item") => candidates.items.push(path),
+                Some("manifest") => {
+                    if let Some(name) = path.file_name().and_then(|s| s.to_str()) {
+                        candidates
+                            .manifests
+                            .insert(name.to_lowercase(), path.clone());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+// Finds `.item` files anywhere under `dir` (e.g. a backup of another PC's
+// Manifests folder), pairs each with its `.manifest` and uploads the pair
+pub async fn import_manifests(
+    app_handle: &AppHandle,
+    dir: &Path,
+    settings: &Settings,
+) -> Result<ImportSummary, String> {
+    if !dir.is_dir() {
+        return Err(format!("{} is not a directory", dir.display()));
+    }
+
+    let root = dir.to_path_buf();
+    let candidates = tauri::async_runtime::spawn_blocking(move || {
+        let mut candidates = ImportCandidates::default();
+        collect_candidates(&root, 0, &mut candidates);
+        candidates
+    })
+    .await
+    .map_err(|e| format!("Failed to walk {}: {}", dir.display(), e))?;
+
+    emit_log(
+        app_handle,
+        "INFO",
+        &format!(
+            "Found {} .item and {} .manifest file(s) in {}",
+            candidates.items.len(),
+            candidates.manifests.len(),
+            dir.display()
+        ),
+    );
+
+    let mut summary = ImportSummary::default();
+    for item_path in &candidates.items {
+        let parse_path = item_path.clone();
+        let parsed = tauri::async_runtime::spawn_blocking(move || parse_item_file(&parse_path))
+            .await
+            .unwrap_or_else(|e| Err(format!("Parser task failed: {}", e)));
+        let game = match parsed {
+            Ok(game) => game,
+            Err(e) => {
+                summary
+                    .invalid_items
+                    .push(format!("{}: {}", item_path.display(), e));
+                continue;
+            }
+        };
+
+        let manifest_name = format!("{}.manifest", game.installation_guid).to_lowercase();
+        let manifest_path = match candidates.manifests.get(&manifest_name) {
+            Some(found) => Some(found.clone()),
+            None => {
+                let installed =
+                    egstore_manifest_path(&game.install_location, &game.installation_guid).await;
+                (probe_path(installed.clone(), PATH_PROBE_TIMEOUT).await == PathProbe::Available)
+                    .then_some(installed)
+            }
+        };
+
+        let Some(manifest_path) = manifest_path else {
+            summary.results.push(UploadStatus {
+                status: "failed".to_string(),
+                message: Some(format!("No .manifest found for {}", game.display_name)),
+                manifest_hash: Some(game.manifest_hash.clone()),
+                endpoint: None,
+                error_code: None,
+                metrics: None,
+            });
+            continue;
+        };
+
+        let status = upload_manifest_files(
+            &HttpEgdataApi,
+            &game,
+            &game.item_content,
+            manifest_path,
+            settings,
+        )
+        .await
+        .unwrap_or_else(|e| UploadStatus {
+            status: "failed".to_string(),
+            message: Some(e),
+            manifest_hash: Some(game.manifest_hash.clone()),
+            endpoint: None,
+            error_code: None,
+            metrics: None,
+        });
+        summary.results.push(status);
+    }
+
+    Ok(summary)
+}
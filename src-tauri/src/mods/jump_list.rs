@@ -0,0 +1,142 @@
+use super::commands;
+use super::notifications;
+use super::state::{GameStore, MetadataCache, ScanCancelFlag, SettingsState, UninstalledGames};
+use super::utils::emit_log;
+use tauri::{AppHandle, Manager};
+
+// Jump List entries relaunch the executable with `--task <id>`; a running
+// instance receives those args through the single-instance plugin
+pub const TASK_ARG: &str = "--task";
+
+struct JumpTask {
+    id: &'static str,
+    #[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+    label: &'static str,
+}
+
+const TASKS: [JumpTask; 3] = [
+    JumpTask {
+        id: "scan",
+        label: "Scan now",
+    },
+    JumpTask {
+        id: "upload-all",
+        label: "Upload all",
+    },
+    JumpTask {
+        id: "open-logs",
+        label: "Open logs",
+    },
+];
+
+pub fn task_from_args(args: &[String]) -> Option<&str> {
+    let position = args.iter().position(|arg| arg == TASK_ARG)?;
+    let task = args.get(position + 1)?.as_str();
+    TASKS.iter().any(|t| t.id == task).then_some(task)
+}
+
+pub fn run_task(app_handle: &AppHandle, task: &str) {
+    match task {
+        "scan" => {
+            let app_handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                let result = commands::scan_games_now(
+                    app_handle.clone(),
+                    app_handle.state::<GameStore>(),
+                    app_handle.state::<MetadataCache>(),
+                    app_handle.state::<SettingsState>(),
+                    app_handle.state::<ScanCancelFlag>(),
+                    app_handle.state::<UninstalledGames>(),
+                )
+                .await;
+                if let Err(e) = result {
+                    emit_log(&app_handle, "ERROR", &format!("Scan failed: {}", e));
+                }
+            });
+        }
+        "upload-all" => {
+            let app_handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                notifications::upload_all_and_report(&app_handle).await
+            });
+        }
+        "open-logs" => notifications::dispatch_action(app_handle, Some("view-log")),
+        _ => {}
+    }
+}
+
+// Registers the tasks on the taskbar icon. COM gets its own thread so it never
+// touches the apartment the event loop runs in.
+#[cfg(target_os = "windows")]
+pub fn register() {
+    let exe = match std::env::current_exe() {
+        Ok(exe) => exe,
+        Err(e) => {
+            eprintln!("Failed to resolve executable for Jump List: {}", e);
+            return;
+        }
+    };
+    std::thread::spawn(move || {
+        if let Err(e) = register_tasks(&exe) {
+            eprintln!("Failed to register Jump List: {}", e);
+        }
+    });
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn register() {}
+
+#[cfg(target_os = "windows")]
+fn register_tasks(exe: &std::path::Path) -> windows::core::Result<()> {
+    use windows::core::{Interface, HSTRING};
+    use windows::Win32::Storage::EnhancedStorage::PKEY_Title;
+    use windows::Win32::System::Com::StructuredStorage::{PropVariantClear, PROPVARIANT};
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED,
+    };
+    use windows::Win32::System::Variant::VT_LPWSTR;
+    use windows::Win32::UI::Shell::Common::{IObjectArray, IObjectCollection};
+    use windows::Win32::UI::Shell::PropertiesSystem::IPropertyStore;
+    use windows::Win32::UI::Shell::{
+        DestinationList, EnumerableObjectCollection, ICustomDestinationList, IShellLinkW,
+        SHStrDupW, ShellLink,
+    };
+
+    let exe = HSTRING::from(exe.as_os_str());
+
+    unsafe {
+        CoInitializeEx(None, COINIT_APARTMENTTHREADED).ok()?;
+
+        let list: ICustomDestinationList =
+            CoCreateInstance(&DestinationList, None, CLSCTX_INPROC_SERVER)?;
+        let mut max_slots = 0u32;
+        let _removed: IObjectArray = list.BeginList(&mut max_slots)?;
+
+        let collection: IObjectCollection =
+            CoCreateInstance(&EnumerableObjectCollection, None, CLSCTX_INPROC_SERVER)?;
+        for task in &TASKS {
+            let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+            link.SetPath(&exe)?;
+            link.SetArguments(&HSTRING::from(format!("{} {}", TASK_ARG, task.id)))?;
+            link.SetIconLocation(&exe, 0)?;
+            link.SetDescription(&HSTRING::from(task.label))?;
+
+            // Jump List tasks take their visible name from the title property
+            let store: IPropertyStore = link.cast()?;
+            // A VT_LPWSTR PROPVARIANT owns a COM-allocated copy of the string
+            let mut title = PROPVARIANT::default();
+            (*title.Anonymous.Anonymous).vt = VT_LPWSTR;
+            (*title.Anonymous.Anonymous).Anonymous.pwszVal = SHStrDupW(&HSTRING::from(task.label))?;
+            let stored = store.SetValue(&PKEY_Title, &title);
+            PropVariantClear(&mut title)?;
+            stored?;
+            store.Commit()?;
+
+            collection.AddObject(&link)?;
+        }
+
+        list.AddUserTasks(&collection.cast::<IObjectArray>()?)?;
+        list.CommitList()?;
+    }
+    Ok(())
+}
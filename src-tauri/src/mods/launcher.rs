@@ -0,0 +1,192 @@
+use super::models::{GameInfo, LauncherConsistencyReport, LauncherInstallation, LauncherState};
+use super::utils::decode_text_file;
+use once_cell::sync::OnceCell;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+#[derive(Debug, Deserialize)]
+struct LauncherInstalledFile {
+    #[serde(rename = "InstallationList", default)]
+    installation_list: Vec<LauncherInstallation>,
+}
+
+pub fn get_launcher_data_path() -> PathBuf {
+    #[cfg(target_os = "windows")]
+    {
+        PathBuf::from(r"C:\ProgramData\Epic\UnrealEngineLauncher")
+    }
+    #[cfg(target_os = "macos")]
+    {
+        let mut path = dirs::home_dir().unwrap_or_else(|| PathBuf::from("~"));
+        path.push("Library/Application Support/Epic/UnrealEngineLauncher");
+        path
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        PathBuf::from("") // Unsupported
+    }
+}
+
+pub fn get_launcher_installed_path() -> PathBuf {
+    get_launcher_data_path().join("LauncherInstalled.dat")
+}
+
+pub fn load_launcher_installations() -> Result<Vec<LauncherInstallation>, String> {
+    let path = get_launcher_installed_path();
+    let bytes = fs::read(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let content = decode_text_file(&path, &bytes)?;
+    let parsed: LauncherInstalledFile = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse LauncherInstalled.dat: {}", e))?;
+    Ok(parsed.installation_list)
+}
+
+fn normalize_location(location: &str) -> String {
+    location
+        .replace('\\', "/")
+        .trim_end_matches('/')
+        .to_lowercase()
+}
+
+// Compares the scanned `.item` manifests against the launcher's own install list
+pub fn reconcile_with_launcher(
+    games: &[GameInfo],
+    installations: &[LauncherInstallation],
+) -> LauncherConsistencyReport {
+    let installed_by_app: HashMap<&str, &LauncherInstallation> = installations
+        .iter()
+        .map(|i| (i.app_name.as_str(), i))
+        .collect();
+    let scanned_by_app: HashMap<&str, &GameInfo> =
+        games.iter().map(|g| (g.app_name.as_str(), g)).collect();
+
+    let mut report = LauncherConsistencyReport::default();
+
+    for game in games {
+        match installed_by_app.get(game.app_name.as_str()) {
+            None => report.orphaned_manifests.push(game.app_name.clone()),
+            Some(installation) => {
+                if normalize_location(&installation.install_location)
+                    != normalize_location(&game.install_location)
+                {
+                    report.location_mismatches.push(game.app_name.clone());
+                }
+            }
+        }
+    }
+
+    for installation in installations {
+        if !scanned_by_app.contains_key(installation.app_name.as_str()) {
+            report.untracked_installs.push(installation.clone());
+        }
+    }
+
+    report.orphaned_manifests.sort();
+    report.location_mismatches.sort();
+    report
+}
+
+// Evidence of a launcher install beyond its manifests folder
+pub fn is_launcher_installed() -> bool {
+    #[cfg(target_os = "windows")]
+    let app_path = PathBuf::from(r"C:\Program Files (x86)\Epic Games\Launcher");
+    #[cfg(target_os = "macos")]
+    let app_path = PathBuf::from("/Applications/Epic Games Launcher.app");
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let app_path = PathBuf::new();
+
+    (!app_path.as_os_str().is_empty() && app_path.exists())
+        || get_launcher_installed_path().exists()
+}
+
+static LAUNCHER_STATE: Mutex<Option<LauncherState>> = Mutex::new(None);
+
+pub fn launcher_state() -> Option<LauncherState> {
+    *LAUNCHER_STATE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// Stores the state; true when it differs from the previous one
+pub fn set_launcher_state(state: LauncherState) -> bool {
+    let mut current = LAUNCHER_STATE.lock().unwrap_or_else(|e| e.into_inner());
+    current.replace(state) != Some(state)
+}
+
+static LAUNCHER_VERSION: OnceCell<Option<String>> = OnceCell::new();
+
+// Detected once per run; manifest format quirks correlate with launcher versions
+pub fn get_launcher_version() -> Option<String> {
+    LAUNCHER_VERSION
+        .get_or_init(detect_launcher_version)
+        .clone()
+}
+
+#[cfg(target_os = "windows")]
+fn detect_launcher_version() -> Option<String> {
+    let exe_path =
+        r"C:\Program Files (x86)\Epic Games\Launcher\Portal\Binaries\Win64\EpicGamesLauncher.exe";
+    if std::path::Path::new(exe_path).exists() {
+        let output = super::utils::hidden_command("powershell")
+            .args([
+                "-NoProfile",
+                "-Command",
+                &format!("(Get-Item '{}').VersionInfo.ProductVersion", exe_path),
+            ])
+            .output();
+        if let Ok(output) = output {
+            let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            if output.status.success() && !version.is_empty() {
+                return Some(version);
+            }
+        }
+    }
+
+    detect_version_from_log()
+}
+
+#[cfg(target_os = "macos")]
+fn detect_launcher_version() -> Option<String> {
+    let plist =
+        fs::read_to_string("/Applications/Epic Games Launcher.app/Contents/Info.plist").ok();
+    plist
+        .and_then(|content| {
+            let key_pos = content.find("<key>CFBundleShortVersionString</key>")?;
+            let rest = &content[key_pos..];
+            let start = rest.find("<string>")? + "<string>".len();
+            let end = rest[start..].find("</string>")?;
+            Some(rest[start..start + end].trim().to_string())
+        })
+        .or_else(detect_version_from_log)
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn detect_launcher_version() -> Option<String> {
+    None
+}
+
+// The launcher log starts with a line like
+// `LogInit: Build: ++Portal+Release-Live-CL-12345678`
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+fn detect_version_from_log() -> Option<String> {
+    #[cfg(target_os = "windows")]
+    let log_path =
+        dirs::data_local_dir()?.join("EpicGamesLauncher/Saved/Logs/EpicGamesLauncher.log");
+    #[cfg(target_os = "macos")]
+    let log_path = dirs::home_dir()?.join("Library/Logs/EpicGamesLauncher/EpicGamesLauncher.log");
+
+    let content = fs::read_to_string(log_path).ok()?;
+    content.lines().take(200).find_map(|line| {
+        line.split_once("LogInit: Build: ")
+            .map(|(_, build)| build.trim().to_string())
+    })
+}
+
+// `com.epicgames.launcher://` link to a game's library entry. The launcher
+// identifies apps as "namespace:catalogItemId:appName", URL-encoded.
+pub fn library_uri(game: &GameInfo) -> String {
+    format!(
+        "com.epicgames.launcher://apps/{}%3A{}%3A{}?action=show",
+        game.catalog_namespace, game.catalog_item_id, game.app_name
+    )
+}
@@ -0,0 +1,66 @@
+use super::models::{GameInfo, LedgerEntry, UploadStatus};
+use super::utils::{read_json, write_json};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+const LEDGER_FILE: &str = "upload_ledger.json";
+
+// Keyed by manifest hash
+pub fn load_ledger() -> HashMap<String, LedgerEntry> {
+    read_json(LEDGER_FILE).unwrap_or_default()
+}
+
+fn save_ledger(ledger: &HashMap<String, LedgerEntry>) {
+    write_json(LEDGER_FILE, ledger, "upload ledger");
+}
+
+// Uploads finish concurrently (batches, single uploads, retries, imports);
+// keep each load-modify-write of the ledger whole
+static LEDGER_LOCK: Mutex<()> = Mutex::new(());
+
+// Records a manifest the server accepted (or already had); failures are not kept
+pub fn record(game: &GameInfo, status: &UploadStatus) {
+    if status.status != "uploaded" && status.status != "already_uploaded" {
+        return;
+    }
+    let Some(manifest_hash) = status.manifest_hash.clone() else {
+        return;
+    };
+
+    let _guard = LEDGER_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut ledger = load_ledger();
+    ledger.insert(
+        manifest_hash.clone(),
+        LedgerEntry {
+            manifest_hash,
+            app_name: game.app_name.clone(),
+            version: game.version.clone(),
+            status: status.status.clone(),
+            endpoint: status.endpoint.clone(),
+            recorded_at: chrono::Utc::now().to_rfc3339(),
+            metrics: status.metrics,
+        },
+    );
+    save_ledger(&ledger);
+}
+
+// Adds entries for manifests the ledger doesn't have yet; ones recorded in the
+// meantime win. Returns how many were added.
+pub fn add_missing(entries: Vec<LedgerEntry>) -> usize {
+    if entries.is_empty() {
+        return 0;
+    }
+    let _guard = LEDGER_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut ledger = load_ledger();
+    let mut added = 0;
+    for entry in entries {
+        if !ledger.contains_key(&entry.manifest_hash) {
+            ledger.insert(entry.manifest_hash.clone(), entry);
+            added += 1;
+        }
+    }
+    if added > 0 {
+        save_ledger(&ledger);
+    }
+    added
+}
@@ -0,0 +1,226 @@
+use super::commands;
+use super::state::{GameStore, MetadataCache, ScanCancelFlag, SettingsState, UninstalledGames};
+use super::utils::{emit_log, get_app_data_path, write_private_file};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::fs;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+const TOKEN_FILE: &str = "local_api_token";
+// Requests carry no body we care about; anything bigger than this is rejected
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+// Clients that haven't sent their request headers by then are dropped
+const REQUEST_READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Shared secret clients send as `Authorization: Bearer <token>`, generated on first use
+static API_TOKEN: Lazy<String> = Lazy::new(|| {
+    let path = get_app_data_path().join(TOKEN_FILE);
+    if let Ok(existing) = fs::read_to_string(&path) {
+        let existing = existing.trim();
+        if !existing.is_empty() {
+            // Tokens written by earlier versions were world-readable
+            #[cfg(unix)]
+            let _ = fs::set_permissions(&path, std::os::unix::fs::PermissionsExt::from_mode(0o600));
+            return existing.to_string();
+        }
+    }
+
+    let token = uuid::Uuid::new_v4().simple().to_string();
+    let _ = fs::create_dir_all(get_app_data_path());
+    if let Err(e) = write_private_file(&path, token.as_bytes()) {
+        eprintln!("Failed to persist local API token: {}", e);
+    }
+    token
+});
+
+pub fn get_token() -> &'static str {
+    API_TOKEN.as_str()
+}
+
+struct Request {
+    method: String,
+    path: String,
+    authorization: Option<String>,
+}
+
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn json(status: u16, value: impl Serialize) -> Self {
+        Response {
+            status,
+            body: serde_json::to_string(&value).unwrap_or_else(|_| "null".to_string()),
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self::json(status, serde_json::json!({ "error": message }))
+    }
+}
+
+// Serves the automation API on 127.0.0.1 only. Endpoints:
+//   GET  /library  installed games
+//   POST /scan     scan now, returns the scanned games
+//   POST /upload   upload all manifests, returns the upload results
+pub async fn serve(app_handle: AppHandle, port: u16) {
+    let listener = match TcpListener::bind(("127.0.0.1", port)).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            emit_log(
+                &app_handle,
+                "ERROR",
+                &format!("Failed to start local API on port {}: {}", port, e),
+            );
+            return;
+        }
+    };
+    emit_log(
+        &app_handle,
+        "INFO",
+        &format!("Local API listening on http://127.0.0.1:{}", port),
+    );
+
+    loop {
+        let (stream, _) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                eprintln!("Local API failed to accept connection: {}", e);
+                continue;
+            }
+        };
+        let app_handle = app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = handle_connection(&app_handle, stream).await {
+                eprintln!("Local API connection error: {}", e);
+            }
+        });
+    }
+}
+
+async fn handle_connection(app_handle: &AppHandle, mut stream: TcpStream) -> std::io::Result<()> {
+    let request = tokio::time::timeout(REQUEST_READ_TIMEOUT, read_request(&mut stream))
+        .await
+        .map_err(|_| {
+            std::io::Error::new(std::io::ErrorKind::TimedOut, "request headers timed out")
+        })??;
+    let response = match request {
+        Some(request) => route(app_handle, request).await,
+        None => Response::error(400, "Malformed request"),
+    };
+
+    let reason = match response.status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        _ => "Internal Server Error",
+    };
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason,
+        response.body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(response.body.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+async fn read_request(stream: &mut TcpStream) -> std::io::Result<Option<Request>> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !buffer.windows(4).any(|w| w == b"\r\n\r\n") {
+        if buffer.len() > MAX_REQUEST_BYTES {
+            return Ok(None);
+        }
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(None);
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+
+    let text = String::from_utf8_lossy(&buffer);
+    let mut lines = text.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let (Some(method), Some(path)) = (request_line.next(), request_line.next()) else {
+        return Ok(None);
+    };
+
+    let authorization = lines
+        .take_while(|line| !line.is_empty())
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("authorization"))
+        .map(|(_, value)| value.trim().to_string());
+
+    Ok(Some(Request {
+        method: method.to_string(),
+        path: path.split('?').next().unwrap_or_default().to_string(),
+        authorization,
+    }))
+}
+
+// Compares without short-circuiting so response timing doesn't leak the token
+fn token_matches(provided: &str) -> bool {
+    let expected = get_token().as_bytes();
+    let provided = provided.as_bytes();
+    provided.len() == expected.len()
+        && provided
+            .iter()
+            .zip(expected)
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+async fn route(app_handle: &AppHandle, request: Request) -> Response {
+    let authorized = request
+        .authorization
+        .as_deref()
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(token_matches);
+    if !authorized {
+        return Response::error(401, "Missing or invalid token");
+    }
+
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/library") => {
+            match commands::get_installed_games(app_handle.state::<GameStore>()) {
+                Ok(games) => Response::json(200, games),
+                Err(e) => Response::error(500, &e),
+            }
+        }
+        ("POST", "/scan") => {
+            let result = commands::scan_games_now(
+                app_handle.clone(),
+                app_handle.state::<GameStore>(),
+                app_handle.state::<MetadataCache>(),
+                app_handle.state::<SettingsState>(),
+                app_handle.state::<ScanCancelFlag>(),
+                app_handle.state::<UninstalledGames>(),
+            )
+            .await;
+            match result {
+                Ok(games) => Response::json(200, games),
+                Err(e) => Response::error(500, &e),
+            }
+        }
+        ("POST", "/upload") => {
+            let result = commands::upload_all_manifests(
+                app_handle.state::<GameStore>(),
+                app_handle.state::<SettingsState>(),
+            )
+            .await;
+            match result {
+                Ok(results) => Response::json(200, results),
+                Err(e) => Response::error(500, &e),
+            }
+        }
+        _ => Response::error(404, "Not found"),
+    }
+}
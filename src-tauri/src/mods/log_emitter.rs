@@ -0,0 +1,48 @@
+use super::events::{self, AppEvent};
+use super::models::LogEvent;
+use once_cell::sync::Lazy;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::AppHandle;
+
+// A full scan logs once per game; batching keeps that to a few IPC events
+const FLUSH_INTERVAL: Duration = Duration::from_millis(250);
+const MAX_BATCH: usize = 50;
+
+static PENDING: Lazy<Mutex<Vec<LogEvent>>> = Lazy::new(|| Mutex::new(Vec::new()));
+static FLUSH_SCHEDULED: AtomicBool = AtomicBool::new(false);
+
+// Queues an event for the next `log-events` batch
+pub fn push(app_handle: &AppHandle, event: LogEvent) {
+    let full = match PENDING.lock() {
+        Ok(mut pending) => {
+            pending.push(event);
+            pending.len() >= MAX_BATCH
+        }
+        Err(_) => return,
+    };
+
+    if full {
+        flush(app_handle);
+    } else if !FLUSH_SCHEDULED.swap(true, Ordering::SeqCst) {
+        let app_handle = app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(FLUSH_INTERVAL).await;
+            FLUSH_SCHEDULED.store(false, Ordering::SeqCst);
+            flush(&app_handle);
+        });
+    }
+}
+
+// Emits while holding the lock so concurrent flushes can't reorder batches
+pub fn flush(app_handle: &AppHandle) {
+    let Ok(mut pending) = PENDING.lock() else {
+        return;
+    };
+    if pending.is_empty() {
+        return;
+    }
+    let batch = std::mem::take(&mut *pending);
+    events::emit(app_handle, AppEvent::LogEvents(&batch));
+}
@@ -1,4 +1,50 @@
+pub mod activity;
+pub mod api;
+pub mod arch;
+pub mod background_service;
+pub mod backup;
+pub mod chunks;
+pub mod cli;
 pub mod commands;
+pub mod companions;
+pub mod crash;
+pub mod diagnostics;
+pub mod digest;
+pub mod dlc;
+pub mod elevation;
+pub mod events;
+pub mod export;
+pub mod history;
+pub mod host;
+pub mod http_cache;
+pub mod import;
+pub mod jump_list;
+pub mod launcher;
+pub mod ledger;
+pub mod local_api;
+pub mod log_emitter;
 pub mod models;
+pub mod network;
+pub mod notifications;
+pub mod opportunities;
+pub mod pause;
+pub mod power;
+pub mod priority;
+pub mod profiles;
+pub mod reconcile;
+pub mod release_notes;
+pub mod relocate;
+pub mod retry_queue;
+pub mod schedule;
+pub mod settings_watch;
+pub mod sidecars;
+pub mod sightings;
+pub mod signing;
 pub mod state;
-pub mod utils;
\ No newline at end of file
+pub mod supervisor;
+pub mod telemetry;
+pub mod tray;
+pub mod utils;
+pub mod webhooks;
+pub mod window;
+pub mod wine;
@@ -1,50 +1,117 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
 
 // Logging utility for emitting log events to frontend
 #[derive(Debug, Clone, Serialize)]
 pub struct LogEvent {
     pub level: String,
     pub message: String,
+    // RFC 3339 UTC with milliseconds
     pub timestamp: String,
+    // Increases with every event in this run; orders events sharing a timestamp
+    pub seq: u64,
 }
 
+// Only the identifiers needed to locate and upload a build are required;
+// launcher versions differ in the rest, so those fall back to defaults
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EpicGameManifest {
-    #[serde(rename = "FormatVersion")]
+    #[serde(
+        rename = "FormatVersion",
+        default,
+        deserialize_with = "lenient_format_version"
+    )]
     pub format_version: i32,
-    #[serde(rename = "bIsIncompleteInstall")]
+    #[serde(rename = "bIsIncompleteInstall", default)]
     pub is_incomplete_install: bool,
-    #[serde(rename = "LaunchCommand")]
+    #[serde(rename = "LaunchCommand", default)]
     pub launch_command: String,
-    #[serde(rename = "LaunchExecutable")]
+    #[serde(rename = "LaunchExecutable", default)]
     pub launch_executable: String,
-    #[serde(rename = "ManifestLocation")]
+    #[serde(rename = "ManifestLocation", default)]
     pub manifest_location: String,
     #[serde(rename = "ManifestHash")]
     pub manifest_hash: String,
-    #[serde(rename = "bIsApplication")]
+    #[serde(rename = "bIsApplication", default)]
     pub is_application: bool,
-    #[serde(rename = "bIsExecutable")]
+    #[serde(rename = "bIsExecutable", default)]
     pub is_executable: bool,
-    #[serde(rename = "DisplayName")]
+    #[serde(rename = "DisplayName", default)]
     pub display_name: String,
     #[serde(rename = "InstallationGuid")]
     pub installation_guid: String,
     #[serde(rename = "InstallLocation")]
     pub install_location: String,
-    #[serde(rename = "InstallSize")]
+    #[serde(rename = "InstallSize", default)]
     pub install_size: u64,
-    #[serde(rename = "CatalogNamespace")]
+    #[serde(rename = "CatalogNamespace", default)]
     pub catalog_namespace: String,
     #[serde(rename = "CatalogItemId")]
     pub catalog_item_id: String,
     #[serde(rename = "AppName")]
     pub app_name: String,
-    #[serde(rename = "AppVersionString")]
+    #[serde(rename = "AppVersionString", default)]
     pub app_version_string: String,
+    // Release label such as "Live"; missing from some older manifests
+    #[serde(rename = "BuildLabel", default)]
+    pub build_label: String,
+    // The base game's AppName for DLC and addons; a game's own AppName otherwise
+    #[serde(rename = "MainGameAppName", default)]
+    pub main_game_app_name: String,
+    // Fields this version of the client doesn't know about
+    #[serde(flatten)]
+    pub unknown_fields: HashMap<String, serde_json::Value>,
+}
+
+// A FormatVersion written as a string or float (or null) shouldn't make the
+// whole `.item` unreadable; anything that isn't a number reads as 0
+fn lenient_format_version<'de, D>(deserializer: D) -> Result<i32, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let version = match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::Number(n) => n
+            .as_i64()
+            .or_else(|| n.as_f64().map(|f| f as i64))
+            .unwrap_or(0),
+        serde_json::Value::String(s) => s.trim().parse::<f64>().map(|f| f as i64).unwrap_or(0),
+        _ => 0,
+    };
+    Ok(i32::try_from(version).unwrap_or(0))
+}
+
+// A directory of launcher `.item` files, natively or inside a Wine prefix
+#[derive(Debug, Clone)]
+pub struct ManifestSource {
+    pub manifests_path: std::path::PathBuf,
+    pub wine_prefix: Option<std::path::PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathProbe {
+    Available,
+    Missing,
+    TimedOut,
+    // Exists, but this user may not read it (e.g. another user's profile)
+    AccessDenied,
 }
 
 #[derive(Debug, Clone, Serialize)]
+pub struct ScanProgress {
+    pub processed: usize,
+    pub total: usize,
+    pub current_game: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MetadataUpdate {
+    pub app_name: String,
+    pub catalog_item_id: String,
+    pub metadata: GameMetadata,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameInfo {
     pub display_name: String,
     pub app_name: String,
@@ -56,6 +123,50 @@ pub struct GameInfo {
     pub metadata: Option<GameMetadata>,
     pub installation_guid: String,
     pub manifest_hash: String,
+    // Build label from the `.item`, e.g. "Live"
+    #[serde(default)]
+    pub environment: String,
+    // False when the install location is missing or its drive didn't respond in time
+    pub available: bool,
+    // Set for games installed by a launcher running inside a Wine/Proton prefix
+    pub wine_prefix: Option<String>,
+    // Installed under a folder this user can't read, typically another
+    // user's profile on a shared PC; `available` is false then too
+    #[serde(default)]
+    pub access_denied: bool,
+    // False for tombstones kept after a game was uninstalled
+    #[serde(default)]
+    pub installed: bool,
+    #[serde(default)]
+    pub uninstalled_at: Option<String>,
+    // When the tombstone's manifest was sent by a final upload
+    #[serde(default)]
+    pub final_uploaded_at: Option<String>,
+    // The launcher's stale `InstallLocation` when the game was found moved;
+    // `install_location` then holds the folder it was found in
+    #[serde(default)]
+    pub moved_from: Option<String>,
+    // Decoded `.item` content; the launcher deletes the file on uninstall, so
+    // this is what a final upload falls back to
+    #[serde(skip)]
+    pub item_content: String,
+    // `FormatVersion` of the `.item`; 0 when the file didn't state one
+    #[serde(default)]
+    pub format_version: i32,
+    // `LaunchExecutable` from the `.item`, relative to the install location
+    #[serde(default)]
+    pub launch_executable: String,
+    // `MainGameAppName` from the `.item`; names the base game of a DLC
+    #[serde(default)]
+    pub main_game_app_name: String,
+    // DLC and addons of this game; only filled in the grouped list sent to
+    // the frontend, the game store itself stays flat
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<GameInfo>,
+    // CPU architecture of the launch executable ("x64", "x86", "arm64",
+    // "universal"); empty when unknown
+    #[serde(default)]
+    pub architecture: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -76,6 +187,49 @@ pub struct GameMetadata {
     pub developer: Option<String>,
     #[serde(rename = "developerId")]
     pub developer_id: Option<String>,
+    // Set when the entry was not served by the egdata API (outage fallback)
+    #[serde(default)]
+    pub fallback: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheStats {
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub hit_rate: f64,
+    pub disk_size_bytes: u64,
+}
+
+// Payload of the `game-uninstalled` event
+#[derive(Debug, Clone, Serialize)]
+pub struct GameUninstalled {
+    pub game: GameInfo,
+    // Whether the `.egstore` manifest survived, so a final upload is still possible
+    pub manifest_available: bool,
+}
+
+// Identifies a game the frontend should drop; its collection is keyed by
+// catalog item id
+#[derive(Debug, Clone, Serialize)]
+pub struct GameKey {
+    pub app_name: String,
+    pub catalog_item_id: String,
+}
+
+// Payload of the `games-updated` event: what changed since the last scan, by
+// app name. Details are fetched with `get_games`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GamesDelta {
+    pub added: Vec<String>,
+    pub changed: Vec<String>,
+    pub removed: Vec<GameKey>,
+}
+
+impl GamesDelta {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -84,13 +238,309 @@ pub struct EnrichedGameInfo {
     pub metadata: Option<GameMetadata>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Settings {
     pub concurrency: u32,
     pub upload_speed_limit: u32,
     pub allowed_environments: Vec<String>,
     pub upload_interval: u64,       // in minutes
     pub scan_interval_minutes: u64, // in minutes
+    // How often known catalog items' metadata is re-validated, independent of scans
+    pub metadata_refresh_interval_minutes: u64,
+    pub crash_reporting_enabled: bool,
+    // Limits for both the in-memory metadata cache and the on-disk HTTP cache
+    pub metadata_cache_max_entries: usize,
+    pub metadata_cache_max_bytes: u64,
+    pub epic_catalog_fallback: bool,
+    pub metadata_timeout_secs: u64,
+    pub upload_timeout_secs: u64,
+    pub upload_require_tls12: bool,
+    // PEM certificate that becomes the only trusted root for the upload endpoint
+    pub upload_pinned_cert_path: Option<String>,
+    pub telemetry_enabled: bool,
+    pub webhooks: Vec<WebhookConfig>,
+    // Token-protected automation API on 127.0.0.1; read at startup
+    pub local_api_enabled: bool,
+    pub local_api_port: u16,
+    // Run scans at reduced CPU/IO priority
+    pub background_priority: bool,
+    // Builds-API base URLs in failover order; empty uses the default endpoint
+    pub upload_endpoints: Vec<String>,
+    // Environment (build label) -> builds-API base URL, overriding `upload_endpoints`
+    pub environment_endpoints: HashMap<String, String>,
+    // macOS: clicking the menu bar icon opens a compact popover instead of the main window
+    pub tray_popover: bool,
+    // App names whose extra `.egstore` files are bundled into uploads (opt-in)
+    pub sidecar_upload_games: Vec<String>,
+    pub profiles: Vec<SettingsProfile>,
+    // Name of the profile last switched to
+    pub active_profile: Option<String>,
+    // Off parks periodic uploads without touching `upload_interval`; manual
+    // uploads still work
+    pub auto_upload_enabled: bool,
+    // Off leaves scanning to "Scan now": no startup, periodic or change-triggered scans
+    pub auto_scan_enabled: bool,
+    // One summary notification a day instead of one per failed upload cycle
+    pub daily_digest_enabled: bool,
+    // Send OS, OS version and CPU architecture with uploads
+    pub share_host_details: bool,
+    // Upload new installs and new versions as soon as a scan finds them
+    // instead of at the next periodic upload
+    pub upload_on_detect: bool,
+    // Linux: add an XDG autostart entry (or ask the Flatpak portal) at startup
+    pub linux_autostart: bool,
+}
+
+// Named bundle of scheduling settings, e.g. "Home fiber" vs "Mobile hotspot";
+// switching copies its values over the live settings. Only settings the
+// client acts on belong here.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsProfile {
+    pub name: String,
+    pub scan_interval_minutes: u64,
+    pub upload_interval: u64,
+}
+
+// Events a webhook can subscribe to
+pub const WEBHOOK_EVENTS: [&str; 3] = ["game-updated", "upload-failed", "upload-completed"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    pub url: String,
+    // Subset of `WEBHOOK_EVENTS`; empty means every event
+    #[serde(default)]
+    pub events: Vec<String>,
+    #[serde(default = "default_webhook_enabled")]
+    pub enabled: bool,
+}
+
+fn default_webhook_enabled() -> bool {
+    true
+}
+
+// Body POSTed to webhook URLs
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookPayload {
+    pub event: String,
+    pub timestamp: String,
+    pub client_version: String,
+    pub data: serde_json::Value,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            concurrency: 3,
+            upload_speed_limit: 0,
+            allowed_environments: vec!["Live".to_string(), "Production".to_string()],
+            upload_interval: 60,      // Default to 60 minutes
+            scan_interval_minutes: 1, // Default to 1 minute
+            metadata_refresh_interval_minutes: 24 * 60,
+            crash_reporting_enabled: false,
+            metadata_cache_max_entries: 5000,
+            metadata_cache_max_bytes: 50 * 1024 * 1024,
+            epic_catalog_fallback: true,
+            metadata_timeout_secs: 10,
+            upload_timeout_secs: 120,
+            upload_require_tls12: true,
+            upload_pinned_cert_path: None,
+            telemetry_enabled: false,
+            webhooks: Vec::new(),
+            local_api_enabled: false,
+            local_api_port: 47863,
+            background_priority: true,
+            upload_endpoints: Vec::new(),
+            environment_endpoints: HashMap::new(),
+            tray_popover: false,
+            sidecar_upload_games: Vec::new(),
+            profiles: Vec::new(),
+            active_profile: None,
+            auto_upload_enabled: true,
+            auto_scan_enabled: true,
+            daily_digest_enabled: false,
+            share_host_details: false,
+            upload_on_detect: true,
+            linux_autostart: false,
+        }
+    }
+}
+
+// Bounds for the periodic task intervals, in minutes; 0 always means disabled
+pub const MAX_SCAN_INTERVAL_MINUTES: u64 = 24 * 60;
+pub const MAX_UPLOAD_INTERVAL_MINUTES: u64 = 7 * 24 * 60;
+pub const MAX_METADATA_REFRESH_INTERVAL_MINUTES: u64 = 30 * 24 * 60;
+
+impl Settings {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.scan_interval_minutes > MAX_SCAN_INTERVAL_MINUTES {
+            return Err(format!(
+                "Scan interval must be between 0 (disabled) and {} minutes",
+                MAX_SCAN_INTERVAL_MINUTES
+            ));
+        }
+        if self.upload_interval > MAX_UPLOAD_INTERVAL_MINUTES {
+            return Err(format!(
+                "Upload interval must be between 0 (disabled) and {} minutes",
+                MAX_UPLOAD_INTERVAL_MINUTES
+            ));
+        }
+        if self.metadata_refresh_interval_minutes > MAX_METADATA_REFRESH_INTERVAL_MINUTES {
+            return Err(format!(
+                "Metadata refresh interval must be between 0 (disabled) and {} minutes",
+                MAX_METADATA_REFRESH_INTERVAL_MINUTES
+            ));
+        }
+        let mut profile_names = std::collections::HashSet::new();
+        for profile in &self.profiles {
+            if profile.name.trim().is_empty() {
+                return Err("Profile name must not be empty".to_string());
+            }
+            if !profile_names.insert(profile.name.as_str()) {
+                return Err(format!("Duplicate profile name: {}", profile.name));
+            }
+            if profile.scan_interval_minutes > MAX_SCAN_INTERVAL_MINUTES
+                || profile.upload_interval > MAX_UPLOAD_INTERVAL_MINUTES
+            {
+                return Err(format!(
+                    "Profile {} has an interval out of range",
+                    profile.name
+                ));
+            }
+        }
+        if let Some(active) = &self.active_profile {
+            if !profile_names.contains(active.as_str()) {
+                return Err(format!("Unknown active profile: {}", active));
+            }
+        }
+        if self.local_api_enabled && self.local_api_port < 1024 {
+            return Err("Local API port must be 1024 or higher".to_string());
+        }
+        if let Some(endpoint) = self
+            .upload_endpoints
+            .iter()
+            .chain(self.environment_endpoints.values())
+            .find(|e| !e.starts_with("https://"))
+        {
+            return Err(format!("Upload endpoint must use https: {}", endpoint));
+        }
+        for webhook in &self.webhooks {
+            if !webhook.url.starts_with("https://") && !webhook.url.starts_with("http://") {
+                return Err(format!(
+                    "Webhook URL must use http or https: {}",
+                    webhook.url
+                ));
+            }
+            if let Some(event) = webhook
+                .events
+                .iter()
+                .find(|e| !WEBHOOK_EVENTS.contains(&e.as_str()))
+            {
+                return Err(format!("Unknown webhook event: {}", event));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn metadata_timeout(&self) -> Duration {
+        Duration::from_secs(self.metadata_timeout_secs.max(1))
+    }
+
+    pub fn upload_timeout(&self) -> Duration {
+        Duration::from_secs(self.upload_timeout_secs.max(1))
+    }
+}
+
+// Entry from the launcher's LauncherInstalled.dat
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LauncherInstallation {
+    #[serde(rename = "InstallLocation")]
+    pub install_location: String,
+    #[serde(rename = "NamespaceId", default)]
+    pub namespace_id: String,
+    #[serde(rename = "ItemId", default)]
+    pub item_id: String,
+    #[serde(rename = "ArtifactId", default)]
+    pub artifact_id: String,
+    #[serde(rename = "AppVersion", default)]
+    pub app_version: String,
+    #[serde(rename = "AppName")]
+    pub app_name: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LauncherConsistencyReport {
+    // `.item` manifests the launcher no longer lists as installed
+    pub orphaned_manifests: Vec<String>,
+    // Installs the launcher lists but that have no `.item` manifest
+    pub untracked_installs: Vec<LauncherInstallation>,
+    // Games whose install location differs between the two sources
+    pub location_mismatches: Vec<String>,
+}
+
+impl LauncherConsistencyReport {
+    pub fn is_consistent(&self) -> bool {
+        self.orphaned_manifests.is_empty()
+            && self.untracked_installs.is_empty()
+            && self.location_mismatches.is_empty()
+    }
+}
+
+// Body returned by the builds API for upload requests
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct UploadApiResponse {
+    pub success: Option<bool>,
+    pub code: Option<String>,
+    pub message: Option<String>,
+    pub error: Option<String>,
+}
+
+impl UploadApiResponse {
+    pub fn is_duplicate(&self) -> bool {
+        matches!(
+            self.code.as_deref(),
+            Some("MANIFEST_EXISTS") | Some("DUPLICATE_MANIFEST") | Some("already_exists")
+        )
+    }
+
+    pub fn description(&self) -> Option<String> {
+        self.message.clone().or_else(|| self.error.clone())
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SidecarFile {
+    // Path relative to `.egstore`
+    pub name: String,
+    pub size: u64,
+    // Mentions the install folder or home directory; left out of uploads
+    pub contains_local_paths: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SidecarReport {
+    pub app_name: String,
+    pub display_name: String,
+    pub files: Vec<SidecarFile>,
+    // Size of the files that would actually be sent
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BulkExportSummary {
+    // Dated folder the manifests were copied into
+    pub path: String,
+    pub exported: Vec<String>,
+    // Games that could not be exported, with the reason
+    pub failed: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportSummary {
+    pub results: Vec<UploadStatus>,
+    // `.item` files that could not be parsed, with the reason
+    pub invalid_items: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -98,4 +548,345 @@ pub struct UploadStatus {
     pub status: String,
     pub message: Option<String>,
     pub manifest_hash: Option<String>,
-}
\ No newline at end of file
+    // Builds-API base URL that answered the upload
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    // Why the server rejected the upload; None for successes and local failures
+    #[serde(default)]
+    pub error_code: Option<UploadErrorCode>,
+    // Set when a request actually went out
+    #[serde(default)]
+    pub metrics: Option<UploadMetrics>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct UploadMetrics {
+    // Request sent to response read, including server processing
+    pub duration_ms: u64,
+    // Bytes of `.item` and manifest data sent
+    pub payload_bytes: u64,
+    pub bytes_per_second: u64,
+}
+
+impl UploadMetrics {
+    pub fn new(duration: Duration, payload_bytes: u64) -> Self {
+        let duration_ms = duration.as_millis() as u64;
+        UploadMetrics {
+            duration_ms,
+            payload_bytes,
+            bytes_per_second: payload_bytes * 1000 / duration_ms.max(1),
+        }
+    }
+}
+
+impl UploadStatus {
+    // Rejections that will repeat until the manifest or the client changes
+    pub fn is_permanent_failure(&self) -> bool {
+        self.error_code.is_some_and(UploadErrorCode::is_permanent)
+    }
+}
+
+// Whether one-off tasks can be run with administrator rights from the UI
+#[derive(Debug, Clone, Serialize)]
+pub struct ElevationStatus {
+    // False where there's no elevation prompt to show (anything but Windows)
+    pub supported: bool,
+    // The client itself already runs with administrator rights
+    pub elevated: bool,
+}
+
+// Whether there is anything to scan; sent as `launcher-status` when it changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LauncherState {
+    Ok,
+    // No launcher installation and no manifests folder anywhere
+    LauncherNotFound,
+    // The launcher is installed but has never written a manifest
+    NoManifests,
+}
+
+// Why a game's folder couldn't be opened
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OpenLocationErrorCode {
+    GameNotFound,
+    PathMissing,
+    // The drive didn't answer in time (disconnected or asleep)
+    DriveOffline,
+    AccessDenied,
+    OpenFailed,
+}
+
+// Error returned by `open_install_location` / `open_manifest_location`
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenLocationError {
+    pub code: OpenLocationErrorCode,
+    pub message: String,
+}
+
+// Typed reasons the builds API rejects an upload
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UploadErrorCode {
+    InvalidManifest,
+    UnsupportedVersion,
+    TooLarge,
+    Unauthorized,
+    Unknown,
+}
+
+impl UploadErrorCode {
+    // From the response's `code`, falling back to the HTTP status for servers
+    // that don't send one
+    pub fn from_response(http_status: u16, code: Option<&str>) -> Self {
+        let code = code.map(|c| c.to_ascii_uppercase());
+        match code.as_deref() {
+            Some("INVALID_MANIFEST" | "BAD_MANIFEST" | "MANIFEST_PARSE_ERROR" | "INVALID_ITEM") => {
+                UploadErrorCode::InvalidManifest
+            }
+            Some("UNSUPPORTED_VERSION" | "UNSUPPORTED_MANIFEST_VERSION" | "CLIENT_OUTDATED") => {
+                UploadErrorCode::UnsupportedVersion
+            }
+            Some("TOO_LARGE" | "PAYLOAD_TOO_LARGE" | "FILE_TOO_LARGE") => UploadErrorCode::TooLarge,
+            Some("UNAUTHORIZED" | "FORBIDDEN" | "INVALID_SIGNATURE") => {
+                UploadErrorCode::Unauthorized
+            }
+            _ => match http_status {
+                400 | 422 => UploadErrorCode::InvalidManifest,
+                413 => UploadErrorCode::TooLarge,
+                401 | 403 => UploadErrorCode::Unauthorized,
+                426 => UploadErrorCode::UnsupportedVersion,
+                _ => UploadErrorCode::Unknown,
+            },
+        }
+    }
+
+    pub fn is_permanent(self) -> bool {
+        matches!(
+            self,
+            UploadErrorCode::InvalidManifest
+                | UploadErrorCode::UnsupportedVersion
+                | UploadErrorCode::TooLarge
+        )
+    }
+}
+
+// A build seen on this machine that the server doesn't have
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissingBuild {
+    pub app_name: String,
+    pub version: String,
+    pub manifest_hash: String,
+    // Still installed and readable, so an upload would fill the gap
+    pub uploadable: bool,
+}
+
+// Result of `reconcile_with_server`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ServerReconciliation {
+    pub checked: usize,
+    pub on_server: usize,
+    pub added_to_ledger: usize,
+    // The server didn't give a clear answer for these
+    pub inconclusive: usize,
+    pub missing: Vec<MissingBuild>,
+}
+
+// One thing the user could do to add data to egdata right now
+#[derive(Debug, Clone, Serialize)]
+pub struct ContributionOpportunity {
+    // "missing_build", "failed_upload", "drive_offline" or "access_denied"
+    pub kind: String,
+    pub app_name: String,
+    pub display_name: String,
+    pub manifest_hash: Option<String>,
+    pub detail: String,
+    // Command or user step that addresses it: "upload_manifest",
+    // "retry_failed_uploads", "run_elevated_task" or "reconnect_drive"
+    pub action: String,
+}
+
+// Upload waiting in the retry queue for `retry_failed_uploads`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailedUpload {
+    pub app_name: String,
+    pub display_name: String,
+    pub installation_guid: String,
+    pub manifest_hash: String,
+    pub message: String,
+    // The server's rejection will repeat; retries skip it
+    pub permanent: bool,
+    pub attempts: u32,
+    pub failed_at: String,
+}
+
+// When a build of a game was first and last observed on this machine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestSighting {
+    pub app_name: String,
+    pub manifest_hash: String,
+    pub version: String,
+    // RFC 3339 UTC
+    pub first_seen: String,
+    pub last_seen: String,
+    // The game was already known under another build when this one appeared,
+    // so `first_seen` is when the build arrived rather than when we started looking
+    #[serde(default)]
+    pub observed_arrival: bool,
+}
+
+// Manifest the builds API has accepted, persisted in the upload ledger
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    pub manifest_hash: String,
+    pub app_name: String,
+    pub version: String,
+    // "uploaded" or "already_uploaded"
+    pub status: String,
+    pub endpoint: Option<String>,
+    pub recorded_at: String,
+    #[serde(default)]
+    pub metrics: Option<UploadMetrics>,
+}
+
+// Crash information captured by the panic hook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashReport {
+    pub id: String,
+    pub timestamp: String,
+    pub version: String,
+    pub os: String,
+    pub arch: String,
+    pub thread: String,
+    pub message: String,
+    pub location: Option<String>,
+    pub backtrace: String,
+}
+
+// Activity over one digest period, counted from the activity timeline
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DailyDigest {
+    pub since: String,
+    pub until: String,
+    pub uploaded: usize,
+    pub installed: usize,
+    pub updated: usize,
+    pub failed: usize,
+}
+
+impl DailyDigest {
+    pub fn is_empty(&self) -> bool {
+        self.uploaded + self.installed + self.updated + self.failed == 0
+    }
+
+    // e.g. "3 new builds uploaded, 2 games updated, 1 failure"
+    pub fn summary(&self) -> String {
+        let plural = |count: usize, one: &str, many: &str| {
+            format!("{} {}", count, if count == 1 { one } else { many })
+        };
+        [
+            (
+                self.uploaded,
+                plural(self.uploaded, "new build uploaded", "new builds uploaded"),
+            ),
+            (
+                self.installed,
+                plural(self.installed, "game installed", "games installed"),
+            ),
+            (
+                self.updated,
+                plural(self.updated, "game updated", "games updated"),
+            ),
+            (self.failed, plural(self.failed, "failure", "failures")),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(_, text)| text)
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+}
+
+// Anonymous, aggregate usage report (see `get_telemetry_preview`)
+#[derive(Debug, Clone, Serialize)]
+pub struct TelemetryReport {
+    pub client_version: String,
+    pub os: String,
+    pub scans: u64,
+    pub average_scan_ms: u64,
+    pub uploads_succeeded: u64,
+    pub uploads_already_present: u64,
+    pub uploads_failed: u64,
+    pub api_requests: u64,
+    pub api_error_rate: f64,
+}
+
+// A GitHub release of the client
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseNote {
+    pub tag_name: String,
+    pub name: Option<String>,
+    pub body: Option<String>,
+    pub published_at: Option<String>,
+    pub html_url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticCheck {
+    pub name: String,
+    pub status: String, // "ok", "warning" or "error"
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticsReport {
+    pub generated_at: String,
+    pub client_version: String,
+    pub healthy: bool,
+    pub checks: Vec<DiagnosticCheck>,
+}
+
+// Minimal view of an installed game kept in library snapshots
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SnapshotEntry {
+    pub app_name: String,
+    pub display_name: String,
+    pub catalog_item_id: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibrarySnapshot {
+    pub timestamp: String,
+    pub games: Vec<SnapshotEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryChange {
+    pub timestamp: String,
+    pub kind: String, // "installed", "uninstalled" or "updated"
+    pub app_name: String,
+    pub display_name: String,
+    pub from_version: Option<String>,
+    pub to_version: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LibraryHistory {
+    pub first_snapshot_at: Option<String>,
+    pub installs: usize,
+    pub uninstalls: usize,
+    pub updates: usize,
+    pub changes: Vec<LibraryChange>,
+}
+
+// Entry in the persisted activity timeline (see `get_activity_timeline`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityEvent {
+    pub id: u64,
+    pub timestamp: String,
+    pub kind: String,
+    pub message: String,
+    pub app_name: Option<String>,
+}
@@ -0,0 +1,102 @@
+use super::api::primary_endpoint;
+use super::state::{GameStore, MetadataCache, SettingsState};
+use super::utils::emit_log;
+use std::net::UdpSocket;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::AppHandle;
+use tokio::time;
+
+// Offline, look for the connection coming back often; online, only often
+// enough to notice it dropped without probing the API every few seconds
+const OFFLINE_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+const ONLINE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Set when connectivity comes back and taken by the periodic upload task
+static UPLOAD_AFTER_RECONNECT: AtomicBool = AtomicBool::new(false);
+
+pub fn take_upload_wake() -> bool {
+    UPLOAD_AFTER_RECONNECT.swap(false, Ordering::SeqCst)
+}
+
+// Connecting a UDP socket sends nothing; it only asks the OS for a route, which
+// fails while no network is up. Saves probing the API when that's certain to fail.
+fn has_route() -> bool {
+    ["1.1.1.1:53", "[2606:4700:4700::1111]:53"]
+        .iter()
+        .any(|target| {
+            let bind = if target.starts_with('[') {
+                "[::]:0"
+            } else {
+                "0.0.0.0:0"
+            };
+            UdpSocket::bind(bind)
+                .and_then(|socket| socket.connect(target))
+                .is_ok()
+        })
+}
+
+// A route only says the local network is up (Wi-Fi can be connected while the
+// WAN behind it is down), so this asks the upload endpoint itself. Any HTTP
+// answer counts as reachable.
+async fn api_reachable(settings: &SettingsState) -> bool {
+    if !has_route() {
+        return false;
+    }
+    let endpoint = settings
+        .lock()
+        .map(|s| primary_endpoint(&s))
+        .unwrap_or_else(|e| primary_endpoint(&e.into_inner()));
+    crate::HTTP_CLIENT
+        .head(endpoint)
+        .timeout(PROBE_TIMEOUT)
+        .send()
+        .await
+        .is_ok()
+}
+
+// When the network comes back, flushes pending uploads and retries metadata
+// that failed to load instead of waiting for the next periodic run
+pub async fn watch_connectivity(
+    app_handle: AppHandle,
+    games: GameStore,
+    metadata_cache: MetadataCache,
+    settings: SettingsState,
+) {
+    let mut online = api_reachable(&settings).await;
+    loop {
+        time::sleep(if online {
+            ONLINE_CHECK_INTERVAL
+        } else {
+            OFFLINE_CHECK_INTERVAL
+        })
+        .await;
+
+        let now_online = api_reachable(&settings).await;
+        let reconnected = now_online && !online;
+        online = now_online;
+        if !reconnected {
+            continue;
+        }
+
+        emit_log(
+            &app_handle,
+            "INFO",
+            "Network connection restored; retrying uploads and metadata",
+        );
+        UPLOAD_AFTER_RECONNECT.store(true, Ordering::SeqCst);
+
+        let known_games = games
+            .lock()
+            .map(|games_lock| games_lock.values().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        crate::spawn_metadata_enrichment(
+            app_handle.clone(),
+            games.clone(),
+            metadata_cache.clone(),
+            settings.clone(),
+            known_games,
+        );
+    }
+}
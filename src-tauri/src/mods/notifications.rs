@@ -0,0 +1,158 @@
+use super::events::{self, AppEvent};
+use super::models::DailyDigest;
+use super::state::{GameStore, SettingsState};
+use super::utils::emit_log;
+use super::window;
+use tauri::{AppHandle, Manager};
+
+// Buttons a notification can carry; clicks are routed back through `dispatch_action`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationAction {
+    RetryUploads,
+    OpenApp,
+    ViewLog,
+}
+
+impl NotificationAction {
+    fn id(self) -> &'static str {
+        match self {
+            NotificationAction::RetryUploads => "retry-uploads",
+            NotificationAction::OpenApp => "open-app",
+            NotificationAction::ViewLog => "view-log",
+        }
+    }
+
+    #[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+    fn label(self) -> &'static str {
+        match self {
+            NotificationAction::RetryUploads => "Retry",
+            NotificationAction::OpenApp => "Open app",
+            NotificationAction::ViewLog => "View log",
+        }
+    }
+
+    fn parse(id: &str) -> Option<Self> {
+        [
+            NotificationAction::RetryUploads,
+            NotificationAction::OpenApp,
+            NotificationAction::ViewLog,
+        ]
+        .into_iter()
+        .find(|action| action.id() == id)
+    }
+}
+
+pub fn notify_upload_failures(app_handle: &AppHandle, failed_count: usize) {
+    show(
+        app_handle,
+        "Manifest upload failed",
+        &format!("{} manifest(s) could not be uploaded", failed_count),
+        &[
+            NotificationAction::RetryUploads,
+            NotificationAction::ViewLog,
+        ],
+    );
+}
+
+pub fn notify_new_games(app_handle: &AppHandle, display_names: &[String]) {
+    let body = match display_names {
+        [] => return,
+        [name] => format!("{} was added to your library", name),
+        [first, rest @ ..] => format!(
+            "{} and {} more were added to your library",
+            first,
+            rest.len()
+        ),
+    };
+    show(
+        app_handle,
+        "New game detected",
+        &body,
+        &[NotificationAction::OpenApp],
+    );
+}
+
+pub fn notify_digest(app_handle: &AppHandle, digest: &DailyDigest) {
+    show(
+        app_handle,
+        "Your daily EGData summary",
+        &digest.summary(),
+        &[NotificationAction::OpenApp],
+    );
+}
+
+// Runs the command behind a clicked button. A click on the toast body arrives
+// without an action and just opens the app.
+pub fn dispatch_action(app_handle: &AppHandle, action: Option<&str>) {
+    match action.and_then(NotificationAction::parse) {
+        Some(NotificationAction::RetryUploads) => {
+            let app_handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move { upload_all_and_report(&app_handle).await });
+        }
+        Some(NotificationAction::ViewLog) => {
+            window::show_main_window(app_handle);
+            events::emit(app_handle, AppEvent::ShowLogConsole);
+        }
+        Some(NotificationAction::OpenApp) | None => window::show_main_window(app_handle),
+    }
+}
+
+// Uploads every manifest and logs a summary; shared with the Jump List task
+pub async fn upload_all_and_report(app_handle: &AppHandle) {
+    emit_log(app_handle, "INFO", "Uploading all manifests...");
+    let settings = app_handle
+        .state::<SettingsState>()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    let games = app_handle.state::<GameStore>().inner().clone();
+
+    match crate::upload_all_manifests_internal(&games, &settings).await {
+        Ok(results) => {
+            let failed_count = results.iter().filter(|r| r.status == "failed").count();
+            emit_log(
+                app_handle,
+                if failed_count > 0 { "WARN" } else { "SUCCESS" },
+                &format!(
+                    "Upload completed: {} of {} failed",
+                    failed_count,
+                    results.len()
+                ),
+            );
+            events::emit(app_handle, AppEvent::PeriodicUploadCompleted(&results));
+        }
+        Err(e) => emit_log(app_handle, "ERROR", &format!("Upload failed: {}", e)),
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn show(app_handle: &AppHandle, title: &str, body: &str, actions: &[NotificationAction]) {
+    use tauri_winrt_notification::Toast;
+
+    // Installed builds are registered under the bundle identifier
+    let app_id = if cfg!(debug_assertions) {
+        Toast::POWERSHELL_APP_ID.to_string()
+    } else {
+        app_handle.config().identifier.clone()
+    };
+
+    let mut toast = Toast::new(&app_id).title(title).text1(body);
+    for action in actions {
+        toast = toast.add_button(action.label(), action.id());
+    }
+
+    let app_handle = app_handle.clone();
+    let result = toast
+        .on_activated(move |action| {
+            dispatch_action(&app_handle, action.as_deref());
+            Ok(())
+        })
+        .show();
+    if let Err(e) = result {
+        eprintln!("Failed to show notification: {}", e);
+    }
+}
+
+// Toasts are Windows-only for now; elsewhere the log already carries the message
+#[cfg(not(target_os = "windows"))]
+fn show(_app_handle: &AppHandle, _title: &str, _body: &str, _actions: &[NotificationAction]) {}
@@ -0,0 +1,105 @@
+use super::ledger;
+use super::models::{ContributionOpportunity, GameInfo};
+use super::reconcile;
+use super::retry_queue;
+use super::state::GameStore;
+use std::collections::HashSet;
+
+// Everything the user could do right now that would add data to egdata:
+// builds the server is missing, failed uploads worth retrying and installs
+// that can't be read. Each entry names the action that addresses it. Missing
+// builds come from the last reconcile; nothing is asked of the server here.
+pub fn find_opportunities(games: &GameStore) -> Vec<ContributionOpportunity> {
+    let installed = games
+        .lock()
+        .map(|games_lock| games_lock.values().cloned().collect::<Vec<_>>())
+        .unwrap_or_default();
+    let display_name = |app_name: &str| {
+        installed
+            .iter()
+            .find(|g| g.app_name == app_name)
+            .map(|g| g.display_name.clone())
+            .unwrap_or_else(|| app_name.to_string())
+    };
+
+    let failed = retry_queue::load_failed_uploads()
+        .into_values()
+        .filter(|f| !f.permanent)
+        .collect::<Vec<_>>();
+    let failed_hashes = failed
+        .iter()
+        .map(|f| f.manifest_hash.to_uppercase())
+        .collect::<HashSet<_>>();
+
+    let mut opportunities = Vec::new();
+
+    // Builds already queued for a retry are listed once, as failed uploads, and
+    // ones uploaded since the last reconcile not at all
+    let ledger = ledger::load_ledger();
+    let missing = reconcile::last_reconciliation()
+        .map(|report| report.missing)
+        .unwrap_or_default();
+    for build in missing {
+        if !build.uploadable
+            || failed_hashes.contains(&build.manifest_hash.to_uppercase())
+            || ledger.contains_key(&build.manifest_hash)
+        {
+            continue;
+        }
+        opportunities.push(ContributionOpportunity {
+            kind: "missing_build".to_string(),
+            display_name: display_name(&build.app_name),
+            detail: format!("egdata doesn't have version {} yet", build.version),
+            action: "upload_manifest".to_string(),
+            app_name: build.app_name,
+            manifest_hash: Some(build.manifest_hash),
+        });
+    }
+
+    for upload in failed {
+        opportunities.push(ContributionOpportunity {
+            kind: "failed_upload".to_string(),
+            app_name: upload.app_name,
+            display_name: upload.display_name,
+            detail: upload.message,
+            action: "retry_failed_uploads".to_string(),
+            manifest_hash: Some(upload.manifest_hash),
+        });
+    }
+
+    for game in installed.iter().filter(|g| !g.available) {
+        opportunities.push(unreachable_install(game));
+    }
+
+    opportunities
+}
+
+fn unreachable_install(game: &GameInfo) -> ContributionOpportunity {
+    let (kind, detail, action) = if game.access_denied {
+        (
+            "access_denied",
+            format!(
+                "{} can't be read by this account; an elevated scan can upload it",
+                game.install_location
+            ),
+            "run_elevated_task",
+        )
+    } else {
+        (
+            "drive_offline",
+            format!(
+                "{} is on a drive that isn't connected",
+                game.install_location
+            ),
+            "reconnect_drive",
+        )
+    };
+    ContributionOpportunity {
+        kind: kind.to_string(),
+        app_name: game.app_name.clone(),
+        display_name: game.display_name.clone(),
+        detail,
+        action: action.to_string(),
+        manifest_hash: Some(game.manifest_hash.clone()),
+    }
+}
@@ -0,0 +1,32 @@
+use super::tray;
+use super::utils::emit_log;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::AppHandle;
+
+// One-click silence for automatic uploads (streaming, metered connections).
+// Lasts until unpaused or the app restarts; explicit uploads still go through.
+static UPLOADS_PAUSED: AtomicBool = AtomicBool::new(false);
+
+pub fn is_paused() -> bool {
+    UPLOADS_PAUSED.load(Ordering::SeqCst)
+}
+
+pub fn set_paused(app_handle: &AppHandle, paused: bool) {
+    if UPLOADS_PAUSED.swap(paused, Ordering::SeqCst) == paused {
+        return;
+    }
+    emit_log(
+        app_handle,
+        "INFO",
+        if paused {
+            "Automatic uploads paused"
+        } else {
+            "Automatic uploads resumed"
+        },
+    );
+    tray::refresh(app_handle);
+}
+
+pub fn toggle(app_handle: &AppHandle) {
+    set_paused(app_handle, !is_paused());
+}
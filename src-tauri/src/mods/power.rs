@@ -0,0 +1,50 @@
+use super::utils::emit_log;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
+use tauri::AppHandle;
+use tokio::time;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(15);
+// Wall-clock time passing this much faster than our sleeps means the machine
+// was suspended in between
+const SUSPEND_THRESHOLD: Duration = Duration::from_secs(60);
+
+// Set on resume and taken by the periodic tasks, each running straight away once
+static SCAN_AFTER_RESUME: AtomicBool = AtomicBool::new(false);
+static UPLOAD_AFTER_RESUME: AtomicBool = AtomicBool::new(false);
+
+pub fn take_scan_wake() -> bool {
+    SCAN_AFTER_RESUME.swap(false, Ordering::SeqCst)
+}
+
+pub fn take_upload_wake() -> bool {
+    UPLOAD_AFTER_RESUME.swap(false, Ordering::SeqCst)
+}
+
+// Tokio timers don't advance during suspend (or all fire at once afterwards,
+// depending on the platform), so resume is detected from the wall clock
+// jumping ahead and the scan and upload tasks are woken explicitly
+pub async fn watch_for_resume(app_handle: AppHandle) {
+    let mut last_check = SystemTime::now();
+    loop {
+        time::sleep(CHECK_INTERVAL).await;
+
+        let now = SystemTime::now();
+        let elapsed = now.duration_since(last_check).unwrap_or_default();
+        last_check = now;
+        if elapsed < CHECK_INTERVAL + SUSPEND_THRESHOLD {
+            continue;
+        }
+
+        emit_log(
+            &app_handle,
+            "INFO",
+            &format!(
+                "System resumed after about {} minutes; scanning and uploading now",
+                elapsed.as_secs() / 60
+            ),
+        );
+        SCAN_AFTER_RESUME.store(true, Ordering::SeqCst);
+        UPLOAD_AFTER_RESUME.store(true, Ordering::SeqCst);
+    }
+}
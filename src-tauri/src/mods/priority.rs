@@ -0,0 +1,144 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// Mirrors `Settings::background_priority`; read from blocking worker threads
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+// Runs `work` at reduced CPU and IO priority, so scans don't cause hitching
+// while a game is running. The calling thread is back at normal priority
+// afterwards.
+pub fn run_in_background<T: Send>(work: impl FnOnce() -> T + Send) -> T {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return work();
+    }
+
+    // Unprivileged Linux processes can lower a thread's priority but never
+    // raise it again, so the work gets a short-lived thread of its own
+    #[cfg(target_os = "linux")]
+    return std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                let _guard = BackgroundGuard::enter();
+                work()
+            })
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    });
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _guard = BackgroundGuard::enter();
+        work()
+    }
+}
+
+// `run_in_background` on the blocking pool, for async callers
+pub async fn spawn_background<T: Send + 'static>(
+    work: impl FnOnce() -> T + Send + 'static,
+) -> Result<T, tokio::task::JoinError> {
+    tokio::task::spawn_blocking(move || run_in_background(work)).await
+}
+
+struct BackgroundGuard;
+
+#[cfg(target_os = "windows")]
+mod sys {
+    extern "system" {
+        fn GetCurrentThread() -> isize;
+        fn SetThreadPriority(thread: isize, priority: i32) -> i32;
+    }
+
+    const THREAD_MODE_BACKGROUND_BEGIN: i32 = 0x0001_0000;
+    const THREAD_MODE_BACKGROUND_END: i32 = 0x0002_0000;
+
+    // Background mode lowers both CPU scheduling and IO priority
+    pub fn enter() {
+        unsafe {
+            SetThreadPriority(GetCurrentThread(), THREAD_MODE_BACKGROUND_BEGIN);
+        }
+    }
+
+    pub fn exit() {
+        unsafe {
+            SetThreadPriority(GetCurrentThread(), THREAD_MODE_BACKGROUND_END);
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod sys {
+    extern "C" {
+        fn setpriority(which: i32, who: u32, prio: i32) -> i32;
+    }
+
+    const PRIO_DARWIN_THREAD: i32 = 3;
+    const PRIO_DARWIN_BG: i32 = 0x1000;
+
+    // Darwin's background band throttles CPU, disk and network for the thread
+    pub fn enter() {
+        unsafe {
+            setpriority(PRIO_DARWIN_THREAD, 0, PRIO_DARWIN_BG);
+        }
+    }
+
+    pub fn exit() {
+        unsafe {
+            setpriority(PRIO_DARWIN_THREAD, 0, 0);
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod sys {
+    extern "C" {
+        fn setpriority(which: i32, who: u32, prio: i32) -> i32;
+        fn syscall(number: i64, ...) -> i64;
+    }
+
+    const PRIO_PROCESS: i32 = 0;
+    const NICE_LEVEL: i32 = 10;
+    #[cfg(target_arch = "x86_64")]
+    const SYS_IOPRIO_SET: Option<i64> = Some(251);
+    #[cfg(target_arch = "aarch64")]
+    const SYS_IOPRIO_SET: Option<i64> = Some(30);
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    const SYS_IOPRIO_SET: Option<i64> = None;
+    const IOPRIO_WHO_PROCESS: i64 = 1;
+    // Best-effort class at its lowest level
+    const IOPRIO_BE_LOWEST: i64 = (2 << 13) | 7;
+
+    // On Linux these calls only affect the calling thread
+    pub fn enter() {
+        unsafe {
+            setpriority(PRIO_PROCESS, 0, NICE_LEVEL);
+            if let Some(number) = SYS_IOPRIO_SET {
+                syscall(number, IOPRIO_WHO_PROCESS, 0i64, IOPRIO_BE_LOWEST);
+            }
+        }
+    }
+
+    // The thread ends with the work; see `run_in_background`
+    pub fn exit() {}
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+mod sys {
+    pub fn enter() {}
+    pub fn exit() {}
+}
+
+impl BackgroundGuard {
+    fn enter() -> Self {
+        sys::enter();
+        BackgroundGuard
+    }
+}
+
+impl Drop for BackgroundGuard {
+    fn drop(&mut self) {
+        sys::exit();
+    }
+}
@@ -0,0 +1,42 @@
+use super::events::{self, AppEvent};
+use super::models::Settings;
+use super::settings_watch;
+use super::state::SettingsState;
+use super::utils::{emit_log, save_settings_to_file};
+use tauri::AppHandle;
+
+// Copies the named profile's values over the settings and marks it active
+pub fn apply_profile(settings: &mut Settings, name: &str) -> Result<(), String> {
+    let profile = settings
+        .profiles
+        .iter()
+        .find(|p| p.name == name)
+        .cloned()
+        .ok_or_else(|| format!("No profile named {}", name))?;
+
+    settings.scan_interval_minutes = profile.scan_interval_minutes;
+    settings.upload_interval = profile.upload_interval;
+    settings.active_profile = Some(profile.name);
+    Ok(())
+}
+
+// Switches profile, persisting it and telling the frontend and tray
+pub fn switch_profile(
+    app_handle: &AppHandle,
+    settings: &SettingsState,
+    name: &str,
+) -> Result<Settings, String> {
+    let updated = {
+        let mut settings_lock = settings
+            .lock()
+            .map_err(|e| format!("Failed to lock settings: {}", e))?;
+        apply_profile(&mut settings_lock, name)?;
+        settings_lock.clone()
+    };
+    save_settings_to_file(&updated);
+
+    emit_log(app_handle, "INFO", &format!("Switched to profile {}", name));
+    settings_watch::apply_settings(app_handle, &updated);
+    events::emit(app_handle, AppEvent::SettingsUpdated(&updated));
+    Ok(updated)
+}
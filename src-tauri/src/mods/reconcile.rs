@@ -0,0 +1,120 @@
+use super::api::EgdataApi;
+use super::ledger;
+use super::models::{LedgerEntry, MissingBuild, ServerReconciliation, Settings};
+use super::sightings;
+use super::state::GameStore;
+use super::utils::{read_json, write_json};
+use std::collections::HashMap;
+
+const LAST_RECONCILIATION_FILE: &str = "last_reconciliation.json";
+
+// Asks the server about every build this machine has seen and brings the
+// ledger in line with its answers. Useful after reinstalling the client, when
+// the ledger is empty but the server already has most builds. Only main
+// manifests are checked; companion files in the ledger are left alone.
+pub async fn reconcile_with_server(
+    api: &impl EgdataApi,
+    games: &GameStore,
+    settings: &Settings,
+) -> ServerReconciliation {
+    let installed = games
+        .lock()
+        .map(|games_lock| games_lock.values().cloned().collect::<Vec<_>>())
+        .unwrap_or_default();
+    // Upper-cased manifest hash -> (available, environment) for installs
+    let installs = installed
+        .iter()
+        .map(|g| {
+            (
+                g.manifest_hash.to_uppercase(),
+                (g.available, g.environment.clone()),
+            )
+        })
+        .collect::<HashMap<_, _>>();
+
+    // Manifest hash -> (app name, version) of every main manifest seen here;
+    // the ledger also holds companion files, so it isn't a source
+    let mut known: HashMap<String, (String, String)> = HashMap::new();
+    for game in installed {
+        known.insert(game.manifest_hash, (game.app_name, game.version));
+    }
+    for sighting in sightings::load_sightings().into_values() {
+        known.insert(
+            sighting.manifest_hash,
+            (sighting.app_name, sighting.version),
+        );
+    }
+
+    let ledger = ledger::load_ledger();
+    let mut additions = Vec::new();
+    let mut report = ServerReconciliation::default();
+    for (manifest_hash, (app_name, version)) in known {
+        report.checked += 1;
+        let in_ledger = ledger.contains_key(&manifest_hash);
+        // An uploaded build is asked about where it went; others on the
+        // endpoint uploads for their environment are routed to
+        let exists = match ledger
+            .get(&manifest_hash)
+            .and_then(|entry| entry.endpoint.clone())
+        {
+            Some(endpoint) => {
+                api.manifest_exists_on(&manifest_hash, &endpoint, settings)
+                    .await
+            }
+            None => {
+                let environment = installs
+                    .get(&manifest_hash.to_uppercase())
+                    .map_or("", |(_, environment)| environment.as_str());
+                api.manifest_exists(&manifest_hash, environment, settings)
+                    .await
+            }
+        };
+        match exists {
+            Some(true) => {
+                report.on_server += 1;
+                if !in_ledger {
+                    additions.push(LedgerEntry {
+                        manifest_hash,
+                        app_name,
+                        version,
+                        status: "already_uploaded".to_string(),
+                        endpoint: None,
+                        recorded_at: chrono::Utc::now().to_rfc3339(),
+                        metrics: None,
+                    });
+                }
+            }
+            // A miss may be a wrong or moved endpoint; the ledger keeps its entry
+            Some(false) => {
+                let uploadable = installs
+                    .get(&manifest_hash.to_uppercase())
+                    .is_some_and(|(available, _)| *available);
+                report.missing.push(MissingBuild {
+                    app_name,
+                    version,
+                    manifest_hash,
+                    uploadable,
+                });
+            }
+            None => report.inconclusive += 1,
+        }
+    }
+
+    // The checks take a while; merge into the ledger as it is now so uploads
+    // recorded meanwhile aren't overwritten
+    report.added_to_ledger = ledger::add_missing(additions);
+    report
+        .missing
+        .sort_by(|a, b| a.app_name.cmp(&b.app_name).then(a.version.cmp(&b.version)));
+    report
+}
+
+// The report of the last reconcile the user ran, so views can show it without
+// putting every known build to the server again
+pub fn last_reconciliation() -> Option<ServerReconciliation> {
+    read_json(LAST_RECONCILIATION_FILE)
+}
+
+pub fn save_reconciliation(report: &ServerReconciliation) {
+    write_json(LAST_RECONCILIATION_FILE, report, "server reconciliation");
+}
@@ -0,0 +1,54 @@
+use super::http_cache;
+use super::models::ReleaseNote;
+use super::utils::get_app_data_path;
+use std::fs;
+use std::time::Duration;
+
+const RELEASES_URL: &str = "https://api.github.com/repos/egdata-app/egdata-client/releases";
+const LAST_SEEN_VERSION_FILE: &str = "last_seen_version";
+
+pub async fn fetch_release_notes(limit: usize) -> Result<Vec<ReleaseNote>, String> {
+    let url = format!("{}?per_page={}", RELEASES_URL, limit);
+    let body = http_cache::get(
+        &crate::EXTERNAL_HTTP_CLIENT,
+        &url,
+        Some("application/vnd.github+json"),
+        Duration::from_secs(10),
+        false,
+    )
+    .await
+    .map_err(|e| format!("Failed to fetch release notes: {}", e))?;
+
+    serde_json::from_str::<Vec<ReleaseNote>>(&body)
+        .map_err(|e| format!("Failed to parse release notes: {}", e))
+}
+
+fn current_version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
+
+// The notes for this version the first time it's asked after an update; the
+// frontend asks on mount. Fresh installs just record the version.
+pub async fn take_whats_new() -> Result<Option<ReleaseNote>, String> {
+    let path = get_app_data_path().join(LAST_SEEN_VERSION_FILE);
+    let last_seen = fs::read_to_string(&path).map(|v| v.trim().to_string()).ok();
+
+    if last_seen.as_deref() == Some(current_version()) {
+        return Ok(None);
+    }
+
+    // On failure the version isn't recorded, so the next launch asks again
+    let note = match last_seen {
+        Some(_) => fetch_release_notes(10)
+            .await?
+            .into_iter()
+            .find(|n| n.tag_name.trim_start_matches('v') == current_version()),
+        None => None,
+    };
+
+    let _ = fs::create_dir_all(get_app_data_path());
+    if let Err(e) = fs::write(&path, current_version()) {
+        eprintln!("Failed to record last seen version: {}", e);
+    }
+    Ok(note)
+}
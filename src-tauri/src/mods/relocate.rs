@@ -0,0 +1,134 @@
+use super::models::{GameInfo, PathProbe};
+use super::utils::{get_egstore_manifest_path, probe_path, PATH_PROBE_TIMEOUT};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// The paths that answer within PATH_PROBE_TIMEOUT. Probed together, so dead
+// mapped or optical drives cost one timeout for the lot instead of one each.
+async fn responsive_paths(paths: impl IntoIterator<Item = PathBuf>) -> HashSet<PathBuf> {
+    let mut seen = HashSet::new();
+    let probes = paths
+        .into_iter()
+        .filter(|path| seen.insert(path.clone()))
+        .map(|path| {
+            tokio::spawn(async move {
+                let probe = probe_path(path.clone(), PATH_PROBE_TIMEOUT).await;
+                (path, probe)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut responsive = HashSet::new();
+    for probe in probes {
+        if let Ok((path, PathProbe::Available)) = probe.await {
+            responsive.insert(path);
+        }
+    }
+    responsive
+}
+
+// Folders a moved game is likely to sit in: the folders holding the other
+// games, plus the launcher's default library on each drive
+async fn candidate_libraries(games: &[GameInfo]) -> Vec<PathBuf> {
+    let mut candidates = games
+        .iter()
+        .filter_map(|g| Path::new(&g.install_location).parent())
+        .map(Path::to_path_buf)
+        .collect::<Vec<_>>();
+
+    #[cfg(target_os = "windows")]
+    for letter in 'A'..='Z' {
+        let root = PathBuf::from(format!("{}:\\", letter));
+        candidates.push(root.join("Epic Games"));
+        candidates.push(root.join("Program Files").join("Epic Games"));
+    }
+
+    let responsive = responsive_paths(candidates.iter().cloned()).await;
+    let mut seen = HashSet::new();
+    candidates.retain(|library| responsive.contains(library) && seen.insert(library.clone()));
+    candidates
+}
+
+// Looks one level below each library for a folder whose `.egstore` holds this
+// installation's manifest
+fn find_moved_install(game: &GameInfo, libraries: &[PathBuf]) -> Option<PathBuf> {
+    libraries.iter().find_map(|library| {
+        fs::read_dir(library)
+            .ok()?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .find(|path| {
+                get_egstore_manifest_path(&path.to_string_lossy(), &game.installation_guid)
+                    .is_file()
+            })
+    })
+}
+
+// For games whose recorded install location is gone, searches for the folder
+// they were moved to. Found games point at the new folder, keep the stale path
+// in `moved_from` and count as available again. Only drives that respond are
+// searched; the search itself runs on the blocking pool at background priority.
+pub async fn reconcile_moved_installs(games: &mut [GameInfo]) {
+    if games.iter().all(|g| g.available) {
+        return;
+    }
+    let libraries = candidate_libraries(games).await;
+
+    // Unreadable installs are still where the launcher says, just not ours to
+    // read, and a game on a disconnected drive wasn't moved
+    let present_parents = responsive_paths(
+        games
+            .iter()
+            .filter(|g| !g.available && !g.access_denied)
+            .filter_map(|g| Path::new(&g.install_location).parent())
+            .map(Path::to_path_buf),
+    )
+    .await;
+    let missing = games
+        .iter()
+        .filter(|g| !g.available && !g.access_denied)
+        .filter(|g| {
+            Path::new(&g.install_location)
+                .parent()
+                .is_some_and(|parent| present_parents.contains(parent))
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    if missing.is_empty() {
+        return;
+    }
+
+    let found = super::priority::spawn_background(move || {
+        missing
+            .iter()
+            .filter_map(|game| {
+                find_moved_install(game, &libraries)
+                    .map(|location| (game.installation_guid.clone(), location))
+            })
+            .collect::<Vec<_>>()
+    })
+    .await
+    .unwrap_or_default();
+
+    for (installation_guid, new_location) in found {
+        let Some(game) = games
+            .iter_mut()
+            .find(|g| g.installation_guid == installation_guid)
+        else {
+            continue;
+        };
+        eprintln!(
+            "{} moved from {} to {}",
+            game.display_name,
+            game.install_location,
+            new_location.display()
+        );
+        game.moved_from = Some(std::mem::replace(
+            &mut game.install_location,
+            new_location.to_string_lossy().to_string(),
+        ));
+        game.available = true;
+    }
+}
@@ -0,0 +1,61 @@
+use super::models::{FailedUpload, GameInfo, UploadStatus};
+use super::utils::{read_json, write_json};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+const RETRY_QUEUE_FILE: &str = "failed_uploads.json";
+
+// Keyed by app name; only a game's latest failure is kept
+pub fn load_failed_uploads() -> HashMap<String, FailedUpload> {
+    read_json(RETRY_QUEUE_FILE).unwrap_or_default()
+}
+
+fn save_failed_uploads(failed: &HashMap<String, FailedUpload>) {
+    write_json(RETRY_QUEUE_FILE, failed, "failed uploads");
+}
+
+// Uploads finish concurrently (scheduled runs, detections, retries); keep
+// each load-modify-write of the queue whole
+static QUEUE_LOCK: Mutex<()> = Mutex::new(());
+
+// Queues a failed upload of the game's main manifest, or clears its entry once
+// the server has it. Only sends that failed are queued: a hash mismatch or an
+// unreadable install is settled by a later scan, not by sending again, and an
+// invalid manifest is kept for the record but never retried.
+pub fn record(game: &GameInfo, result: &Result<UploadStatus, String>) {
+    let _guard = QUEUE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut failed = load_failed_uploads();
+    let (message, permanent) = match result {
+        Ok(status) if status.status == "uploaded" || status.status == "already_uploaded" => {
+            if failed.remove(&game.app_name).is_some() {
+                save_failed_uploads(&failed);
+            }
+            return;
+        }
+        Ok(status) if status.status == "failed" || status.status == "invalid_manifest" => (
+            status
+                .message
+                .clone()
+                .unwrap_or_else(|| status.status.clone()),
+            status.status == "invalid_manifest" || status.is_permanent_failure(),
+        ),
+        Ok(_) => return,
+        Err(e) => (e.clone(), false),
+    };
+
+    let attempts = failed.get(&game.app_name).map_or(0, |f| f.attempts) + 1;
+    failed.insert(
+        game.app_name.clone(),
+        FailedUpload {
+            app_name: game.app_name.clone(),
+            display_name: game.display_name.clone(),
+            installation_guid: game.installation_guid.clone(),
+            manifest_hash: game.manifest_hash.clone(),
+            message,
+            permanent,
+            attempts,
+            failed_at: chrono::Utc::now().to_rfc3339(),
+        },
+    );
+    save_failed_uploads(&failed);
+}
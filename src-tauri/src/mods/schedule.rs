@@ -0,0 +1,123 @@
+use super::utils::emit_log;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tokio::time;
+
+// How often settings are re-read while waiting, so interval changes and
+// enable/disable transitions apply without restarting the task
+const SETTINGS_POLL_INTERVAL: Duration = Duration::from_secs(30);
+// Clients that start together (e.g. at boot) spread their first run over this window
+const MAX_STARTUP_JITTER: Duration = Duration::from_secs(120);
+// Each later wait is stretched by up to this fraction of the interval
+const INTERVAL_JITTER_FRACTION: f64 = 0.1;
+
+// Uniform in [0, max); std's randomly keyed hasher is plenty for spreading load
+fn random_jitter(max: Duration) -> Duration {
+    let random = RandomState::new().build_hasher().finish();
+    max.mul_f64((random >> 11) as f64 / (1u64 << 53) as f64)
+}
+
+// Drives a periodic task whose interval (in minutes) comes from settings.
+// An interval of 0 disables the task until it's set to something else.
+pub struct IntervalSchedule {
+    name: &'static str,
+    minutes: u64,
+    started: Instant,
+    last_run: Option<Instant>,
+    // Stretches the interval while runs keep finding nothing to do
+    backoff_multiplier: u64,
+    // Random delay added to the current wait so clients don't fire in lockstep
+    jitter: Duration,
+}
+
+impl IntervalSchedule {
+    pub fn new(name: &'static str, minutes: u64) -> Self {
+        Self {
+            name,
+            minutes,
+            started: Instant::now(),
+            last_run: None,
+            backoff_multiplier: 1,
+            jitter: random_jitter(MAX_STARTUP_JITTER),
+        }
+    }
+
+    // Doubles the effective interval, up to `max_multiplier` times the configured one
+    pub fn back_off(&mut self, max_multiplier: u64) {
+        self.backoff_multiplier = (self.backoff_multiplier * 2).min(max_multiplier.max(1));
+    }
+
+    pub fn reset_backoff(&mut self) {
+        self.backoff_multiplier = 1;
+    }
+
+    // Waits until the task is due. The first run happens shortly after startup if enabled.
+    pub async fn tick(&mut self, app_handle: &AppHandle, read_minutes: impl Fn() -> u64) {
+        self.tick_or_wake(app_handle, read_minutes, || false).await
+    }
+
+    // Like `tick`, but `woken` is checked on every settings poll; when it reports
+    // activity the backoff is dropped and the task runs straight away
+    pub async fn tick_or_wake(
+        &mut self,
+        app_handle: &AppHandle,
+        read_minutes: impl Fn() -> u64,
+        mut woken: impl FnMut() -> bool,
+    ) {
+        loop {
+            let minutes = read_minutes();
+            if minutes != self.minutes {
+                self.log_transition(app_handle, minutes);
+                self.minutes = minutes;
+                self.reset_backoff();
+            }
+
+            if minutes == 0 {
+                time::sleep(SETTINGS_POLL_INTERVAL).await;
+                continue;
+            }
+
+            if self.last_run.is_some() && woken() {
+                self.reset_backoff();
+                self.mark_run(minutes);
+                return;
+            }
+
+            let now = Instant::now();
+            let due = match self.last_run {
+                Some(last) => {
+                    last + Duration::from_secs(minutes * 60 * self.backoff_multiplier) + self.jitter
+                }
+                None => self.started + self.jitter,
+            };
+            if now >= due {
+                self.mark_run(minutes);
+                return;
+            }
+
+            time::sleep((due - now).min(SETTINGS_POLL_INTERVAL)).await;
+        }
+    }
+
+    fn mark_run(&mut self, minutes: u64) {
+        self.last_run = Some(Instant::now());
+        self.jitter =
+            random_jitter(Duration::from_secs(minutes * 60).mul_f64(INTERVAL_JITTER_FRACTION));
+    }
+
+    fn log_transition(&self, app_handle: &AppHandle, minutes: u64) {
+        let message = if minutes == 0 {
+            format!("Periodic {} disabled", self.name)
+        } else if self.minutes == 0 {
+            format!("Periodic {} enabled every {} minutes", self.name, minutes)
+        } else {
+            format!(
+                "Periodic {} interval updated to {} minutes",
+                self.name, minutes
+            )
+        };
+        emit_log(app_handle, "INFO", &message);
+    }
+}
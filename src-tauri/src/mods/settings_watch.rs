@@ -0,0 +1,79 @@
+use super::events::{self, AppEvent};
+use super::models::Settings;
+use super::state::{MetadataCache, SettingsState};
+use super::utils::{emit_log, get_settings_path, read_settings_file};
+use std::fs;
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Manager};
+use tokio::time;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+// Pushes settings that are read outside `SettingsState` to where they're used,
+// and refreshes the tray. Every path that changes settings at runtime calls this
+// once the new settings are stored.
+pub fn apply_settings(app_handle: &AppHandle, settings: &Settings) {
+    super::priority::set_enabled(settings.background_priority);
+    super::telemetry::set_enabled(settings.telemetry_enabled);
+    if let Ok(mut cache_lock) = app_handle.state::<MetadataCache>().lock() {
+        cache_lock.set_limits(
+            settings.metadata_cache_max_entries,
+            settings.metadata_cache_max_bytes,
+        );
+    }
+    super::http_cache::set_limits(
+        settings.metadata_cache_max_entries,
+        settings.metadata_cache_max_bytes,
+    );
+    super::tray::refresh(app_handle);
+}
+
+fn modified_time() -> Option<SystemTime> {
+    fs::metadata(get_settings_path())
+        .and_then(|m| m.modified())
+        .ok()
+}
+
+// Picks up edits to settings.json made outside the app (dotfiles, provisioning
+// scripts). Valid changes are applied live and announced with `settings-updated`;
+// invalid files are reported and ignored. The app's own saves compare equal to
+// the current settings and are skipped.
+pub async fn watch_settings_file(app_handle: AppHandle, settings: SettingsState) {
+    let mut last_modified = modified_time();
+
+    loop {
+        time::sleep(POLL_INTERVAL).await;
+
+        let modified = modified_time();
+        if modified.is_none() || modified == last_modified {
+            continue;
+        }
+        last_modified = modified;
+
+        let new_settings = match read_settings_file() {
+            Ok(new_settings) => new_settings,
+            Err(e) => {
+                emit_log(
+                    &app_handle,
+                    "WARN",
+                    &format!("Ignoring external settings change: {}", e),
+                );
+                continue;
+            }
+        };
+
+        {
+            let mut settings_lock = settings.lock().unwrap_or_else(|e| e.into_inner());
+            let unchanged = serde_json::to_value(&*settings_lock).ok()
+                == serde_json::to_value(&new_settings).ok();
+            if unchanged {
+                continue;
+            }
+            *settings_lock = new_settings.clone();
+        }
+
+        apply_settings(&app_handle, &new_settings);
+        emit_log(&app_handle, "INFO", "Settings reloaded from settings.json");
+        events::emit(&app_handle, AppEvent::SettingsUpdated(&new_settings));
+    }
+}
@@ -0,0 +1,132 @@
+use super::models::{GameInfo, SidecarFile, SidecarReport};
+use super::utils::get_egstore_manifest_path;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+// Nested caches sit one folder below `.egstore`
+const MAX_DEPTH: usize = 2;
+// Larger files are staged payload rather than metadata
+const MAX_SIDECAR_BYTES: u64 = 16 * 1024 * 1024;
+
+// Everything in `.egstore` other than what is already uploaded on its own:
+// the manifests and `.mancpn` files, and chunk data (reported by size only)
+fn sidecar_paths(game: &GameInfo) -> Vec<(String, PathBuf)> {
+    let manifest_path = get_egstore_manifest_path(&game.install_location, &game.installation_guid);
+    let Some(egstore) = manifest_path.parent() else {
+        return Vec::new();
+    };
+    let mut paths = Vec::new();
+    collect(egstore, egstore, 0, &mut paths);
+    paths.sort();
+    paths
+}
+
+fn collect(root: &Path, dir: &Path, depth: usize, paths: &mut Vec<(String, PathBuf)>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            if depth < MAX_DEPTH {
+                collect(root, &path, depth + 1, paths);
+            }
+            continue;
+        }
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        if matches!(extension.as_str(), "manifest" | "mancpn" | "chunk")
+            || metadata.len() > MAX_SIDECAR_BYTES
+        {
+            continue;
+        }
+        let Ok(relative) = path.strip_prefix(root) else {
+            continue;
+        };
+        // Forward slashes so bundles look the same from every OS
+        let name = relative.to_string_lossy().replace('\\', "/");
+        paths.push((name, path));
+    }
+}
+
+// Local paths and the user's name are what could identify the uploader
+fn contains_local_paths(bytes: &[u8], game: &GameInfo) -> bool {
+    let text = String::from_utf8_lossy(bytes);
+    let home = dirs::home_dir().map(|home| home.to_string_lossy().to_string());
+    [Some(game.install_location.clone()), home]
+        .into_iter()
+        .flatten()
+        .filter(|needle| !needle.is_empty())
+        .any(|needle| text.contains(&needle) || text.contains(&needle.replace('\\', "/")))
+}
+
+// What an opted-in upload would send for this game, shown before opting in
+pub fn preview(game: &GameInfo) -> SidecarReport {
+    let files = sidecar_paths(game)
+        .into_iter()
+        .filter_map(|(name, path)| {
+            let bytes = fs::read(&path).ok()?;
+            Some(SidecarFile {
+                name,
+                size: bytes.len() as u64,
+                contains_local_paths: contains_local_paths(&bytes, game),
+            })
+        })
+        .collect::<Vec<_>>();
+    SidecarReport {
+        app_name: game.app_name.clone(),
+        display_name: game.display_name.clone(),
+        total_bytes: files
+            .iter()
+            .filter(|file| !file.contains_local_paths)
+            .map(|file| file.size)
+            .sum(),
+        files,
+    }
+}
+
+// A zip of the game's sidecar files and a hash of their names and contents,
+// stable across runs so unchanged bundles are not re-sent. Files that mention
+// local paths are left out. None when the game has nothing left to send.
+pub fn bundle(game: &GameInfo) -> Result<Option<(Vec<u8>, String)>, String> {
+    let files = sidecar_paths(game)
+        .into_iter()
+        .filter_map(|(name, path)| fs::read(&path).ok().map(|bytes| (name, bytes)))
+        .filter(|(_, bytes)| !contains_local_paths(bytes, game))
+        .collect::<Vec<_>>();
+    if files.is_empty() {
+        return Ok(None);
+    }
+
+    let mut hasher = sha1_smol::Sha1::new();
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, bytes) in &files {
+        hasher.update(name.as_bytes());
+        hasher.update(bytes);
+        zip.start_file(name.as_str(), options)
+            .map_err(|e| format!("Failed to add {} to sidecar bundle: {}", name, e))?;
+        zip.write_all(bytes)
+            .map_err(|e| format!("Failed to write {} to sidecar bundle: {}", name, e))?;
+    }
+    let bytes = zip
+        .finish()
+        .map_err(|e| format!("Failed to finish sidecar bundle: {}", e))?
+        .into_inner();
+    Ok(Some((bytes, hasher.digest().to_string())))
+}
+
+// `bundle` on the blocking pool at background priority
+pub async fn bundle_in_background(game: GameInfo) -> Result<Option<(Vec<u8>, String)>, String> {
+    super::priority::spawn_background(move || bundle(&game))
+        .await
+        .map_err(|e| format!("Sidecar bundle task failed: {}", e))?
+}
@@ -0,0 +1,92 @@
+use super::models::{GameInfo, ManifestSighting};
+use super::utils::{read_json, write_json};
+use std::collections::HashMap;
+
+const SIGHTINGS_FILE: &str = "manifest_sightings.json";
+// Scans run every minute; `last_seen` only needs to be this precise
+const LAST_SEEN_RESOLUTION: chrono::Duration = chrono::Duration::hours(1);
+// Least recently seen builds are dropped beyond this
+const MAX_SIGHTINGS: usize = 5000;
+
+fn sighting_key(app_name: &str, manifest_hash: &str) -> String {
+    format!("{}:{}", app_name, manifest_hash.to_uppercase())
+}
+
+// Keyed by app name and manifest hash
+pub fn load_sightings() -> HashMap<String, ManifestSighting> {
+    read_json(SIGHTINGS_FILE).unwrap_or_default()
+}
+
+fn save_sightings(sightings: &HashMap<String, ManifestSighting>) {
+    write_json(SIGHTINGS_FILE, sightings, "manifest sightings");
+}
+
+// Notes every scanned (game, manifest hash) pair: new pairs get a first-seen
+// time, known ones have their last-seen time moved forward
+pub fn record(games: &[GameInfo]) {
+    let now = chrono::Utc::now();
+    let mut sightings = load_sightings();
+    let mut changed = false;
+    let known_apps = sightings
+        .values()
+        .map(|s| s.app_name.clone())
+        .collect::<std::collections::HashSet<_>>();
+
+    // Games on an offline drive weren't actually seen this time
+    for game in games
+        .iter()
+        .filter(|g| g.available && !g.manifest_hash.is_empty())
+    {
+        let key = sighting_key(&game.app_name, &game.manifest_hash);
+        match sightings.get_mut(&key) {
+            Some(sighting) => {
+                let stale = chrono::DateTime::parse_from_rfc3339(&sighting.last_seen)
+                    .map(|last| now.signed_duration_since(last) >= LAST_SEEN_RESOLUTION)
+                    .unwrap_or(true);
+                if stale {
+                    sighting.last_seen = now.to_rfc3339();
+                    changed = true;
+                }
+            }
+            None => {
+                sightings.insert(
+                    key,
+                    ManifestSighting {
+                        app_name: game.app_name.clone(),
+                        manifest_hash: game.manifest_hash.clone(),
+                        version: game.version.clone(),
+                        first_seen: now.to_rfc3339(),
+                        last_seen: now.to_rfc3339(),
+                        observed_arrival: known_apps.contains(&game.app_name),
+                    },
+                );
+                changed = true;
+            }
+        }
+    }
+
+    if sightings.len() > MAX_SIGHTINGS {
+        let mut by_age = sightings
+            .iter()
+            .map(|(key, s)| (s.last_seen.clone(), key.clone()))
+            .collect::<Vec<_>>();
+        by_age.sort();
+        for (_, key) in by_age.into_iter().take(sightings.len() - MAX_SIGHTINGS) {
+            sightings.remove(&key);
+        }
+        changed = true;
+    }
+
+    if changed {
+        save_sightings(&sightings);
+    }
+}
+
+// When this build of the game arrived on this machine; None for builds that
+// were already installed the first time the game was seen
+pub fn first_seen(app_name: &str, manifest_hash: &str) -> Option<String> {
+    load_sightings()
+        .remove(&sighting_key(app_name, manifest_hash))
+        .filter(|sighting| sighting.observed_arrival)
+        .map(|sighting| sighting.first_seen)
+}
@@ -0,0 +1,190 @@
+use super::utils::get_app_data_path;
+use ed25519_dalek::{Signer, SigningKey};
+use once_cell::sync::Lazy;
+use std::fs;
+use std::path::Path;
+
+const KEY_FILE: &str = "signing_key";
+
+// Signature attached to an upload; the server checks it against `public_key`
+pub struct UploadSignature {
+    pub public_key: String,
+    pub signed_at: String,
+    pub signature: String,
+}
+
+// Ed25519 key generated on first run. Its public half doubles as a stable
+// anonymous identity, so it is never rotated automatically. Uploads go
+// unsigned rather than under a new identity when the stored key can't be read
+// or a new one can't be saved.
+static SIGNING_KEY: Lazy<Option<SigningKey>> = Lazy::new(|| {
+    let path = get_app_data_path().join(KEY_FILE);
+    match fs::read(&path) {
+        Ok(stored) => {
+            let Some(seed) = decode_seed(&stored) else {
+                eprintln!(
+                    "Signing key {} is unreadable; uploads won't be signed",
+                    path.display()
+                );
+                return None;
+            };
+            secure_stored_seed(&path, &stored, &seed);
+            return Some(SigningKey::from_bytes(&seed));
+        }
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            eprintln!("Failed to read signing key: {}", e);
+            return None;
+        }
+        Err(_) => {}
+    }
+
+    let mut seed = [0u8; 32];
+    if let Err(e) = getrandom::getrandom(&mut seed) {
+        eprintln!("Failed to generate signing key: {}", e);
+        return None;
+    }
+    let _ = fs::create_dir_all(get_app_data_path());
+    if let Err(e) = store_seed(&path, &seed) {
+        eprintln!(
+            "Failed to persist signing key; uploads won't be signed: {}",
+            e
+        );
+        return None;
+    }
+    Some(SigningKey::from_bytes(&seed))
+});
+
+fn parse_seed(text: &[u8]) -> Option<[u8; 32]> {
+    let text = std::str::from_utf8(text).ok()?;
+    decode_hex(text.trim()).and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+}
+
+// The seed is kept as hex, readable only by the current user: mode 0600 on
+// Unix, encrypted with DPAPI on Windows
+#[cfg(not(target_os = "windows"))]
+fn store_seed(path: &Path, seed: &[u8; 32]) -> std::io::Result<()> {
+    super::utils::write_private_file(path, encode_hex(seed).as_bytes())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn decode_seed(stored: &[u8]) -> Option<[u8; 32]> {
+    parse_seed(stored)
+}
+
+// Keys written by earlier versions were world-readable
+#[cfg(not(target_os = "windows"))]
+fn secure_stored_seed(path: &Path, _stored: &[u8], _seed: &[u8; 32]) {
+    #[cfg(unix)]
+    if let Err(e) = fs::set_permissions(path, std::os::unix::fs::PermissionsExt::from_mode(0o600)) {
+        eprintln!("Failed to restrict signing key permissions: {}", e);
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn store_seed(path: &Path, seed: &[u8; 32]) -> std::io::Result<()> {
+    fs::write(path, dpapi::protect(encode_hex(seed).as_bytes())?)
+}
+
+#[cfg(target_os = "windows")]
+fn decode_seed(stored: &[u8]) -> Option<[u8; 32]> {
+    dpapi::unprotect(stored)
+        .and_then(|plain| parse_seed(&plain))
+        .or_else(|| parse_seed(stored))
+}
+
+// Keys written by earlier versions were plain hex
+#[cfg(target_os = "windows")]
+fn secure_stored_seed(path: &Path, stored: &[u8], seed: &[u8; 32]) {
+    if dpapi::unprotect(stored).is_none() {
+        if let Err(e) = store_seed(path, seed) {
+            eprintln!("Failed to encrypt signing key: {}", e);
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod dpapi {
+    use windows::core::PCWSTR;
+    use windows::Win32::Foundation::{LocalFree, HLOCAL};
+    use windows::Win32::Security::Cryptography::{
+        CryptProtectData, CryptUnprotectData, CRYPTPROTECT_UI_FORBIDDEN, CRYPT_INTEGER_BLOB,
+    };
+
+    fn blob(data: &[u8]) -> CRYPT_INTEGER_BLOB {
+        CRYPT_INTEGER_BLOB {
+            cbData: data.len() as u32,
+            pbData: data.as_ptr() as *mut u8,
+        }
+    }
+
+    // Copies out and frees a blob DPAPI allocated
+    unsafe fn take(output: CRYPT_INTEGER_BLOB) -> Vec<u8> {
+        let data = std::slice::from_raw_parts(output.pbData, output.cbData as usize).to_vec();
+        LocalFree(Some(HLOCAL(output.pbData.cast())));
+        data
+    }
+
+    // Encrypts `data` so only the current Windows user can decrypt it
+    pub fn protect(data: &[u8]) -> std::io::Result<Vec<u8>> {
+        let input = blob(data);
+        let mut output = CRYPT_INTEGER_BLOB::default();
+        unsafe {
+            CryptProtectData(
+                &input,
+                PCWSTR::null(),
+                None,
+                None,
+                None,
+                CRYPTPROTECT_UI_FORBIDDEN,
+                &mut output,
+            )
+            .map_err(std::io::Error::other)?;
+            Ok(take(output))
+        }
+    }
+
+    pub fn unprotect(data: &[u8]) -> Option<Vec<u8>> {
+        let input = blob(data);
+        let mut output = CRYPT_INTEGER_BLOB::default();
+        unsafe {
+            CryptUnprotectData(
+                &input,
+                None,
+                None,
+                None,
+                None,
+                CRYPTPROTECT_UI_FORBIDDEN,
+                &mut output,
+            )
+            .ok()?;
+            Some(take(output))
+        }
+    }
+}
+
+// Signs `<manifest_hash>:<signed_at>`; None only if no key could be created
+pub fn sign_upload(manifest_hash: &str) -> Option<UploadSignature> {
+    let key = SIGNING_KEY.as_ref()?;
+    let signed_at = chrono::Utc::now().to_rfc3339();
+    let signature = key.sign(format!("{}:{}", manifest_hash, signed_at).as_bytes());
+
+    Some(UploadSignature {
+        public_key: encode_hex(key.verifying_key().as_bytes()),
+        signed_at,
+        signature: encode_hex(&signature.to_bytes()),
+    })
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
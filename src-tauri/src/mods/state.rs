@@ -1,7 +1,135 @@
 use super::models::{GameInfo, GameMetadata, Settings};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, Mutex};
 
 pub type GameStore = Arc<Mutex<HashMap<String, GameInfo>>>;
-pub type MetadataCache = Arc<Mutex<HashMap<String, GameMetadata>>>;
-pub type SettingsState = Arc<Mutex<Settings>>;
\ No newline at end of file
+pub type MetadataCache = Arc<Mutex<MetadataCacheData>>;
+pub type SettingsState = Arc<Mutex<Settings>>;
+pub type ScanCancelFlag = Arc<AtomicBool>;
+// Tombstones: last-known state of uninstalled games, keyed by app name and
+// persisted so the library history survives restarts
+pub type UninstalledGames = Arc<Mutex<HashMap<String, GameInfo>>>;
+
+#[derive(Debug, Default)]
+pub struct MetadataCacheData {
+    pub entries: HashMap<String, GameMetadata>,
+    pub hits: u64,
+    pub misses: u64,
+    // Limits enforced with least-recently-used eviction; 0 means unlimited
+    pub max_entries: usize,
+    pub max_bytes: u64,
+    // Running footprint, so inserts don't re-measure every entry
+    sizes: HashMap<String, u64>,
+    total_bytes: u64,
+    // Use tick per entry and the reverse index, oldest first
+    last_used: HashMap<String, u64>,
+    recency: BTreeMap<u64, String>,
+    clock: u64,
+}
+
+impl MetadataCacheData {
+    pub fn new(entries: HashMap<String, GameMetadata>, max_entries: usize, max_bytes: u64) -> Self {
+        let mut cache = MetadataCacheData {
+            max_entries,
+            max_bytes,
+            ..Default::default()
+        };
+        for (catalog_item_id, metadata) in entries {
+            cache.store(catalog_item_id, metadata);
+        }
+        cache.enforce_limits();
+        cache
+    }
+
+    pub fn lookup(&mut self, catalog_item_id: &str) -> Option<GameMetadata> {
+        match self.entries.get(catalog_item_id).cloned() {
+            Some(metadata) => {
+                self.hits += 1;
+                self.touch(catalog_item_id);
+                Some(metadata)
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub fn insert(&mut self, catalog_item_id: String, metadata: GameMetadata) {
+        self.store(catalog_item_id, metadata);
+        self.enforce_limits();
+    }
+
+    pub fn remove(&mut self, catalog_item_id: &str) -> Option<GameMetadata> {
+        if let Some(tick) = self.last_used.remove(catalog_item_id) {
+            self.recency.remove(&tick);
+        }
+        if let Some(size) = self.sizes.remove(catalog_item_id) {
+            self.total_bytes -= size;
+        }
+        self.entries.remove(catalog_item_id)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.sizes.clear();
+        self.total_bytes = 0;
+        self.last_used.clear();
+        self.recency.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    pub fn set_limits(&mut self, max_entries: usize, max_bytes: u64) {
+        self.max_entries = max_entries;
+        self.max_bytes = max_bytes;
+        self.enforce_limits();
+    }
+
+    fn store(&mut self, catalog_item_id: String, metadata: GameMetadata) {
+        let size = entry_size(&metadata);
+        if let Some(previous) = self.sizes.insert(catalog_item_id.clone(), size) {
+            self.total_bytes -= previous;
+        }
+        self.total_bytes += size;
+        self.touch(&catalog_item_id);
+        self.entries.insert(catalog_item_id, metadata);
+    }
+
+    fn touch(&mut self, catalog_item_id: &str) {
+        self.clock += 1;
+        if let Some(previous) = self
+            .last_used
+            .insert(catalog_item_id.to_string(), self.clock)
+        {
+            self.recency.remove(&previous);
+        }
+        self.recency.insert(self.clock, catalog_item_id.to_string());
+    }
+
+    fn evict_least_recently_used(&mut self) -> Option<GameMetadata> {
+        let oldest = self.recency.values().next().cloned()?;
+        self.remove(&oldest)
+    }
+
+    fn enforce_limits(&mut self) {
+        while self.max_entries > 0 && self.entries.len() > self.max_entries {
+            if self.evict_least_recently_used().is_none() {
+                break;
+            }
+        }
+        while self.max_bytes > 0 && self.total_bytes > self.max_bytes {
+            if self.evict_least_recently_used().is_none() {
+                break;
+            }
+        }
+    }
+}
+
+// Approximate on-disk footprint of a cached entry
+fn entry_size(metadata: &GameMetadata) -> u64 {
+    serde_json::to_vec(metadata)
+        .map(|bytes| bytes.len() as u64)
+        .unwrap_or(0)
+}
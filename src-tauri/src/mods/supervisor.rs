@@ -0,0 +1,51 @@
+use super::utils::emit_log;
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tokio::time;
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(5);
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+// A task that ran at least this long before dying is considered healthy again
+const STABLE_RUNTIME: Duration = Duration::from_secs(600);
+
+// Runs a long-lived background task and respawns it with exponential backoff
+// whenever it panics or returns.
+pub fn spawn_supervised<F, Fut>(app_handle: AppHandle, name: &'static str, make_task: F)
+where
+    F: Fn() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    tauri::async_runtime::spawn(async move {
+        let mut backoff = INITIAL_BACKOFF;
+
+        loop {
+            let started = Instant::now();
+
+            match tauri::async_runtime::spawn(make_task()).await {
+                Ok(()) => emit_log(
+                    &app_handle,
+                    "WARN",
+                    &format!("Background task {} exited unexpectedly", name),
+                ),
+                Err(e) => emit_log(
+                    &app_handle,
+                    "ERROR",
+                    &format!("Background task {} crashed: {}", name, e),
+                ),
+            }
+
+            if started.elapsed() >= STABLE_RUNTIME {
+                backoff = INITIAL_BACKOFF;
+            }
+
+            emit_log(
+                &app_handle,
+                "INFO",
+                &format!("Restarting {} in {} seconds", name, backoff.as_secs()),
+            );
+            time::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    });
+}
@@ -0,0 +1,199 @@
+use super::models::TelemetryReport;
+use super::state::SettingsState;
+use super::utils::{get_app_data_path, read_json, user_agent, write_json};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time;
+
+const TELEMETRY_URL: &str = "https://egdata-builds-api.snpm.workers.dev/telemetry";
+const COUNTERS_FILE: &str = "telemetry_counters.json";
+const REPORT_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+// How often changed counters are written out, so restarts don't lose them
+const SAVE_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+// Aggregate counters only; nothing here identifies the user or their library
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct TelemetryCounters {
+    scans: u64,
+    scan_duration_ms_total: u64,
+    uploads_succeeded: u64,
+    uploads_already_present: u64,
+    uploads_failed: u64,
+    api_requests: u64,
+    api_errors: u64,
+    // Start of the period these counters cover (RFC 3339)
+    period_started: Option<String>,
+}
+
+// Mirrors `Settings::telemetry_enabled`; nothing is counted while it's off
+static ENABLED: AtomicBool = AtomicBool::new(false);
+// Counters changed since they were last saved
+static DIRTY: AtomicBool = AtomicBool::new(false);
+
+static COUNTERS: Lazy<Mutex<TelemetryCounters>> = Lazy::new(|| Mutex::new(load_counters()));
+
+fn get_counters_path() -> PathBuf {
+    get_app_data_path().join(COUNTERS_FILE)
+}
+
+fn load_counters() -> TelemetryCounters {
+    read_json(COUNTERS_FILE).unwrap_or_default()
+}
+
+fn save_counters(counters: &TelemetryCounters) {
+    write_json(COUNTERS_FILE, counters, "telemetry counters");
+}
+
+// Turning telemetry off discards whatever was counted so far
+pub fn set_enabled(enabled: bool) {
+    let was_enabled = ENABLED.swap(enabled, Ordering::SeqCst);
+    if was_enabled && !enabled {
+        if let Ok(mut counters) = COUNTERS.lock() {
+            *counters = TelemetryCounters::default();
+        }
+        DIRTY.store(false, Ordering::SeqCst);
+        let _ = fs::remove_file(get_counters_path());
+    }
+}
+
+fn with_counters(update: impl FnOnce(&mut TelemetryCounters)) {
+    if !ENABLED.load(Ordering::SeqCst) {
+        return;
+    }
+    if let Ok(mut counters) = COUNTERS.lock() {
+        if counters.period_started.is_none() {
+            counters.period_started = Some(chrono::Utc::now().to_rfc3339());
+        }
+        update(&mut counters);
+        DIRTY.store(true, Ordering::SeqCst);
+    }
+}
+
+pub fn record_scan(duration: Duration) {
+    with_counters(|c| {
+        c.scans += 1;
+        c.scan_duration_ms_total += duration.as_millis() as u64;
+    });
+}
+
+pub fn record_upload(status: &str) {
+    with_counters(|c| match status {
+        "uploaded" => c.uploads_succeeded += 1,
+        "already_uploaded" | "skipped_duplicate" => c.uploads_already_present += 1,
+        _ => c.uploads_failed += 1,
+    });
+}
+
+pub fn record_api_request(succeeded: bool) {
+    with_counters(|c| {
+        c.api_requests += 1;
+        if !succeeded {
+            c.api_errors += 1;
+        }
+    });
+}
+
+// Exactly what would be sent on the next report
+pub fn build_report() -> TelemetryReport {
+    let counters = COUNTERS.lock().map(|c| c.clone()).unwrap_or_default();
+
+    TelemetryReport {
+        client_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        scans: counters.scans,
+        average_scan_ms: counters
+            .scan_duration_ms_total
+            .checked_div(counters.scans)
+            .unwrap_or(0),
+        uploads_succeeded: counters.uploads_succeeded,
+        uploads_already_present: counters.uploads_already_present,
+        uploads_failed: counters.uploads_failed,
+        api_requests: counters.api_requests,
+        api_error_rate: if counters.api_requests > 0 {
+            counters.api_errors as f64 / counters.api_requests as f64
+        } else {
+            0.0
+        },
+    }
+}
+
+async fn submit_report(report: &TelemetryReport) -> Result<(), String> {
+    // Deliberately not the shared client: telemetry must not carry the client id
+    let client = reqwest::Client::builder()
+        .user_agent(user_agent())
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| format!("Failed to create telemetry client: {}", e))?;
+
+    let resp = client
+        .post(TELEMETRY_URL)
+        .json(report)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send telemetry: {}", e))?;
+
+    if resp.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("Telemetry rejected: {}", resp.status()))
+    }
+}
+
+// Whether the counters cover a full report period yet
+fn report_due(counters: &TelemetryCounters) -> bool {
+    counters
+        .period_started
+        .as_deref()
+        .and_then(|started| chrono::DateTime::parse_from_rfc3339(started).ok())
+        .is_some_and(|started| {
+            chrono::Utc::now()
+                .signed_duration_since(started)
+                .to_std()
+                .is_ok_and(|elapsed| elapsed >= REPORT_INTERVAL)
+        })
+}
+
+pub async fn periodic_report(settings: SettingsState) {
+    let mut interval = time::interval(SAVE_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let enabled = settings
+            .lock()
+            .map(|s| s.telemetry_enabled)
+            .unwrap_or(false);
+        set_enabled(enabled);
+        if !enabled {
+            continue;
+        }
+
+        let counters = COUNTERS.lock().map(|c| c.clone()).unwrap_or_default();
+        if DIRTY.swap(false, Ordering::SeqCst) {
+            save_counters(&counters);
+        }
+        if !report_due(&counters) {
+            continue;
+        }
+
+        let report = build_report();
+        match submit_report(&report).await {
+            Ok(()) => {
+                if let Ok(mut counters) = COUNTERS.lock() {
+                    *counters = TelemetryCounters {
+                        period_started: Some(chrono::Utc::now().to_rfc3339()),
+                        ..TelemetryCounters::default()
+                    };
+                    save_counters(&counters);
+                }
+            }
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+}
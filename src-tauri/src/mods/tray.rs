@@ -0,0 +1,186 @@
+use super::commands;
+use super::models::Settings;
+use super::pause;
+use super::state::{GameStore, SettingsState};
+use super::utils::emit_log;
+use tauri::image::Image;
+use tauri::menu::{CheckMenuItemBuilder, Menu, MenuBuilder, MenuItemBuilder, SubmenuBuilder};
+use tauri::{AppHandle, Manager, Theme, Wry};
+
+pub const TRAY_ID: &str = "main";
+// Menu ids of profile entries are this prefix plus the profile name
+pub const PROFILE_MENU_PREFIX: &str = "profile:";
+pub const PAUSE_MENU_ID: &str = "pause_uploads";
+// Per-game actions; ids are the prefix plus the game's app name
+pub const GAME_UPLOAD_PREFIX: &str = "game-upload:";
+pub const GAME_OPEN_PREFIX: &str = "game-open:";
+
+// Monochrome variants: a light glyph for dark taskbars and vice versa
+const TRAY_ICON_DARK: &[u8] = include_bytes!("../../icons/tray-dark.png");
+const TRAY_ICON_LIGHT: &[u8] = include_bytes!("../../icons/tray-light.png");
+
+pub fn icon_for_theme(theme: Theme) -> Option<Image<'static>> {
+    let bytes = match theme {
+        Theme::Light => TRAY_ICON_LIGHT,
+        _ => TRAY_ICON_DARK,
+    };
+    match Image::from_bytes(bytes) {
+        Ok(image) => Some(image),
+        Err(e) => {
+            eprintln!("Failed to load tray icon: {}", e);
+            None
+        }
+    }
+}
+
+// macOS recolors template icons itself, so only other platforms swap variants
+pub fn apply_theme(app_handle: &AppHandle, theme: Theme) {
+    if cfg!(target_os = "macos") {
+        return;
+    }
+    let (Some(tray), Some(icon)) = (app_handle.tray_by_id(TRAY_ID), icon_for_theme(theme)) else {
+        return;
+    };
+    if let Err(e) = tray.set_icon(Some(icon)) {
+        eprintln!("Failed to update tray icon: {}", e);
+    }
+}
+
+pub fn build_menu(app_handle: &AppHandle) -> tauri::Result<Menu<Wry>> {
+    let show_item = MenuItemBuilder::new("Show").id("show").build(app_handle)?;
+    let hide_item = MenuItemBuilder::new("Hide").id("hide").build(app_handle)?;
+    let quit_item = MenuItemBuilder::new("Quit").id("quit").build(app_handle)?;
+
+    let (profiles, active_profile) = app_handle
+        .state::<SettingsState>()
+        .lock()
+        .map(|s| (s.profiles.clone(), s.active_profile.clone()))
+        .unwrap_or_default();
+
+    let pause_item = CheckMenuItemBuilder::new("Pause uploads")
+        .id(PAUSE_MENU_ID)
+        .checked(pause::is_paused())
+        .build(app_handle)?;
+
+    let mut menu = MenuBuilder::new(app_handle)
+        .item(&show_item)
+        .item(&hide_item)
+        .separator()
+        .item(&pause_item);
+    if !profiles.is_empty() {
+        let mut submenu = SubmenuBuilder::new(app_handle, "Profile");
+        for profile in &profiles {
+            let item = CheckMenuItemBuilder::new(&profile.name)
+                .id(format!("{}{}", PROFILE_MENU_PREFIX, profile.name))
+                .checked(active_profile.as_deref() == Some(profile.name.as_str()))
+                .build(app_handle)?;
+            submenu = submenu.item(&item);
+        }
+        menu = menu.separator().item(&submenu.build()?);
+    }
+
+    let mut games = app_handle
+        .state::<GameStore>()
+        .lock()
+        .map(|games_lock| games_lock.values().cloned().collect::<Vec<_>>())
+        .unwrap_or_default();
+    if !games.is_empty() {
+        games.sort_by(|a, b| a.display_name.cmp(&b.display_name));
+        let mut games_menu = SubmenuBuilder::new(app_handle, "Games");
+        for game in &games {
+            let upload_item = MenuItemBuilder::new("Upload manifest")
+                .id(format!("{}{}", GAME_UPLOAD_PREFIX, game.app_name))
+                .enabled(game.available)
+                .build(app_handle)?;
+            let open_item = MenuItemBuilder::new("Open install folder")
+                .id(format!("{}{}", GAME_OPEN_PREFIX, game.app_name))
+                .enabled(game.available)
+                .build(app_handle)?;
+            let game_menu = SubmenuBuilder::new(app_handle, &game.display_name)
+                .item(&upload_item)
+                .item(&open_item)
+                .build()?;
+            games_menu = games_menu.item(&game_menu);
+        }
+        menu = menu.separator().item(&games_menu.build()?);
+    }
+
+    menu.separator().item(&quit_item).build()
+}
+
+// Runs a per-game menu action through the same commands the window uses.
+// Returns false for ids that aren't game actions.
+pub fn handle_game_action(app_handle: &AppHandle, id: &str) -> bool {
+    let (app_name, upload) = if let Some(app_name) = id.strip_prefix(GAME_UPLOAD_PREFIX) {
+        (app_name, true)
+    } else if let Some(app_name) = id.strip_prefix(GAME_OPEN_PREFIX) {
+        (app_name, false)
+    } else {
+        return false;
+    };
+    let Some(game) = app_handle
+        .state::<GameStore>()
+        .lock()
+        .ok()
+        .and_then(|games_lock| games_lock.get(app_name).cloned())
+    else {
+        return true;
+    };
+
+    if upload {
+        let app_handle = app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            // Outcomes are logged by the command itself
+            let _ = commands::upload_manifest(
+                app_handle.clone(),
+                game.catalog_item_id,
+                game.installation_guid,
+                app_handle.state(),
+                app_handle.state(),
+            )
+            .await;
+        });
+    } else if let Err(e) = commands::open_directory(&game.install_location) {
+        emit_log(app_handle, "ERROR", &e);
+    }
+    true
+}
+
+// Names whatever automatic work has been turned off or paused
+pub fn tooltip(settings: &Settings) -> String {
+    if pause::is_paused() {
+        return "EGData Client (uploads paused)".to_string();
+    }
+    let off = [
+        (!settings.auto_scan_enabled).then_some("scans"),
+        (!settings.auto_upload_enabled).then_some("uploads"),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+    if off.is_empty() {
+        "EGData Client".to_string()
+    } else {
+        format!("EGData Client (automatic {} off)", off.join(" and "))
+    }
+}
+
+// Rebuilds the menu and tooltip after settings change
+pub fn refresh(app_handle: &AppHandle) {
+    let Some(tray) = app_handle.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    if let Ok(settings) = app_handle.state::<SettingsState>().lock() {
+        if let Err(e) = tray.set_tooltip(Some(tooltip(&settings))) {
+            eprintln!("Failed to update tray tooltip: {}", e);
+        }
+    }
+    match build_menu(app_handle) {
+        Ok(menu) => {
+            if let Err(e) = tray.set_menu(Some(menu)) {
+                eprintln!("Failed to update tray menu: {}", e);
+            }
+        }
+        Err(e) => eprintln!("Failed to build tray menu: {}", e),
+    }
+}
@@ -1,21 +1,96 @@
-use super::models::{LogEvent, Settings};
+use super::models::{GameInfo, GameMetadata, LogEvent, PathProbe, Settings};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::collections::{HashMap, VecDeque};
 use std::fs::{self, File};
-use std::io::Read;
-use tauri::{AppHandle, Emitter};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::AppHandle;
+
+// Number of log events kept in memory for diagnostics bundles
+const RECENT_LOG_CAPACITY: usize = 500;
+
+static RECENT_LOGS: Lazy<Mutex<VecDeque<LogEvent>>> =
+    Lazy::new(|| Mutex::new(VecDeque::with_capacity(RECENT_LOG_CAPACITY)));
+static LOG_SEQ: AtomicU64 = AtomicU64::new(0);
 
 pub fn emit_log(app_handle: &AppHandle, level: &str, message: &str) {
     let log_event = LogEvent {
         level: level.to_string(),
         message: message.to_string(),
-        timestamp: chrono::Utc::now().format("%H:%M:%S").to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        seq: LOG_SEQ.fetch_add(1, Ordering::SeqCst),
     };
-    let _ = app_handle.emit("log-event", &log_event);
+    if let Ok(mut recent) = RECENT_LOGS.lock() {
+        if recent.len() == RECENT_LOG_CAPACITY {
+            recent.pop_front();
+        }
+        recent.push_back(log_event.clone());
+    }
+    super::log_emitter::push(app_handle, log_event);
+
+    if level == "ERROR" {
+        super::activity::record("error", message, None);
+    }
+}
+
+pub fn get_recent_logs() -> Vec<LogEvent> {
+    RECENT_LOGS
+        .lock()
+        .map(|recent| recent.iter().cloned().collect())
+        .unwrap_or_default()
 }
 
 const SETTINGS_FILE: &str = "settings.json";
+const METADATA_CACHE_FILE: &str = "metadata_cache.json";
+const CLIENT_ID_FILE: &str = "client_id";
+const UNINSTALLED_GAMES_FILE: &str = "uninstalled_games.json";
+const LIBRARY_CACHE_FILE: &str = "library_cache.json";
+
+// Flatpak exposes the app id to sandboxed processes and mounts `/.flatpak-info`
+pub fn is_flatpak() -> bool {
+    std::env::var_os("FLATPAK_ID").is_some() || Path::new("/.flatpak-info").exists()
+}
+
+#[cfg(target_os = "linux")]
+const AUTOSTART_DESKTOP_FILE: &str = "egdata-client.desktop";
+
+// Console programs started by the GUI process flash a console window unless
+// they're created without one
+#[cfg(target_os = "windows")]
+pub fn hidden_command(program: &str) -> std::process::Command {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    let mut command = std::process::Command::new(program);
+    command.creation_flags(CREATE_NO_WINDOW);
+    command
+}
+
+// Writes a file only the current user can read: 0600 on Unix, while Windows
+// profile folders are already private to their user
+pub fn write_private_file(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path)?;
+    // The mode above only applies when the file is created
+    #[cfg(unix)]
+    file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+    file.write_all(contents)
+}
 
 // Auto-start functionality
-pub fn setup_auto_start() -> Result<(), Box<dyn std::error::Error>> {
+pub fn setup_auto_start(settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    // Only the Linux entry is optional
+    #[cfg(not(target_os = "linux"))]
+    let _ = settings;
+
     #[cfg(target_os = "windows")]
     {
         use std::process::Command;
@@ -54,10 +129,118 @@ pub fn setup_auto_start() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    // Linux desktops don't expect apps to add themselves to autostart, so it's
+    // opt-in; turning it off removes the entry written earlier
+    #[cfg(target_os = "linux")]
+    {
+        let autostart_dir = dirs::config_dir()
+            .ok_or("Could not determine config directory")?
+            .join("autostart");
+        if !settings.linux_autostart {
+            match fs::remove_file(autostart_dir.join(AUTOSTART_DESKTOP_FILE)) {
+                Ok(()) => println!("Auto-start disabled"),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => eprintln!("Failed to remove auto-start entry: {}", e),
+            }
+        } else if is_flatpak() {
+            request_background_portal_autostart();
+        } else {
+            fs::create_dir_all(&autostart_dir)?;
+            let exe_path = std::env::current_exe()?;
+            let entry = format!(
+                "[Desktop Entry]\nType=Application\nName=EGData Client\nExec=\"{}\"\nX-GNOME-Autostart-enabled=true\n",
+                exe_path.display()
+            );
+            fs::write(autostart_dir.join(AUTOSTART_DESKTOP_FILE), entry)?;
+            println!("Auto-start enabled successfully");
+        }
+    }
+
     Ok(())
 }
 
+// Sandboxed apps can't write to the host's autostart directory; the Background
+// portal asks the desktop to register the autostart entry on our behalf
+#[cfg(target_os = "linux")]
+fn request_background_portal_autostart() {
+    let app_id = std::env::var("FLATPAK_ID").unwrap_or_else(|_| "com.egdata.client".to_string());
+    let options = format!(
+        "{{'reason': <'Keep uploading manifests in the background'>, 'autostart': <true>, 'commandline': <['{}']>, 'dbus-activatable': <false>}}",
+        app_id
+    );
+
+    let output = std::process::Command::new("gdbus")
+        .args([
+            "call",
+            "--session",
+            "--dest",
+            "org.freedesktop.portal.Desktop",
+            "--object-path",
+            "/org/freedesktop/portal/desktop",
+            "--method",
+            "org.freedesktop.portal.Background.RequestBackground",
+            "",
+            &options,
+        ])
+        .output();
+
+    match output {
+        Ok(result) if result.status.success() => {
+            println!("Auto-start requested via background portal");
+        }
+        Ok(result) => {
+            eprintln!(
+                "Background portal rejected auto-start: {}",
+                String::from_utf8_lossy(&result.stderr)
+            );
+        }
+        Err(e) => {
+            eprintln!("Error requesting auto-start from background portal: {}", e);
+        }
+    }
+}
+
+// None when auto-start isn't supported on this platform
+pub fn is_auto_start_enabled() -> Option<bool> {
+    #[cfg(target_os = "windows")]
+    {
+        use std::process::Command;
+
+        let output = Command::new("reg")
+            .args([
+                "query",
+                "HKEY_CURRENT_USER\\Software\\Microsoft\\Windows\\CurrentVersion\\Run",
+                "/v",
+                "EGDataClient",
+            ])
+            .output()
+            .ok()?;
+        Some(output.status.success())
+    }
+    #[cfg(target_os = "linux")]
+    {
+        // The portal's autostart entry lives outside the sandbox where we can't see it
+        if is_flatpak() {
+            return None;
+        }
+        let path = dirs::config_dir()?
+            .join("autostart")
+            .join(AUTOSTART_DESKTOP_FILE);
+        Some(path.exists())
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "linux")))]
+    {
+        None
+    }
+}
+
 pub fn get_app_data_path() -> std::path::PathBuf {
+    // On Linux follow XDG, which Flatpak points at the per-app `~/.var/app/<id>` tree
+    #[cfg(target_os = "linux")]
+    if let Some(data_dir) = dirs::data_dir() {
+        return data_dir.join("egdata-client");
+    }
+
     // Use standard system app data directory
     let mut path = std::env::var("APPDATA")
         .map(std::path::PathBuf::from)
@@ -66,6 +249,105 @@ pub fn get_app_data_path() -> std::path::PathBuf {
     path
 }
 
+// Reads a JSON state file from the app data directory; None if it is missing
+// or no longer parses
+pub fn read_json<T: serde::de::DeserializeOwned>(file_name: &str) -> Option<T> {
+    fs::read_to_string(get_app_data_path().join(file_name))
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+}
+
+// Writes a JSON state file into the app data directory; `what` names it in
+// the logged error
+pub fn write_json<T: Serialize + ?Sized>(file_name: &str, value: &T, what: &str) {
+    if let Err(e) = fs::create_dir_all(get_app_data_path()) {
+        eprintln!("Failed to create app data directory: {}", e);
+        return;
+    }
+    match serde_json::to_string(value) {
+        Ok(json) => {
+            if let Err(e) = fs::write(get_app_data_path().join(file_name), json) {
+                eprintln!("Failed to write {}: {}", what, e);
+            }
+        }
+        Err(e) => eprintln!("Failed to serialize {}: {}", what, e),
+    }
+}
+
+// Linux builds used to keep their data in `egdata-client` under the working
+// directory (usually home) before following XDG. Moves it over once, when the
+// XDG directory doesn't exist yet.
+#[cfg(target_os = "linux")]
+pub fn migrate_legacy_data_dir() {
+    let data_dir = get_app_data_path();
+    if data_dir.exists() {
+        return;
+    }
+    // settings.json tells a data directory apart from e.g. a source checkout
+    let Some(legacy_dir) = [std::env::current_dir().ok(), dirs::home_dir()]
+        .into_iter()
+        .flatten()
+        .map(|dir| dir.join("egdata-client"))
+        .find(|dir| dir.join(SETTINGS_FILE).is_file())
+    else {
+        return;
+    };
+
+    if let Some(parent) = data_dir.parent() {
+        if let Err(e) = fs::create_dir_all(parent) {
+            eprintln!("Failed to create {}: {}", parent.display(), e);
+            return;
+        }
+    }
+    // Falls back to copying when the two are on different filesystems
+    let moved = fs::rename(&legacy_dir, &data_dir).or_else(|_| {
+        copy_dir(&legacy_dir, &data_dir)?;
+        fs::remove_dir_all(&legacy_dir)
+    });
+    match moved {
+        Ok(()) => println!(
+            "Moved app data from {} to {}",
+            legacy_dir.display(),
+            data_dir.display()
+        ),
+        Err(e) => eprintln!(
+            "Failed to move app data from {}: {}",
+            legacy_dir.display(),
+            e
+        ),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn get_settings_path() -> std::path::PathBuf {
+    get_app_data_path().join(SETTINGS_FILE)
+}
+
+// Strict variant of `load_settings_from_file` for reloads: reports parse and
+// validation errors instead of falling back to defaults
+pub fn read_settings_file() -> Result<Settings, String> {
+    let contents = fs::read_to_string(get_settings_path())
+        .map_err(|e| format!("Failed to read settings file: {}", e))?;
+    let settings = serde_json::from_str::<Settings>(&contents)
+        .map_err(|e| format!("Invalid settings file: {}", e))?;
+    settings.validate()?;
+    Ok(settings)
+}
+
 pub fn load_settings_from_file() -> Settings {
     let app_data_path = get_app_data_path();
     let settings_path = app_data_path.join(SETTINGS_FILE);
@@ -78,14 +360,7 @@ pub fn load_settings_from_file() -> Settings {
             }
         }
     }
-    // Default settings
-    Settings {
-        concurrency: 3,
-        upload_speed_limit: 0,
-        allowed_environments: vec!["Live".to_string(), "Production".to_string()],
-        upload_interval: 60,      // Default to 60 minutes
-        scan_interval_minutes: 1, // Default to 1 minute
-    }
+    Settings::default()
 }
 
 pub fn save_settings_to_file(settings: &Settings) {
@@ -100,4 +375,465 @@ pub fn save_settings_to_file(settings: &Settings) {
     if let Ok(json) = serde_json::to_string_pretty(settings) {
         let _ = fs::write(settings_path, json);
     }
-}
\ No newline at end of file
+}
+
+pub fn get_metadata_cache_path() -> std::path::PathBuf {
+    get_app_data_path().join(METADATA_CACHE_FILE)
+}
+
+pub fn load_metadata_cache_from_file() -> HashMap<String, GameMetadata> {
+    fs::read_to_string(get_metadata_cache_path())
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_metadata_cache_to_file(entries: &HashMap<String, GameMetadata>) {
+    let app_data_path = get_app_data_path();
+    if let Err(e) = fs::create_dir_all(&app_data_path) {
+        eprintln!("Failed to create app data directory: {}", e);
+        return;
+    }
+
+    if let Ok(json) = serde_json::to_string(entries) {
+        let _ = fs::write(get_metadata_cache_path(), json);
+    }
+}
+
+fn get_uninstalled_games_path() -> std::path::PathBuf {
+    get_app_data_path().join(UNINSTALLED_GAMES_FILE)
+}
+
+// `GameInfo` leaves `item_content` out (it would bloat every IPC payload), but
+// for a tombstone it's all that's left of the deleted `.item`
+#[derive(Serialize, Deserialize)]
+struct StoredTombstone {
+    #[serde(flatten)]
+    game: GameInfo,
+    #[serde(default)]
+    item_content: String,
+}
+
+pub fn load_uninstalled_games_from_file() -> HashMap<String, GameInfo> {
+    fs::read_to_string(get_uninstalled_games_path())
+        .ok()
+        .and_then(|contents| {
+            serde_json::from_str::<HashMap<String, StoredTombstone>>(&contents).ok()
+        })
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(app_name, stored)| {
+            let mut game = stored.game;
+            game.item_content = stored.item_content;
+            (app_name, game)
+        })
+        .collect()
+}
+
+pub fn save_uninstalled_games_to_file(games: &HashMap<String, GameInfo>) {
+    let app_data_path = get_app_data_path();
+    if let Err(e) = fs::create_dir_all(&app_data_path) {
+        eprintln!("Failed to create app data directory: {}", e);
+        return;
+    }
+
+    let stored = games
+        .iter()
+        .map(|(app_name, game)| {
+            (
+                app_name,
+                StoredTombstone {
+                    item_content: game.item_content.clone(),
+                    game: game.clone(),
+                },
+            )
+        })
+        .collect::<HashMap<_, _>>();
+    if let Ok(json) = serde_json::to_string(&stored) {
+        let _ = fs::write(get_uninstalled_games_path(), json);
+    }
+}
+
+fn get_library_cache_path() -> std::path::PathBuf {
+    get_app_data_path().join(LIBRARY_CACHE_FILE)
+}
+
+// Library from the last scan, shown at startup until the first scan finishes
+pub fn load_library_cache_from_file() -> HashMap<String, GameInfo> {
+    fs::read_to_string(get_library_cache_path())
+        .ok()
+        .and_then(|contents| serde_json::from_str::<Vec<GameInfo>>(&contents).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|game| (game.app_name.clone(), game))
+        .collect()
+}
+
+pub fn save_library_cache_to_file(games: &[GameInfo]) {
+    let app_data_path = get_app_data_path();
+    if let Err(e) = fs::create_dir_all(&app_data_path) {
+        eprintln!("Failed to create app data directory: {}", e);
+        return;
+    }
+
+    if let Ok(json) = serde_json::to_string(games) {
+        let _ = fs::write(get_library_cache_path(), json);
+    }
+}
+
+// Anonymous per-installation identifier, generated on first run
+static CLIENT_ID: Lazy<String> = Lazy::new(|| {
+    let path = get_app_data_path().join(CLIENT_ID_FILE);
+    if let Ok(existing) = fs::read_to_string(&path) {
+        let existing = existing.trim();
+        if !existing.is_empty() {
+            return existing.to_string();
+        }
+    }
+
+    let client_id = uuid::Uuid::new_v4().to_string();
+    let _ = fs::create_dir_all(get_app_data_path());
+    if let Err(e) = fs::write(&path, &client_id) {
+        eprintln!("Failed to persist client id: {}", e);
+    }
+    client_id
+});
+
+pub fn get_client_id() -> &'static str {
+    CLIENT_ID.as_str()
+}
+
+pub fn user_agent() -> String {
+    format!(
+        "egdata-client/{} ({}; {})",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    )
+}
+
+// Every client talking to egdata identifies itself with a versioned User-Agent
+// and the anonymous client id
+pub fn base_client_builder() -> reqwest::ClientBuilder {
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert(
+        "X-Client-Version",
+        reqwest::header::HeaderValue::from_static(env!("CARGO_PKG_VERSION")),
+    );
+    if let Ok(client_id) = reqwest::header::HeaderValue::from_str(get_client_id()) {
+        headers.insert("X-Client-Id", client_id);
+    }
+
+    reqwest::Client::builder()
+        .user_agent(user_agent())
+        .default_headers(headers)
+}
+
+// TLS settings an upload client was built with
+type UploadClientKey = (bool, Option<String>);
+
+// Reused across requests for connection pooling; rebuilt when the TLS settings change
+static UPLOAD_CLIENT: Lazy<Mutex<Option<(UploadClientKey, reqwest::Client)>>> =
+    Lazy::new(|| Mutex::new(None));
+
+// The client used for manifest uploads and existence checks, with the TLS settings applied
+pub fn upload_client(settings: &Settings) -> Result<reqwest::Client, String> {
+    let key = (
+        settings.upload_require_tls12,
+        settings
+            .upload_pinned_cert_path
+            .clone()
+            .filter(|p| !p.is_empty()),
+    );
+    let mut cached = UPLOAD_CLIENT.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((cached_key, client)) = cached.as_ref() {
+        if *cached_key == key {
+            return Ok(client.clone());
+        }
+    }
+    let client = build_upload_client(settings)?;
+    *cached = Some((key, client.clone()));
+    Ok(client)
+}
+
+fn build_upload_client(settings: &Settings) -> Result<reqwest::Client, String> {
+    let mut builder = base_client_builder().https_only(true);
+
+    if settings.upload_require_tls12 {
+        builder = builder.min_tls_version(reqwest::tls::Version::TLS_1_2);
+    }
+
+    if let Some(cert_path) = settings
+        .upload_pinned_cert_path
+        .as_deref()
+        .filter(|p| !p.is_empty())
+    {
+        let pem = fs::read(cert_path)
+            .map_err(|e| format!("Failed to read pinned certificate {}: {}", cert_path, e))?;
+        let cert = reqwest::Certificate::from_pem(&pem)
+            .map_err(|e| format!("Invalid pinned certificate {}: {}", cert_path, e))?;
+        builder = builder
+            .tls_built_in_root_certs(false)
+            .add_root_certificate(cert);
+    }
+
+    builder
+        .build()
+        .map_err(|e| format!("Failed to create upload client: {}", e))
+}
+
+// Formats a request error, calling out TLS/certificate failures explicitly since
+// they usually mean a pinning mismatch or an intercepting proxy
+pub fn describe_request_error(e: &reqwest::Error) -> String {
+    let mut details = e.to_string();
+    let mut source = std::error::Error::source(e);
+    while let Some(inner) = source {
+        details.push_str(": ");
+        details.push_str(&inner.to_string());
+        source = inner.source();
+    }
+
+    let lowered = details.to_lowercase();
+    if ["certificate", "tls", "ssl", "handshake"]
+        .iter()
+        .any(|needle| lowered.contains(needle))
+    {
+        format!(
+            "TLS error (certificate rejected or connection intercepted): {}",
+            details
+        )
+    } else {
+        details
+    }
+}
+
+// How long a single filesystem probe may block before its drive is treated as
+// unavailable (sleeping NAS, disconnected USB disk, ...)
+pub const PATH_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub async fn probe_path(path: PathBuf, timeout: Duration) -> PathProbe {
+    match tokio::time::timeout(
+        timeout,
+        tokio::task::spawn_blocking(move || fs::metadata(&path)),
+    )
+    .await
+    {
+        Ok(Ok(Ok(_))) => PathProbe::Available,
+        Ok(Ok(Err(e))) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+            PathProbe::AccessDenied
+        }
+        Ok(Ok(Err(_))) | Ok(Err(_)) => PathProbe::Missing,
+        Err(_) => PathProbe::TimedOut,
+    }
+}
+
+// Backoff between attempts while the launcher is still writing a file
+const STABLE_READ_BACKOFF: [Duration; 4] = [
+    Duration::from_millis(100),
+    Duration::from_millis(250),
+    Duration::from_millis(500),
+    Duration::from_millis(1000),
+];
+
+// Size and mtime, which change whenever the launcher rewrites the file
+pub fn file_fingerprint(path: &Path) -> std::io::Result<(u64, Option<std::time::SystemTime>)> {
+    let metadata = fs::metadata(path)?;
+    Ok((metadata.len(), metadata.modified().ok()))
+}
+
+// The launcher rewrites `.item`/`.manifest` files during updates, holding a
+// lock (Windows) or leaving them half-written. Reads until the file's size and
+// mtime are the same before and after, backing off on sharing violations.
+pub fn read_stable_file(path: &Path) -> std::io::Result<Vec<u8>> {
+    let mut last_error = None;
+    for delay in std::iter::once(Duration::ZERO).chain(STABLE_READ_BACKOFF) {
+        std::thread::sleep(delay);
+        let attempt = file_fingerprint(path).and_then(|before| {
+            let bytes = fs::read(path)?;
+            Ok((before, bytes, file_fingerprint(path)?))
+        });
+        match attempt {
+            Ok((before, bytes, after)) if before == after && bytes.len() as u64 == after.0 => {
+                return Ok(bytes)
+            }
+            Ok(_) => {
+                last_error = Some(std::io::Error::other(
+                    "file kept changing while being read (launcher update in progress?)",
+                ))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Err(e),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| std::io::Error::other("file could not be read")))
+}
+
+// Reading a manifest takes longer than a stat on a slow but working drive, so
+// the read gets the probe timeout plus time for the bytes at this rate
+const MIN_READ_BYTES_PER_SECOND: u64 = 1024 * 1024;
+
+pub fn read_timeout(size: u64) -> Duration {
+    PATH_PROBE_TIMEOUT + Duration::from_secs(size / MIN_READ_BYTES_PER_SECOND)
+}
+
+fn timed_out_reading(display: &str) -> String {
+    format!("Timed out reading {} (drive unavailable?)", display)
+}
+
+// The file's size, stat'ed within the probe timeout
+pub async fn file_size_with_timeout(path: PathBuf) -> Result<u64, String> {
+    let display = path.display().to_string();
+    let stat = tokio::task::spawn_blocking(move || fs::metadata(&path));
+    match tokio::time::timeout(PATH_PROBE_TIMEOUT, stat).await {
+        Ok(Ok(Ok(metadata))) => Ok(metadata.len()),
+        Ok(Ok(Err(e))) => Err(format!("Failed to read {}: {}", display, e)),
+        Ok(Err(e)) => Err(format!("Failed to read {}: {}", display, e)),
+        Err(_) => Err(timed_out_reading(&display)),
+    }
+}
+
+// Stats the file within the probe timeout, then reads it within a timeout
+// scaled to its size
+pub async fn read_file_with_timeout(path: PathBuf) -> Result<Vec<u8>, String> {
+    let size = file_size_with_timeout(path.clone()).await?;
+    read_sized_file_with_timeout(path, size).await
+}
+
+// Reads a file already stat'ed at `size` within a timeout scaled to that size
+pub async fn read_sized_file_with_timeout(path: PathBuf, size: u64) -> Result<Vec<u8>, String> {
+    let display = path.display().to_string();
+    let read = tokio::task::spawn_blocking(move || read_stable_file(&path));
+    match tokio::time::timeout(read_timeout(size), read).await {
+        Ok(Ok(Ok(bytes))) => Ok(bytes),
+        Ok(Ok(Err(e))) => Err(format!("Failed to read {}: {}", display, e)),
+        Ok(Err(e)) => Err(format!("Failed to read {}: {}", display, e)),
+        Err(_) => Err(timed_out_reading(&display)),
+    }
+}
+
+const MAX_LINK_HOPS: usize = 32;
+
+// Follows symlinks/junctions at the install location (users often relocate
+// games this way) and returns the canonical directory, failing on link cycles
+pub fn resolve_install_location(install_location: &str) -> Result<PathBuf, String> {
+    let mut current = to_long_path(Path::new(install_location));
+    let mut visited = HashSet::new();
+
+    for _ in 0..MAX_LINK_HOPS {
+        let is_link = fs::symlink_metadata(&current)
+            .map(|m| m.file_type().is_symlink())
+            .unwrap_or(false);
+        if !is_link {
+            return fs::canonicalize(&current)
+                .map_err(|e| format!("Failed to resolve {}: {}", current.display(), e));
+        }
+
+        if !visited.insert(current.clone()) {
+            return Err(format!("Symlink cycle detected at {}", current.display()));
+        }
+
+        let target = fs::read_link(&current)
+            .map_err(|e| format!("Failed to read link {}: {}", current.display(), e))?;
+        current = if target.is_absolute() {
+            target
+        } else {
+            current
+                .parent()
+                .unwrap_or_else(|| Path::new(""))
+                .join(target)
+        };
+    }
+
+    Err(format!(
+        "Too many levels of symbolic links at {}",
+        install_location
+    ))
+}
+
+pub fn get_egstore_manifest_path(install_location: &str, installation_guid: &str) -> PathBuf {
+    let install_dir = resolve_install_location(install_location)
+        .unwrap_or_else(|_| to_long_path(Path::new(install_location)));
+    install_dir
+        .join(".egstore")
+        .join(format!("{}.manifest", installation_guid))
+}
+
+// `get_egstore_manifest_path` for async callers. Resolving the install
+// location touches the disk, which hangs on a dead network drive, so it runs on
+// the blocking pool and falls back to the unresolved path after the probe timeout.
+pub async fn egstore_manifest_path(install_location: &str, installation_guid: &str) -> PathBuf {
+    let fallback = to_long_path(Path::new(install_location))
+        .join(".egstore")
+        .join(format!("{}.manifest", installation_guid));
+    let (install_location, installation_guid) =
+        (install_location.to_string(), installation_guid.to_string());
+    tokio::time::timeout(
+        PATH_PROBE_TIMEOUT,
+        tokio::task::spawn_blocking(move || {
+            get_egstore_manifest_path(&install_location, &installation_guid)
+        }),
+    )
+    .await
+    .ok()
+    .and_then(Result::ok)
+    .unwrap_or(fallback)
+}
+
+// Decodes launcher JSON files regardless of BOMs or UTF-16 encoding, reporting
+// where decoding failed instead of a bare "invalid data" IO error
+pub fn decode_text_file(path: &Path, bytes: &[u8]) -> Result<String, String> {
+    if let Some(rest) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        return decode_utf8(path, rest);
+    }
+
+    let utf16_le = bytes.strip_prefix(&[0xFF, 0xFE]);
+    let utf16_be = bytes.strip_prefix(&[0xFE, 0xFF]);
+    if let Some((rest, little_endian)) = utf16_le
+        .map(|rest| (rest, true))
+        .or_else(|| utf16_be.map(|rest| (rest, false)))
+    {
+        let units = rest
+            .chunks_exact(2)
+            .map(|pair| {
+                if little_endian {
+                    u16::from_le_bytes([pair[0], pair[1]])
+                } else {
+                    u16::from_be_bytes([pair[0], pair[1]])
+                }
+            })
+            .collect::<Vec<_>>();
+        return String::from_utf16(&units)
+            .map_err(|e| format!("{} is not valid UTF-16: {}", path.display(), e));
+    }
+
+    decode_utf8(path, bytes)
+}
+
+fn decode_utf8(path: &Path, bytes: &[u8]) -> Result<String, String> {
+    std::str::from_utf8(bytes).map(str::to_string).map_err(|e| {
+        format!(
+            "{} is not valid UTF-8 (invalid byte at offset {})",
+            path.display(),
+            e.valid_up_to()
+        )
+    })
+}
+
+// Converts an absolute Windows path to its `\\?\` form so deeply nested installs
+// aren't cut off by MAX_PATH. A no-op on other platforms.
+#[cfg(target_os = "windows")]
+pub fn to_long_path(path: &Path) -> PathBuf {
+    let raw = path.to_string_lossy().replace('/', "\\");
+    if raw.starts_with(r"\\?\") || !path.is_absolute() {
+        return PathBuf::from(raw);
+    }
+    match raw.strip_prefix(r"\\") {
+        Some(unc) => PathBuf::from(format!(r"\\?\UNC\{}", unc)),
+        None => PathBuf::from(format!(r"\\?\{}", raw)),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn to_long_path(path: &Path) -> PathBuf {
+    path.to_path_buf()
+}
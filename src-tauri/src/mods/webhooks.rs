@@ -0,0 +1,105 @@
+use super::models::{Settings, WebhookPayload};
+use serde::Serialize;
+use serde_json::Value;
+
+// Payload keys holding local folders; their values (and any other mention of
+// them or of the home directory) are masked before the payload leaves
+const LOCAL_PATH_KEYS: [&str; 3] = ["install_location", "moved_from", "wine_prefix"];
+
+fn collect_local_paths(value: &Value, paths: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map {
+                match v.as_str() {
+                    Some(path) if LOCAL_PATH_KEYS.contains(&key.as_str()) && !path.is_empty() => {
+                        paths.push(path.to_string())
+                    }
+                    _ => collect_local_paths(v, paths),
+                }
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|v| collect_local_paths(v, paths)),
+        _ => {}
+    }
+}
+
+fn mask_local_paths(value: &mut Value, paths: &[String]) {
+    match value {
+        Value::Object(map) => map.values_mut().for_each(|v| mask_local_paths(v, paths)),
+        Value::Array(items) => items.iter_mut().for_each(|v| mask_local_paths(v, paths)),
+        Value::String(text) => {
+            for path in paths {
+                if text.contains(path.as_str()) {
+                    *text = text.replace(path.as_str(), "<local path>");
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+// Masks the folders named in `data`, `known_paths` and the home directory
+pub fn redact_local_paths(mut data: Value, known_paths: &[String]) -> Value {
+    let mut paths = known_paths
+        .iter()
+        .filter(|path| !path.is_empty())
+        .cloned()
+        .collect::<Vec<_>>();
+    collect_local_paths(&data, &mut paths);
+    if let Some(home) = dirs::home_dir() {
+        paths.push(home.to_string_lossy().to_string());
+    }
+    // Longest first, so a folder inside another isn't left half-masked
+    paths.sort_by_key(|path| std::cmp::Reverse(path.len()));
+    mask_local_paths(&mut data, &paths);
+    data
+}
+
+// POSTs `data` to every enabled webhook subscribed to `event`. Delivery happens in
+// the background and failures are only logged; webhooks never block the caller.
+pub fn dispatch(settings: &Settings, event: &str, data: impl Serialize) {
+    // Webhooks are named by position in logs: their URLs usually embed a token
+    let targets = settings
+        .webhooks
+        .iter()
+        .enumerate()
+        .filter(|(_, w)| w.enabled && (w.events.is_empty() || w.events.iter().any(|e| e == event)))
+        .map(|(index, w)| (index + 1, w.url.clone()))
+        .collect::<Vec<_>>();
+    if targets.is_empty() {
+        return;
+    }
+
+    let payload = WebhookPayload {
+        event: event.to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        client_version: env!("CARGO_PKG_VERSION").to_string(),
+        data: redact_local_paths(serde_json::to_value(data).unwrap_or_default(), &[]),
+    };
+
+    tauri::async_runtime::spawn(async move {
+        for (number, url) in targets {
+            // Third-party endpoints don't get the egdata client id
+            match crate::EXTERNAL_HTTP_CLIENT
+                .post(&url)
+                .json(&payload)
+                .send()
+                .await
+            {
+                Ok(resp) if resp.status().is_success() => {}
+                Ok(resp) => eprintln!(
+                    "Webhook #{} rejected {}: {}",
+                    number,
+                    payload.event,
+                    resp.status()
+                ),
+                Err(e) => eprintln!(
+                    "Failed to deliver webhook {} to webhook #{}: {}",
+                    payload.event,
+                    number,
+                    e.without_url()
+                ),
+            }
+        }
+    });
+}
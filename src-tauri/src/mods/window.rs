@@ -0,0 +1,137 @@
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+// On macOS the app drops to the Accessory activation policy while hidden so it
+// behaves like a menu-bar utility, and gets its Dock icon back when shown.
+pub fn show_main_window(app_handle: &AppHandle) {
+    #[cfg(target_os = "macos")]
+    if let Err(e) = app_handle.set_activation_policy(tauri::ActivationPolicy::Regular) {
+        eprintln!("Failed to restore Dock icon: {}", e);
+    }
+
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+pub fn hide_main_window(app_handle: &AppHandle) {
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.hide();
+    }
+
+    #[cfg(target_os = "macos")]
+    if let Err(e) = app_handle.set_activation_policy(tauri::ActivationPolicy::Accessory) {
+        eprintln!("Failed to hide Dock icon: {}", e);
+    }
+}
+
+pub fn toggle_main_window(app_handle: &AppHandle) {
+    let visible = app_handle
+        .get_webview_window("main")
+        .is_some_and(|window| window.is_visible().unwrap_or(false));
+    if visible {
+        hide_main_window(app_handle);
+    } else {
+        show_main_window(app_handle);
+    }
+}
+
+pub const POPOVER_LABEL: &str = "popover";
+const POPOVER_WIDTH: f64 = 380.0;
+const POPOVER_HEIGHT: f64 = 560.0;
+// Gap between the menu bar item and the popover
+const POPOVER_MARGIN: f64 = 4.0;
+// Clicking the tray icon while the popover is open first takes focus away from
+// it; a click this soon after that blur is the one that should close it
+const BLUR_CLICK_WINDOW: Duration = Duration::from_millis(300);
+
+static LAST_BLUR_HIDE: Lazy<Mutex<Option<Instant>>> = Lazy::new(|| Mutex::new(None));
+
+fn hidden_by_recent_blur() -> bool {
+    LAST_BLUR_HIDE
+        .lock()
+        .ok()
+        .and_then(|mut last| last.take())
+        .is_some_and(|hidden_at| hidden_at.elapsed() < BLUR_CLICK_WINDOW)
+}
+
+// Opens (or closes) the compact window anchored under the tray icon at `anchor`.
+// The window is created on first use and hides itself when it loses focus.
+pub fn toggle_popover(app_handle: &AppHandle, anchor: tauri::Rect) -> Result<(), String> {
+    let popover = match app_handle.get_webview_window(POPOVER_LABEL) {
+        Some(popover) => popover,
+        None => create_popover(app_handle)?,
+    };
+
+    if popover.is_visible().unwrap_or(false) {
+        let _ = popover.hide();
+        return Ok(());
+    }
+    if hidden_by_recent_blur() {
+        return Ok(());
+    }
+
+    let scale = popover.scale_factor().map_err(|e| e.to_string())?;
+    let position = popover_position(anchor, scale, popover.current_monitor().ok().flatten());
+    popover
+        .set_position(position)
+        .map_err(|e| format!("Failed to position popover: {}", e))?;
+    let _ = popover.show();
+    let _ = popover.set_focus();
+    Ok(())
+}
+
+fn create_popover(app_handle: &AppHandle) -> Result<tauri::WebviewWindow, String> {
+    let popover = tauri::WebviewWindowBuilder::new(
+        app_handle,
+        POPOVER_LABEL,
+        tauri::WebviewUrl::App("index.html?view=popover".into()),
+    )
+    .title("egdata-client")
+    .inner_size(POPOVER_WIDTH, POPOVER_HEIGHT)
+    .resizable(false)
+    .decorations(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .visible(false)
+    .build()
+    .map_err(|e| format!("Failed to create popover window: {}", e))?;
+
+    let popover_for_events = popover.clone();
+    popover.on_window_event(move |event| {
+        if let tauri::WindowEvent::Focused(false) = event {
+            if popover_for_events.is_visible().unwrap_or(false) {
+                if let Ok(mut last) = LAST_BLUR_HIDE.lock() {
+                    *last = Some(Instant::now());
+                }
+            }
+            let _ = popover_for_events.hide();
+        }
+    });
+    Ok(popover)
+}
+
+// Centers the popover under the tray item, kept inside the monitor's bounds
+fn popover_position(
+    anchor: tauri::Rect,
+    scale: f64,
+    monitor: Option<tauri::Monitor>,
+) -> tauri::PhysicalPosition<f64> {
+    let anchor_position = anchor.position.to_physical::<f64>(scale);
+    let anchor_size = anchor.size.to_physical::<f64>(scale);
+    let width = POPOVER_WIDTH * scale;
+
+    let mut x = anchor_position.x + anchor_size.width / 2.0 - width / 2.0;
+    let y = anchor_position.y + anchor_size.height + POPOVER_MARGIN * scale;
+
+    if let Some(monitor) = monitor {
+        let left = monitor.position().x as f64;
+        let right = left + monitor.size().width as f64;
+        x = x.clamp(left, (right - width).max(left));
+    }
+
+    tauri::PhysicalPosition::new(x, y)
+}
@@ -0,0 +1,93 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Location of the launcher's manifests relative to a prefix's `drive_c`
+const PREFIX_MANIFESTS_PATH: &str = "drive_c/ProgramData/Epic/EpicGamesLauncher/Data/Manifests";
+
+pub fn manifests_path_in_prefix(prefix: &Path) -> PathBuf {
+    prefix.join(PREFIX_MANIFESTS_PATH)
+}
+
+#[cfg(target_os = "linux")]
+fn subdirectories(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.is_dir())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// Looks for Wine prefixes created by common tools (plain Wine, Lutris, Bottles,
+// Heroic, Steam Proton) that contain an Epic Games Launcher installation
+#[cfg(target_os = "linux")]
+pub fn find_wine_prefixes() -> Vec<PathBuf> {
+    let Some(home) = dirs::home_dir() else {
+        return Vec::new();
+    };
+
+    let mut candidates = vec![home.join(".wine")];
+    if let Ok(prefix) = std::env::var("WINEPREFIX") {
+        candidates.push(PathBuf::from(prefix));
+    }
+
+    // Lutris and Heroic default to one prefix per game
+    candidates.extend(subdirectories(&home.join("Games")));
+    candidates.extend(subdirectories(&home.join("Games/Heroic/Prefixes")));
+
+    // Bottles, native and Flatpak
+    candidates.extend(subdirectories(&home.join(".local/share/bottles/bottles")));
+    candidates.extend(subdirectories(
+        &home.join(".var/app/com.usebottles.bottles/data/bottles/bottles"),
+    ));
+
+    // Steam Proton keeps the prefix in `compatdata/<appid>/pfx`
+    for steam_root in [".steam/steam", ".local/share/Steam"] {
+        for compat in subdirectories(&home.join(steam_root).join("steamapps/compatdata")) {
+            candidates.push(compat.join("pfx"));
+        }
+    }
+
+    let mut prefixes = Vec::new();
+    for candidate in candidates {
+        if !manifests_path_in_prefix(&candidate).is_dir() {
+            continue;
+        }
+        let canonical = fs::canonicalize(&candidate).unwrap_or(candidate);
+        if !prefixes.contains(&canonical) {
+            prefixes.push(canonical);
+        }
+    }
+    prefixes
+}
+
+// Maps a Windows path recorded inside a prefix (e.g. `D:\Games\Foo`) to the
+// host path, following the prefix's `dosdevices` drive mappings
+pub fn to_host_path(prefix: &Path, windows_path: &str) -> PathBuf {
+    let normalized = windows_path.replace('\\', "/");
+    let bytes = normalized.as_bytes();
+    if bytes.len() < 2 || !bytes[0].is_ascii_alphabetic() || bytes[1] != b':' {
+        return PathBuf::from(windows_path);
+    }
+
+    let drive = normalized[..1].to_lowercase();
+    let rest = normalized[2..].trim_start_matches('/');
+    let drive_root = fs::canonicalize(prefix.join("dosdevices").join(format!("{}:", drive)))
+        .unwrap_or_else(|_| {
+            if drive == "c" {
+                prefix.join("drive_c")
+            } else {
+                // Wine maps Z: to the host root by default
+                PathBuf::from("/")
+            }
+        });
+
+    if rest.is_empty() {
+        drive_root
+    } else {
+        drive_root.join(rest)
+    }
+}
@@ -1,6 +1,8 @@
 import React from 'react';
 import { Icon } from "@iconify/react";
 import { Toaster, toast } from 'sonner';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { GameScanner } from "./components/game-scanner";
 import { GameListHeader } from "./components/game-list-header";
 import { LogConsole } from "./components/log-console";
@@ -17,6 +19,44 @@ import { Tooltip, TooltipContent, TooltipTrigger } from "@/components/ui/tooltip
 import { cn } from "@/lib/utils";
 import { Loader2 } from "lucide-react";
 
+// Explanations for the `error_code` the backend attaches to rejected uploads
+const UPLOAD_ERROR_MESSAGES: Record<string, string> = {
+  invalid_manifest: 'The server could not read this manifest.',
+  unsupported_version: 'The server no longer accepts uploads from this version. Please update the client.',
+  too_large: 'This manifest is larger than the server accepts.',
+  unauthorized: "The server refused this client's upload signature.",
+};
+
+interface DailyDigest {
+  since: string;
+  until: string;
+  uploaded: number;
+  installed: number;
+  updated: number;
+  failed: number;
+}
+
+// e.g. "3 new builds uploaded, 2 games updated, 1 failure"
+function digestSummary(digest: DailyDigest) {
+  const plural = (count: number, one: string, many: string) =>
+    `${count} ${count === 1 ? one : many}`;
+  return [
+    digest.uploaded > 0 && plural(digest.uploaded, 'new build uploaded', 'new builds uploaded'),
+    digest.installed > 0 && plural(digest.installed, 'game installed', 'games installed'),
+    digest.updated > 0 && plural(digest.updated, 'game updated', 'games updated'),
+    digest.failed > 0 && plural(digest.failed, 'failure', 'failures'),
+  ]
+    .filter(Boolean)
+    .join(', ');
+}
+
+interface ReleaseNote {
+  tag_name: string;
+  name: string | null;
+  body: string | null;
+  html_url: string;
+}
+
 function maybeParseJson(json: string) {
   try {
     return JSON.parse(json);
@@ -76,9 +116,15 @@ export default function App() {
         toast.success('Manifest uploaded successfully!');
       } else if (result?.status === 'already_uploaded') {
         toast.info(result?.message || 'Manifest already uploaded');
+      } else if (result?.status === 'hash_mismatch') {
+        toast.warning('Manifest does not match its .item file; the game may be mid-update.');
+      } else if (result?.status === 'invalid_manifest') {
+        toast.error(result?.message || 'Manifest file is not a valid launcher manifest');
       } else if (result?.status === 'failed') {
         const parsed = maybeParseJson(result?.message || '');
-        if (parsed && parsed.error) {
+        if (result?.error_code && UPLOAD_ERROR_MESSAGES[result.error_code]) {
+          toast.error(UPLOAD_ERROR_MESSAGES[result.error_code]);
+        } else if (parsed && parsed.error) {
           toast.error(parsed.error);
         } else {
           toast.error('Upload failed.');
@@ -89,6 +135,34 @@ export default function App() {
     }
   }, [uploadMutation.isSuccess, uploadMutation.isError, uploadMutation.data]);
 
+  // Release notes are shown once, the first launch after an update
+  React.useEffect(() => {
+    invoke<ReleaseNote | null>('get_whats_new')
+      .then((note) => {
+        if (note) {
+          toast.info(`What's new in ${note.name || note.tag_name}`, {
+            description: note.body ?? undefined,
+            duration: 15000,
+          });
+        }
+      })
+      .catch((error) => console.error('Failed to load release notes:', error));
+  }, []);
+
+  // System notifications only exist on Windows; the digest is shown here everywhere
+  React.useEffect(() => {
+    const unlisten = listen<DailyDigest>('daily-digest', (event) => {
+      toast.info('Your daily EGData summary', {
+        description: digestSummary(event.payload),
+        duration: 10000,
+      });
+    });
+
+    return () => {
+      unlisten.then(f => f());
+    };
+  }, []);
+
   return (
     <div className="flex flex-col h-screen bg-background text-foreground overflow-hidden">
       <Toaster position="top-right" richColors className="z-[9999]" />
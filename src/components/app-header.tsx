@@ -19,6 +19,12 @@ import {
 } from "@/components/ui/popover";
 import { ScrollArea } from "@/components/ui/scroll-area";
 
+// 0 is a valid value (disables the task), so only fall back on unparsable input
+const parseIntervalInput = (value: string) => {
+  const parsed = parseInt(value);
+  return Number.isNaN(parsed) ? 1 : Math.max(0, parsed);
+};
+
 export const AppHeader: React.FC = () => {
   const { settings, isLoading, updateSettings } = useSettings();
 
@@ -109,14 +115,14 @@ export const AppHeader: React.FC = () => {
 
                 <div className="space-y-2">
                   <Label htmlFor="upload-interval">Upload Interval (minutes)</Label>
-                  <p className="text-xs text-muted-foreground">How often to check for new games</p>
+                  <p className="text-xs text-muted-foreground">How often to check for new games (0 disables)</p>
                   <Input
                     id="upload-interval"
                     type="number"
-                    min={1}
+                    min={0}
                     max={10080}
                     value={settings.upload_interval}
-                    onChange={(e) => updateSettings({ upload_interval: parseInt(e.target.value) || 1 })}
+                    onChange={(e) => updateSettings({ upload_interval: parseIntervalInput(e.target.value) })}
                   />
                 </div>
 
@@ -124,14 +130,14 @@ export const AppHeader: React.FC = () => {
 
                 <div className="space-y-2">
                   <Label htmlFor="scan-interval">Scan Interval (minutes)</Label>
-                  <p className="text-xs text-muted-foreground">How often to scan for game changes</p>
+                  <p className="text-xs text-muted-foreground">How often to scan for game changes (0 disables)</p>
                   <Input
                     id="scan-interval"
                     type="number"
-                    min={1}
-                    max={60}
+                    min={0}
+                    max={1440}
                     value={settings.scan_interval_minutes}
-                    onChange={(e) => updateSettings({ scan_interval_minutes: parseInt(e.target.value) || 1 })}
+                    onChange={(e) => updateSettings({ scan_interval_minutes: parseIntervalInput(e.target.value) })}
                   />
                 </div>
 
@@ -158,6 +164,66 @@ export const AppHeader: React.FC = () => {
 
                 <Separator />
 
+                <div className="flex items-start gap-2">
+                  <Checkbox
+                    id="upload-on-detect"
+                    checked={settings.upload_on_detect ?? true}
+                    onCheckedChange={(checked) => updateSettings({ upload_on_detect: checked as boolean })}
+                  />
+                  <div className="space-y-1">
+                    <Label htmlFor="upload-on-detect" className="text-sm font-normal cursor-pointer">
+                      Upload new builds as soon as they're detected
+                    </Label>
+                    <p className="text-xs text-muted-foreground">Instead of waiting for the next upload interval</p>
+                  </div>
+                </div>
+
+                <div className="flex items-start gap-2">
+                  <Checkbox
+                    id="daily-digest"
+                    checked={settings.daily_digest_enabled || false}
+                    onCheckedChange={(checked) => updateSettings({ daily_digest_enabled: checked as boolean })}
+                  />
+                  <div className="space-y-1">
+                    <Label htmlFor="daily-digest" className="text-sm font-normal cursor-pointer">
+                      Daily digest
+                    </Label>
+                    <p className="text-xs text-muted-foreground">One summary notification a day instead of one per failed upload</p>
+                  </div>
+                </div>
+
+                <div className="flex items-start gap-2">
+                  <Checkbox
+                    id="share-host-details"
+                    checked={settings.share_host_details || false}
+                    onCheckedChange={(checked) => updateSettings({ share_host_details: checked as boolean })}
+                  />
+                  <div className="space-y-1">
+                    <Label htmlFor="share-host-details" className="text-sm font-normal cursor-pointer">
+                      Share OS, OS version and CPU architecture with uploads
+                    </Label>
+                    <p className="text-xs text-muted-foreground">Helps tell platform-specific builds apart</p>
+                  </div>
+                </div>
+
+                <Separator />
+
+                <div className="flex items-start gap-2">
+                  <Checkbox
+                    id="linux-autostart"
+                    checked={settings.linux_autostart || false}
+                    onCheckedChange={(checked) => updateSettings({ linux_autostart: checked as boolean })}
+                  />
+                  <div className="space-y-1">
+                    <Label htmlFor="linux-autostart" className="text-sm font-normal cursor-pointer">
+                      Start at login (Linux)
+                    </Label>
+                    <p className="text-xs text-muted-foreground">Applies after restart</p>
+                  </div>
+                </div>
+
+                <Separator />
+
                 <p className="text-xs text-muted-foreground text-right">
                   Settings are saved automatically
                 </p>
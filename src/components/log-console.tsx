@@ -8,10 +8,17 @@ import { ScrollArea } from "@/components/ui/scroll-area";
 import { cn } from "@/lib/utils";
 
 interface LogEntry {
+  id: string;
   message: string;
   timestamp: string;
 }
 
+// Backend timestamps are RFC 3339 UTC; show them in local time
+const formatTimestamp = (timestamp: string) => {
+  const date = new Date(timestamp);
+  return isNaN(date.getTime()) ? timestamp : date.toLocaleTimeString();
+};
+
 interface LogConsoleProps {
   logs: LogEntry[];
   onClear?: () => void;
@@ -65,9 +72,9 @@ export const LogConsole: React.FC<LogConsoleProps> = ({ logs, onClear }) => {
               <div className="text-muted-foreground italic">No logs to display...</div>
             ) : (
               <div className="flex flex-col-reverse">
-                {logs.map((log, index) => (
+                {logs.map((log) => (
                   <div
-                    key={index}
+                    key={log.id}
                     className={cn(
                       "py-0.5 inline-flex w-full gap-2",
                       log.message.includes("ERROR") && "text-destructive",
@@ -75,7 +82,7 @@ export const LogConsole: React.FC<LogConsoleProps> = ({ logs, onClear }) => {
                     )}
                   >
                     <span className="text-muted-foreground shrink-0">
-                      {log.timestamp}
+                      {formatTimestamp(log.timestamp)}
                     </span>
                     <span>{log.message}</span>
                   </div>
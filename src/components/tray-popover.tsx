@@ -0,0 +1,82 @@
+import React from "react";
+import { Icon } from "@iconify/react";
+import { useMutation } from "@tanstack/react-query";
+import { invoke } from "@tauri-apps/api/core";
+import { Toaster, toast } from 'sonner';
+import { useGameLibrary, type Game } from "../hooks/use-scan-games";
+import { Button } from "@/components/ui/button";
+import { Badge } from "@/components/ui/badge";
+import { Separator } from "@/components/ui/separator";
+import { ScrollArea } from "@/components/ui/scroll-area";
+import { Loader2 } from "lucide-react";
+
+// Compact view loaded by the menu bar popover (`index.html?view=popover`):
+// library at a glance plus the common actions; everything else is in the main window
+export const TrayPopover: React.FC = () => {
+  const { games, isScanning, scanGames } = useGameLibrary();
+
+  const uploadAllMutation = useMutation({
+    mutationFn: async () => {
+      return await invoke<{ status: string }[]>('upload_all_manifests');
+    },
+    onSuccess: (results) => {
+      const uploaded = results.filter(r => r.status === 'uploaded').length;
+      const failed = results.filter(r => r.status === 'failed').length;
+      if (failed > 0) {
+        toast.error(`${uploaded} uploaded, ${failed} failed`);
+      } else {
+        toast.success(`${uploaded} uploaded`);
+      }
+    },
+    onError: () => toast.error('Upload failed'),
+  });
+
+  return (
+    <div className="flex flex-col h-screen bg-background text-foreground overflow-hidden rounded-lg border border-border">
+      <Toaster position="bottom-center" richColors />
+      <div className="flex items-center justify-between px-3 py-2">
+        <span className="font-semibold">egdata-client</span>
+        <Badge variant="secondary">{games.length} games</Badge>
+      </div>
+      <Separator />
+
+      <ScrollArea className="flex-1">
+        <div className="divide-y divide-border">
+          {games.map((game: Game) => (
+            <div key={game.id} className="flex items-center gap-2 px-3 py-2">
+              <img
+                src={game.icon}
+                alt={game.name}
+                className="w-8 h-8 rounded object-cover bg-muted"
+              />
+              <div className="flex-1 min-w-0">
+                <div className="text-sm truncate">{game.name}</div>
+                <div className="text-xs text-muted-foreground truncate font-mono">{game.version}</div>
+              </div>
+            </div>
+          ))}
+        </div>
+      </ScrollArea>
+
+      <Separator />
+      <div className="flex items-center gap-2 p-2">
+        <Button size="sm" variant="outline" disabled={isScanning} onClick={() => scanGames()}>
+          {isScanning ? <Loader2 className="h-4 w-4 animate-spin" /> : <Icon icon="lucide:refresh-cw" width={16} />}
+          <span>Scan</span>
+        </Button>
+        <Button
+          size="sm"
+          variant="outline"
+          disabled={uploadAllMutation.isPending}
+          onClick={() => uploadAllMutation.mutate()}
+        >
+          {uploadAllMutation.isPending ? <Loader2 className="h-4 w-4 animate-spin" /> : <Icon icon="lucide:upload" width={16} />}
+          <span>Upload all</span>
+        </Button>
+        <Button size="sm" className="ml-auto" onClick={() => invoke('show_window')}>
+          Open
+        </Button>
+      </div>
+    </div>
+  );
+};
@@ -6,25 +6,16 @@ import { logsCollection } from '../lib/store';
 export interface BackendLogEvent {
   level: string;
   message: string;
-  timestamp: string;
+  timestamp: string; // RFC 3339, UTC
+  seq: number;
 }
 
 export function useBackendLogs(onLog?: (message: string) => void) {
-  const handleLogEvent = useCallback((event: any) => {
-    const logData = event.payload as BackendLogEvent;
+  const handleLogEvent = useCallback((logData: BackendLogEvent) => {
     const formattedMessage = `[${logData.timestamp}] ${logData.level}: ${logData.message}`;
-    
-    // Add to TanStack DB store for real-time updates
-    logsCollection.insert({
-      id: `${Date.now()}-${Math.random()}`,
-      level: logData.level,
-      message: logData.message,
-      timestamp: logData.timestamp,
-      formattedMessage,
-      createdAt: new Date().toISOString()
-    });
-    
-    // Call the optional callback for backward compatibility
+
+    // The store's log-events listener records the entry (keyed by seq);
+    // call the optional callback for backward compatibility
     if (onLog) {
       onLog(formattedMessage);
     }
@@ -35,7 +26,10 @@ export function useBackendLogs(onLog?: (message: string) => void) {
 
     const setupListener = async () => {
       try {
-        unlisten = await listen('log-event', handleLogEvent);
+        // The backend batches log events; handle them in order
+        unlisten = await listen<BackendLogEvent[]>('log-events', (event) => {
+          event.payload.forEach(handleLogEvent);
+        });
       } catch (error) {
         console.error('Failed to setup backend log listener:', error);
       }
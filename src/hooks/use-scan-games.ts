@@ -1,5 +1,6 @@
 import { useMutation } from '@tanstack/react-query';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import React, { useCallback } from 'react';
 import { useGames, useLogs, gameCollection, logsCollection } from '../lib/store';
 import { useBackendLogs } from './use-backend-logs';
@@ -28,6 +29,25 @@ export interface GameInfo {
   metadata?: GameMetadata;
   installation_guid: string;
   manifest_hash: string;
+  available?: boolean;
+  access_denied?: boolean;
+  moved_from?: string | null;
+  wine_prefix?: string | null;
+  environment?: string;
+  installed?: boolean;
+  uninstalled_at?: string | null;
+  final_uploaded_at?: string | null;
+  format_version?: number;
+  launch_executable?: string;
+  architecture?: string;
+  main_game_app_name?: string;
+  // DLC and addons, nested under their base game by the backend
+  children?: GameInfo[];
+}
+
+// The game list from the backend is grouped; the store keeps one entry per item
+export function flattenGameInfos(gameInfos: GameInfo[]): GameInfo[] {
+  return gameInfos.flatMap(({ children, ...game }) => [game, ...(children ?? [])]);
 }
 
 export interface KeyImage {
@@ -46,6 +66,7 @@ export interface GameMetadata {
 }
 
 export interface LogEntry {
+  id: string;
   message: string;
   timestamp: string;
 }
@@ -63,6 +84,7 @@ export function useScanGames() {
   // Use TanStack DB for real-time logs
   const { data: logsData = [] } = useLogs();
   const logs: LogEntry[] = logsData.map((log: any) => ({
+    id: log.id,
     message: `[${log.level}] ${log.message}`,
     timestamp: log.timestamp || new Date().toISOString(),
   }));
@@ -71,6 +93,7 @@ export function useScanGames() {
     // Logs are now handled by TanStack DB store automatically
     // If you want to add a log with timestamp, you can do so here
     const logEntry = {
+      id: `local-${Date.now()}-${Math.random()}`,
       message,
       timestamp: new Date().toISOString(),
     };
@@ -87,11 +110,19 @@ export function useScanGames() {
     });
   }, [logsData]);
 
-  // Listen for backend log events (still needed for compatibility)
-  useBackendLogs(addLog);
+  // Backend log events are recorded by the store's listener; don't add them twice
+  useBackendLogs();
 
   const toggleConsole = () => setShowConsole(prev => !prev);
 
+  // "View log" on a notification opens the console
+  React.useEffect(() => {
+    const unlisten = listen('show-log-console', () => setShowConsole(true));
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   const formatFileSize = (bytes: number): string => {
     const sizes = ['Bytes', 'KB', 'MB', 'GB', 'TB'];
     if (bytes === 0) return '0 Bytes';
@@ -135,7 +166,7 @@ export function useScanGames() {
   const refetch = React.useCallback(async () => {
     try {
       const gameInfos = await invoke<GameInfo[]>('get_installed_games');
-      const convertedGames = gameInfos.map(convertGameInfo);
+      const convertedGames = flattenGameInfos(gameInfos).map(convertGameInfo);
 
       // Clear existing games first by deleting all current games
       games.forEach(game => {
@@ -6,8 +6,38 @@ export interface Settings {
   concurrency?: number;
   upload_speed_limit?: number;
   allowed_environments?: string[];
-  upload_interval?: number; // in minutes
-  scan_interval_minutes?: number; // in minutes
+  upload_interval?: number; // in minutes, 0 disables
+  scan_interval_minutes?: number; // in minutes, 0 disables
+  metadata_refresh_interval_minutes?: number; // in minutes, 0 disables
+  crash_reporting_enabled?: boolean;
+  metadata_cache_max_entries?: number;
+  metadata_cache_max_bytes?: number;
+  epic_catalog_fallback?: boolean;
+  metadata_timeout_secs?: number;
+  upload_timeout_secs?: number;
+  upload_require_tls12?: boolean;
+  upload_pinned_cert_path?: string | null;
+  telemetry_enabled?: boolean;
+  webhooks?: { url: string; events?: string[]; enabled?: boolean }[];
+  local_api_enabled?: boolean; // applies after restart
+  local_api_port?: number;
+  background_priority?: boolean;
+  upload_endpoints?: string[];
+  environment_endpoints?: Record<string, string>;
+  tray_popover?: boolean; // macOS only; applies after restart
+  sidecar_upload_games?: string[];
+  profiles?: {
+    name: string;
+    scan_interval_minutes: number;
+    upload_interval: number;
+  }[];
+  active_profile?: string | null;
+  auto_upload_enabled?: boolean;
+  auto_scan_enabled?: boolean;
+  daily_digest_enabled?: boolean;
+  share_host_details?: boolean;
+  upload_on_detect?: boolean;
+  linux_autostart?: boolean; // Linux only; applies after restart
 }
 
 export function useSettings() {
@@ -3,9 +3,22 @@ import { useLiveQuery } from '@tanstack/react-db';
 import { eq } from '@tanstack/db';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
-import type { Game, GameInfo } from '../hooks/use-scan-games';
+import { flattenGameInfos } from '../hooks/use-scan-games';
+import type { Game, GameInfo, GameMetadata } from '../hooks/use-scan-games';
 import type { Settings } from '../hooks/use-settings';
 
+interface GamesDelta {
+  added: string[];
+  changed: string[];
+  removed: { app_name: string; catalog_item_id: string }[];
+}
+
+interface MetadataUpdate {
+  app_name: string;
+  catalog_item_id: string;
+  metadata: GameMetadata;
+}
+
 
 // Game Collection
 export const gameCollection = createCollection(
@@ -74,7 +87,7 @@ export async function initializeStore() {
   try {
     // Load initial games data
     const gameInfos = await invoke<GameInfo[]>('get_installed_games');
-    const games = gameInfos.map(convertGameInfo);
+    const games = flattenGameInfos(gameInfos).map(convertGameInfo);
 
     // Populate games collection with fresh data, assuming upsert behavior
     for (const game of games) {
@@ -97,14 +110,22 @@ export async function initializeStore() {
 
 // Set up real-time event listeners
 function setupRealtimeListeners() {
-  // Listen for games updates from backend
-  listen<GameInfo[]>('games-updated', async (event) => {
+  // The backend only sends which games changed; details are fetched on demand
+  listen<GamesDelta>('games-updated', async (event) => {
     try {
-      const gameInfos = event.payload;
-      const games = gameInfos.map(convertGameInfo);
+      const { added, changed, removed } = event.payload;
+      for (const { catalog_item_id } of removed) {
+        if (gameCollection.has(catalog_item_id)) {
+          gameCollection.delete(catalog_item_id);
+        }
+      }
+
+      const appNames = [...added, ...changed];
+      if (appNames.length === 0) return;
+      const gameInfos = await invoke<GameInfo[]>('get_games', { appNames });
 
       // Insert new games, assuming upsert behavior from `insert`
-      for (const game of games) {
+      for (const game of gameInfos.map(convertGameInfo)) {
         gameCollection.insert(game as any);
       }
     } catch (error) {
@@ -112,14 +133,35 @@ function setupRealtimeListeners() {
     }
   });
 
-  // Listen for log events
-  listen('log-event', (event: any) => {
-    const logEntry = {
-      id: Date.now().toString(),
-      timestamp: new Date().toISOString(),
-      ...event.payload
-    };
-    logsCollection.insert(logEntry);
+  // Listen for metadata resolved for a single game, so its card updates in place
+  listen<MetadataUpdate>('metadata-updated', (event) => {
+    const { catalog_item_id, metadata } = event.payload;
+    if (!gameCollection.has(catalog_item_id)) return;
+    const { coverImage, icon } = gameImages(metadata, event.payload.app_name);
+    gameCollection.update(catalog_item_id, (draft: any) => {
+      draft.name = metadata.title || draft.name;
+      draft.coverImage = coverImage;
+      draft.icon = icon;
+    });
+  });
+
+  // Listen for log events, delivered in batches in the order they were logged
+  listen<any[]>('log-events', (event) => {
+    event.payload.forEach((log) => {
+      // seq is unique per backend session, so it is a stable key across batches
+      logsCollection.insert({
+        id: `log-${log.seq}`,
+        timestamp: new Date().toISOString(),
+        ...log
+      });
+    });
+  });
+
+  // Listen for settings reloaded from an externally edited settings.json
+  listen<Settings>('settings-updated', (event) => {
+    settingsCollection.update('current', (draft: any) => {
+      Object.assign(draft, event.payload);
+    });
   });
 
   // Listen for upload status updates
@@ -130,18 +172,10 @@ function setupRealtimeListeners() {
   });
 }
 
-// Helper function to convert GameInfo to Game (same as in use-scan-games.ts)
-function convertGameInfo(gameInfo: GameInfo): Game {
-  const formatFileSize = (bytes: number): string => {
-    const sizes = ['Bytes', 'KB', 'MB', 'GB', 'TB'];
-    if (bytes === 0) return '0 Bytes';
-    const i = Math.floor(Math.log(bytes) / Math.log(1024));
-    return Math.round(bytes / Math.pow(1024, i) * 100) / 100 + ' ' + sizes[i];
-  };
-
-  const metadata = gameInfo.metadata;
-  let coverImage = `https://img.heroui.chat/image/game?w=400&h=600&seed=${gameInfo.app_name}`;
-  let icon = `https://img.heroui.chat/image/game?w=100&h=100&seed=${gameInfo.app_name}`;
+// Cover and icon from the metadata key images, with placeholder fallbacks
+function gameImages(metadata: GameMetadata | undefined, appName: string) {
+  let coverImage = `https://img.heroui.chat/image/game?w=400&h=600&seed=${appName}`;
+  let icon = `https://img.heroui.chat/image/game?w=100&h=100&seed=${appName}`;
 
   if (metadata?.keyImages) {
     const tallCover = metadata.keyImages.find(img => img.type === 'DieselGameBoxTall');
@@ -155,6 +189,21 @@ function convertGameInfo(gameInfo: GameInfo): Game {
     }
   }
 
+  return { coverImage, icon };
+}
+
+// Helper function to convert GameInfo to Game (same as in use-scan-games.ts)
+function convertGameInfo(gameInfo: GameInfo): Game {
+  const formatFileSize = (bytes: number): string => {
+    const sizes = ['Bytes', 'KB', 'MB', 'GB', 'TB'];
+    if (bytes === 0) return '0 Bytes';
+    const i = Math.floor(Math.log(bytes) / Math.log(1024));
+    return Math.round(bytes / Math.pow(1024, i) * 100) / 100 + ' ' + sizes[i];
+  };
+
+  const metadata = gameInfo.metadata;
+  const { coverImage, icon } = gameImages(metadata, gameInfo.app_name);
+
   return {
     id: gameInfo.catalog_item_id,
     name: metadata?.title || gameInfo.display_name,
@@ -185,6 +234,7 @@ export function useLogs() {
   return useLiveQuery((q: any) =>
     q.from({ log: logsCollection })
       .orderBy(({ log }: any) => log.timestamp, 'desc')
+      .orderBy(({ log }: any) => log.seq ?? 0, 'desc')
   );
 }
 
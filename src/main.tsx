@@ -1,6 +1,7 @@
 import React from "react";
 import ReactDOM from "react-dom/client";
 import App from "./App";
+import { TrayPopover } from "./components/tray-popover";
 import "./App.css"
 import { QueryClient, QueryClientProvider } from '@tanstack/react-query';
 import { initializeStore } from './lib/store';
@@ -8,6 +9,9 @@ import { TooltipProvider } from "@/components/ui/tooltip";
 
 const queryClient = new QueryClient();
 
+// The menu bar popover loads the same bundle with `?view=popover`
+const isPopover = new URLSearchParams(window.location.search).get("view") === "popover";
+
 // Initialize TanStack DB store
 initializeStore().catch(console.error);
 
@@ -15,7 +19,7 @@ ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
   <React.StrictMode>
     <QueryClientProvider client={queryClient}>
       <TooltipProvider>
-        <App />
+        {isPopover ? <TrayPopover /> : <App />}
       </TooltipProvider>
     </QueryClientProvider>
   </React.StrictMode>,